@@ -7,9 +7,9 @@ use ndarray::{Array1, ArrayViewD, Axis, array, concatenate, s};
 use ort::{
 	execution_providers::CUDAExecutionProvider,
 	inputs,
+	sampling::top_k_sample,
 	session::{Session, builder::GraphOptimizationLevel}
 };
-use rand::Rng;
 use tokenizers::Tokenizer;
 
 const PROMPT: &str = "The corsac fox (Vulpes corsac), also known simply as a corsac, is a medium-sized fox found in";
@@ -57,19 +57,9 @@ fn main() -> ort::Result<()> {
 		let outputs = session.run(inputs![array]?)?;
 		let generated_tokens: ArrayViewD<f32> = outputs["output1"].try_extract_tensor()?;
 
-		// Collect and sort logits
-		let probabilities = &mut generated_tokens
-			.slice(s![0, 0, -1, ..])
-			.insert_axis(Axis(0))
-			.to_owned()
-			.iter()
-			.cloned()
-			.enumerate()
-			.collect::<Vec<_>>();
-		probabilities.sort_unstable_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Less));
-
 		// Sample using top-k sampling
-		let token = probabilities[rng.gen_range(0..=TOP_K)].0;
+		let logits = generated_tokens.slice(s![0, 0, -1, ..]);
+		let token = top_k_sample(logits, TOP_K, &mut rng);
 		tokens = concatenate![Axis(0), tokens, array![token.try_into().unwrap()]];
 
 		let token_str = tokenizer.decode(&[token as _], true).unwrap();
@@ -937,6 +937,61 @@ pub struct OrtTrainingApi {
 				graph_output_names: *const *const c_char
 			) -> OrtStatusPtr
 		)
+	>,
+	pub SetSeed: ::std::option::Option<_system!(unsafe fn(seed: i64) -> OrtStatusPtr)>,
+	pub TrainingSessionGetTrainingModelInputCount: ::std::option::Option<_system!(unsafe fn(sess: *const OrtTrainingSession, out: *mut usize) -> OrtStatusPtr)>,
+	pub TrainingSessionGetEvalModelInputCount: ::std::option::Option<_system!(unsafe fn(sess: *const OrtTrainingSession, out: *mut usize) -> OrtStatusPtr)>,
+	pub TrainingSessionGetTrainingModelInputName: ::std::option::Option<
+		_system!(unsafe fn(sess: *const OrtTrainingSession, index: usize, allocator: *mut OrtAllocator, output: *mut *mut c_char) -> OrtStatusPtr)
+	>,
+	pub TrainingSessionGetEvalModelInputName: ::std::option::Option<
+		_system!(unsafe fn(sess: *const OrtTrainingSession, index: usize, allocator: *mut OrtAllocator, output: *mut *mut c_char) -> OrtStatusPtr)
+	>,
+	pub AddProperty: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				checkpoint_state: *mut OrtCheckpointState,
+				property_name: *const c_char,
+				property_type: OrtPropertyType,
+				property_value: *mut c_void
+			) -> OrtStatusPtr
+		)
+	>,
+	pub GetProperty: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				checkpoint_state: *const OrtCheckpointState,
+				property_name: *const c_char,
+				allocator: *mut OrtAllocator,
+				property_type: *mut OrtPropertyType,
+				property_value: *mut *mut c_void
+			) -> OrtStatusPtr
+		)
+	>,
+	pub LoadCheckpointFromBuffer: ::std::option::Option<
+		_system!(unsafe fn(checkpoint_buffer: *const c_void, num_bytes: usize, checkpoint_state: *mut *mut OrtCheckpointState) -> OrtStatusPtr)
+	>,
+	pub GetParameterTypeAndShape: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				checkpoint_state: *const OrtCheckpointState,
+				parameter_name: *const c_char,
+				parameter_type_and_shape: *mut *mut OrtTensorTypeAndShapeInfo
+			) -> OrtStatusPtr
+		)
+	>,
+	pub UpdateParameter: ::std::option::Option<
+		_system!(unsafe fn(checkpoint_state: *mut OrtCheckpointState, parameter_name: *const c_char, parameter: *mut OrtValue) -> OrtStatusPtr)
+	>,
+	pub GetParameter: ::std::option::Option<
+		_system!(
+			unsafe fn(
+				checkpoint_state: *const OrtCheckpointState,
+				parameter_name: *const c_char,
+				allocator: *mut OrtAllocator,
+				parameter: *mut *mut OrtValue
+			) -> OrtStatusPtr
+		)
 	>
 }
 #[doc = " \\brief The helper interface to get the right version of OrtApi\n\n Get a pointer to this structure through ::OrtGetApiBase"]
@@ -24,7 +24,15 @@ use tracing::{Level, debug};
 
 #[cfg(feature = "load-dynamic")]
 use crate::G_ORT_DYLIB_PATH;
-use crate::{AsPointer, error::Result, execution_providers::ExecutionProviderDispatch, extern_system_fn, ortsys};
+use crate::{
+	AsPointer,
+	error::{Result, status_to_result},
+	execution_providers::ExecutionProviderDispatch,
+	extern_system_fn,
+	memory::{AllocationDevice, AllocatorType, MemoryInfo, MemoryType},
+	ortsys,
+	session::builder::ArenaConfig
+};
 
 struct EnvironmentSingleton {
 	lock: RwLock<Option<Arc<Environment>>>
@@ -212,8 +220,10 @@ pub(crate) unsafe extern "C" fn thread_join<T: ThreadManager + Any>(ort_custom_t
 pub struct EnvironmentBuilder {
 	name: String,
 	telemetry: bool,
+	tracing: bool,
 	execution_providers: Vec<ExecutionProviderDispatch>,
-	global_thread_pool_options: Option<GlobalThreadPoolOptions>
+	global_thread_pool_options: Option<GlobalThreadPoolOptions>,
+	memory_arena: Option<ArenaConfig>
 }
 
 impl EnvironmentBuilder {
@@ -221,8 +231,10 @@ impl EnvironmentBuilder {
 		EnvironmentBuilder {
 			name: "default".to_string(),
 			telemetry: true,
+			tracing: true,
 			execution_providers: vec![],
-			global_thread_pool_options: None
+			global_thread_pool_options: None,
+			memory_arena: None
 		}
 	}
 
@@ -246,12 +258,53 @@ impl EnvironmentBuilder {
 	/// not FP16 is used, operator domains & versions, model graph name & custom metadata, execution provider names,
 	/// error messages, and the total number & time of session inference runs. The ONNX Runtime team uses this data to
 	/// better understand how customers use ONNX Runtime and where performance can be improved.
+	///
+	/// Telemetry is enabled by default. Passing `false` calls ONNX Runtime's `DisableTelemetryEvents` when the
+	/// environment is created, which turns off its ETW telemetry provider on Windows:
+	///
+	/// ```
+	/// # fn main() -> ort::Result<()> {
+	/// ort::init().with_telemetry(false).commit()?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	#[must_use = "commit() must be called in order for the environment to take effect"]
+	#[doc(alias("with_telemetry_events", "DisableTelemetryEvents", "EnableTelemetryEvents"))]
 	pub fn with_telemetry(mut self, enable: bool) -> Self {
 		self.telemetry = enable;
 		self
 	}
 
+	/// Enable or disable forwarding ONNX Runtime's internal logs to the [`tracing`] crate. Enabled by default.
+	///
+	/// When enabled, each log message from ONNX Runtime is emitted as a `tracing` event within an `ort` span (with the
+	/// logger ID & source location as fields), so it can be filtered & formatted by the same subscriber as the rest of
+	/// your application's logs. ONNX Runtime's severities are mapped to `tracing` levels as follows:
+	///
+	/// | ONNX Runtime | `tracing` |
+	/// |:------------ |:--------- |
+	/// | `VERBOSE`    | `TRACE`   |
+	/// | `INFO`       | `DEBUG`   |
+	/// | `WARNING`    | `INFO`    |
+	/// | `ERROR`      | `WARN`    |
+	/// | `FATAL`      | `ERROR`   |
+	///
+	/// When disabled, ONNX Runtime writes warnings & errors to its own default sink (usually `stderr`) instead.
+	///
+	/// ```
+	/// # fn main() -> ort::Result<()> {
+	/// tracing_subscriber::fmt::init();
+	///
+	/// ort::init().with_tracing(true).commit()?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[must_use = "commit() must be called in order for the environment to take effect"]
+	pub fn with_tracing(mut self, enable: bool) -> Self {
+		self.tracing = enable;
+		self
+	}
+
 	/// Sets a list of execution providers which all sessions created in this environment will register.
 	///
 	/// If a session is created in this environment with [`SessionBuilder::with_execution_providers`], those EPs
@@ -269,51 +322,164 @@ impl EnvironmentBuilder {
 	}
 
 	/// Enables the global thread pool for this environment.
+	///
+	/// Sessions created in this environment will share the threads of this pool instead of each creating their own
+	/// intra- & inter-op thread pools, which greatly reduces the number of OS threads used by applications running many
+	/// sessions at once. Sessions can opt out of the global thread pool with
+	/// [`SessionBuilder::with_independent_thread_pool`].
+	///
+	/// ```
+	/// # use ort::{environment::GlobalThreadPoolOptions, session::Session};
+	/// # fn main() -> ort::Result<()> {
+	/// ort::init()
+	/// 	.with_global_thread_pool(GlobalThreadPoolOptions::default().with_inter_threads(1)?.with_intra_threads(4)?)
+	/// 	.commit()?;
+	///
+	/// // both sessions share the same 4 intra-op threads
+	/// let a = Session::builder()?.disable_per_session_threads()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let b = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`SessionBuilder::with_independent_thread_pool`]: crate::session::builder::SessionBuilder::with_independent_thread_pool
 	#[must_use = "commit() must be called in order for the environment to take effect"]
 	pub fn with_global_thread_pool(mut self, options: GlobalThreadPoolOptions) -> Self {
 		self.global_thread_pool_options = Some(options);
 		self
 	}
 
+	/// Registers a CPU memory arena with this environment, which sessions can share instead of each creating their own.
+	///
+	/// By default, each session has its own CPU arena, which doubles in size each time it needs to grow. When running
+	/// many sessions in a fixed memory budget, sharing one arena using
+	/// [`ArenaExtendStrategy::SameAsRequested`](crate::execution_providers::ArenaExtendStrategy::SameAsRequested)
+	/// and/or a memory limit can significantly reduce memory usage. Only sessions created with
+	/// [`SessionBuilder::with_env_allocators`] use the shared arena; all other sessions keep their own.
+	///
+	/// ```
+	/// # use ort::{execution_providers::ArenaExtendStrategy, session::{Session, builder::ArenaConfig}};
+	/// # fn main() -> ort::Result<()> {
+	/// ort::init()
+	/// 	.with_memory_arena(ArenaConfig {
+	/// 		strategy: ArenaExtendStrategy::SameAsRequested,
+	/// 		max_mem_limit: Some(256 * 1024 * 1024),
+	/// 		..Default::default()
+	/// 	})
+	/// 	.commit()?;
+	///
+	/// let session = Session::builder()?.with_env_allocators()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Notes
+	/// Since ONNX Runtime only maintains one environment per process, the arena is registered process-wide, replacing
+	/// any arena registered by a previously built environment for sessions created afterwards. Existing sessions keep
+	/// using the arena they were created with.
+	///
+	/// [`SessionBuilder::with_env_allocators`]: crate::session::builder::SessionBuilder::with_env_allocators
+	#[must_use = "commit() must be called in order for the environment to take effect"]
+	pub fn with_memory_arena(mut self, config: ArenaConfig) -> Self {
+		self.memory_arena = Some(config);
+		self
+	}
+
 	/// Commit the environment configuration and set the global environment.
+	///
+	/// Returns an [`ErrorKind::VersionMismatch`](crate::ErrorKind::VersionMismatch) error if the linked ONNX Runtime
+	/// binary is older than the version `ort` was built for.
 	pub fn commit(self) -> Result<Arc<Environment>> {
-		let (env_ptr, thread_manager, has_global_threadpool) = if let Some(mut thread_pool_options) = self.global_thread_pool_options {
-			let mut env_ptr: *mut ort_sys::OrtEnv = std::ptr::null_mut();
-			let logging_function: ort_sys::OrtLoggingFunction = Some(custom_logger);
-			let logger_param: *mut std::ffi::c_void = std::ptr::null_mut();
-			let cname = CString::new(self.name.clone()).unwrap_or_else(|_| unreachable!());
-
-			ortsys![
-				unsafe CreateEnvWithCustomLoggerAndGlobalThreadPools(
-					logging_function,
-					logger_param,
-					ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_VERBOSE,
-					cname.as_ptr(),
-					thread_pool_options.ptr(),
-					&mut env_ptr
-				)?;
-				nonNull(env_ptr)
-			];
-
-			let thread_manager = thread_pool_options.thread_manager.take();
-			(env_ptr, thread_manager, true)
+		let env = Arc::new(self.build()?);
+
+		let mut env_lock = G_ENV.lock.write().expect("poisoned lock");
+		// drop global reference to previous environment
+		if let Some(env_arc) = env_lock.take() {
+			drop(env_arc);
+		}
+		env_lock.replace(Arc::clone(&env));
+
+		Ok(env)
+	}
+
+	/// Builds an environment *without* setting it as the global environment.
+	///
+	/// Sessions only use this environment if they are created with [`Session::builder_with_env`]; all other sessions
+	/// keep using the global environment. This allows a library crate to configure its own default execution providers
+	/// without interfering with the application's call to [`init`], and vice versa.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let env = ort::init().with_name("my-library").build_local()?;
+	/// let session = Session::builder_with_env(&env)?.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Notes
+	/// ONNX Runtime itself only maintains one environment per process, which is shared between all `Environment`s. The
+	/// name, logging & telemetry settings of whichever environment was created first therefore apply process-wide, and
+	/// a global thread pool configured via [`EnvironmentBuilder::with_global_thread_pool`] is only created if no other
+	/// environment exists yet. Default execution providers are specific to each `Environment`.
+	///
+	/// [`Session::builder_with_env`]: crate::session::Session::builder_with_env
+	pub fn build_local(self) -> Result<Arc<Environment>> {
+		Ok(Arc::new(self.build()?))
+	}
+
+	fn build(self) -> Result<Environment> {
+		crate::check_runtime_version()?;
+
+		let cname = CString::new(self.name.clone()).unwrap_or_else(|_| unreachable!());
+		let mut env_ptr: *mut ort_sys::OrtEnv = std::ptr::null_mut();
+		let (thread_manager, has_global_threadpool) = if let Some(mut thread_pool_options) = self.global_thread_pool_options {
+			if self.tracing {
+				let logging_function: ort_sys::OrtLoggingFunction = Some(custom_logger);
+				let logger_param: *mut std::ffi::c_void = std::ptr::null_mut();
+				ortsys![
+					unsafe CreateEnvWithCustomLoggerAndGlobalThreadPools(
+						logging_function,
+						logger_param,
+						ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_VERBOSE,
+						cname.as_ptr(),
+						thread_pool_options.ptr(),
+						&mut env_ptr
+					)?;
+					nonNull(env_ptr)
+				];
+			} else {
+				ortsys![
+					unsafe CreateEnvWithGlobalThreadPools(
+						ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_WARNING,
+						cname.as_ptr(),
+						thread_pool_options.ptr(),
+						&mut env_ptr
+					)?;
+					nonNull(env_ptr)
+				];
+			}
+
+			(thread_pool_options.thread_manager.take(), true)
 		} else {
-			let mut env_ptr: *mut ort_sys::OrtEnv = std::ptr::null_mut();
-			let logging_function: ort_sys::OrtLoggingFunction = Some(custom_logger);
-			// FIXME: What should go here?
-			let logger_param: *mut std::ffi::c_void = std::ptr::null_mut();
-			let cname = CString::new(self.name.clone()).unwrap_or_else(|_| unreachable!());
-			ortsys![
-				unsafe CreateEnvWithCustomLogger(
-					logging_function,
-					logger_param,
-					ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_VERBOSE,
-					cname.as_ptr(),
-					&mut env_ptr
-				)?;
-				nonNull(env_ptr)
-			];
-			(env_ptr, None, false)
+			if self.tracing {
+				let logging_function: ort_sys::OrtLoggingFunction = Some(custom_logger);
+				// FIXME: What should go here?
+				let logger_param: *mut std::ffi::c_void = std::ptr::null_mut();
+				ortsys![
+					unsafe CreateEnvWithCustomLogger(
+						logging_function,
+						logger_param,
+						ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_VERBOSE,
+						cname.as_ptr(),
+						&mut env_ptr
+					)?;
+					nonNull(env_ptr)
+				];
+			} else {
+				ortsys![unsafe CreateEnv(ort_sys::OrtLoggingLevel::ORT_LOGGING_LEVEL_WARNING, cname.as_ptr(), &mut env_ptr)?; nonNull(env_ptr)];
+			}
+			(None, false)
 		};
 		debug!(env_ptr = format!("{env_ptr:?}").as_str(), "Environment created");
 
@@ -323,19 +489,22 @@ impl EnvironmentBuilder {
 			ortsys![unsafe DisableTelemetryEvents(env_ptr)?];
 		}
 
-		let mut env_lock = G_ENV.lock.write().expect("poisoned lock");
-		// drop global reference to previous environment
-		if let Some(env_arc) = env_lock.take() {
-			drop(env_arc);
-		}
-		let env = Arc::new(Environment {
+		let env = Environment {
 			execution_providers: self.execution_providers,
-			// we already asserted the env pointer is non-null in the `CreateEnvWithCustomLogger` call
+			// we already asserted the env pointer is non-null in the `CreateEnv*` call
 			ptr: unsafe { NonNull::new_unchecked(env_ptr) },
 			has_global_threadpool,
 			_thread_manager: thread_manager
-		});
-		env_lock.replace(Arc::clone(&env));
+		};
+
+		if let Some(config) = self.memory_arena {
+			let arena_cfg = config.create()?;
+			let memory_info = MemoryInfo::new(AllocationDevice::CPU, 0, AllocatorType::Arena, MemoryType::Default)?;
+			// Registering fails if an arena was already registered, so remove any previous one first; this fails harmlessly
+			// if there is none.
+			let _ = status_to_result(ortsys![unsafe UnregisterAllocator(env_ptr, memory_info.ptr())]);
+			ortsys![unsafe CreateAndRegisterAllocator(env_ptr, memory_info.ptr(), arena_cfg.ptr())?];
+		}
 
 		Ok(env)
 	}
@@ -356,7 +525,8 @@ impl EnvironmentBuilder {
 /// # Notes
 /// - It is not required to call this function. If this is not called by the time any other `ort` APIs are used, a
 ///   default environment will be created.
-/// - **Library crates that use `ort` shouldn't create their own environment.** Let downstream applications create it.
+/// - **Library crates that use `ort` shouldn't create their own global environment.** Let downstream applications
+///   create it, or use [`EnvironmentBuilder::build_local`] for an environment that only the library's sessions use.
 /// - In order for environment settings to apply, this must be called **before** you use other APIs like [`Session`],
 ///   and you *must* call `.commit()` on the builder returned by this function.
 ///
@@ -1,6 +1,6 @@
 use std::{convert::Infallible, ffi::CString, fmt, ptr};
 
-use crate::{char_p_to_string, ortsys};
+use crate::{char_p_to_string, ortsys, tensor::TensorElementType, value::ValueType};
 
 /// Type alias for the Result type returned by ORT functions.
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -25,6 +25,7 @@ impl<T> IntoStatus for Result<T, Error> {
 #[derive(Debug)]
 pub struct Error {
 	code: ErrorCode,
+	kind: ErrorKind,
 	msg: String
 }
 
@@ -36,6 +37,7 @@ impl Error {
 	pub fn wrap<T: std::error::Error + Send + Sync + 'static>(err: T) -> Self {
 		Error {
 			code: ErrorCode::GenericFailure,
+			kind: ErrorKind::Other,
 			msg: err.to_string()
 		}
 	}
@@ -44,19 +46,52 @@ impl Error {
 	pub fn new(msg: impl Into<String>) -> Self {
 		Error {
 			code: ErrorCode::GenericFailure,
+			kind: ErrorKind::Other,
 			msg: msg.into()
 		}
 	}
 
 	/// Creates a custom [`Error`] with the given [`ErrorCode`] and message.
 	pub fn new_with_code(code: ErrorCode, msg: impl Into<String>) -> Self {
-		Error { code, msg: msg.into() }
+		Error {
+			code,
+			kind: ErrorKind::Other,
+			msg: msg.into()
+		}
+	}
+
+	pub(crate) fn new_with_kind(code: ErrorCode, kind: ErrorKind, msg: impl Into<String>) -> Self {
+		Error { code, kind, msg: msg.into() }
 	}
 
 	pub fn code(&self) -> ErrorCode {
 		self.code
 	}
 
+	/// Returns structured information about the cause of this error, if `ort` was able to diagnose it.
+	///
+	/// ```
+	/// # use ort::{error::ErrorKind, session::Session, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// // `upsample.onnx` expects an input of shape `[N, H, W, 3]`.
+	/// let input = Tensor::<f32>::from_array(([1, 4, 4, 2], vec![0.0; 32]))?;
+	/// match session.run(ort::inputs![input]?) {
+	/// 	Err(e) => match e.kind() {
+	/// 		ErrorKind::ShapeMismatch { input, expected, got } => {
+	/// 			eprintln!("input `{input}` should have shape {expected:?}, but got {got:?}");
+	/// 		}
+	/// 		_ => return Err(e)
+	/// 	},
+	/// 	Ok(_) => unreachable!()
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn kind(&self) -> &ErrorKind {
+		&self.kind
+	}
+
 	pub fn message(&self) -> &str {
 		self.msg.as_str()
 	}
@@ -82,6 +117,28 @@ impl From<std::ffi::NulError> for Error {
 	}
 }
 
+/// Structured information about the cause of an [`Error`]; see [`Error::kind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// An input required by the model was not provided.
+	MissingInput(String),
+	/// The shape of an input tensor is incompatible with the shape expected by the model. Dynamic dimensions in
+	/// `expected` are represented by `-1`.
+	ShapeMismatch { input: String, expected: Vec<i64>, got: Vec<i64> },
+	/// The type of an input is incompatible with the type expected by the model.
+	TypeMismatch { input: String, expected: ValueType, got: ValueType },
+	/// A tensor's data was accessed as a different element type than the one it actually holds, e.g. when extracting an
+	/// `f32` tensor as `i64`.
+	DTypeMismatch { expected: TensorElementType, got: TensorElementType },
+	/// The linked ONNX Runtime binary is too old for the version of the C API `ort` was built for, e.g. because a stale
+	/// system installation of ONNX Runtime was found on the library path. `expected` is the version `ort` requires
+	/// (like `1.20.x`), and `found` is the version reported by the binary.
+	VersionMismatch { expected: String, found: String },
+	/// Any other error. [`Error::code`] & [`Error::message`] describe the error in more detail.
+	Other
+}
+
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ErrorCode {
@@ -154,12 +211,13 @@ pub(crate) fn status_to_result(status: *mut ort_sys::OrtStatus) -> Result<(), Er
 		match char_p_to_string(raw) {
 			Ok(msg) => {
 				ortsys![unsafe ReleaseStatus(status)];
-				Err(Error { code, msg })
+				Err(Error { code, kind: ErrorKind::Other, msg })
 			}
 			Err(err) => {
 				ortsys![unsafe ReleaseStatus(status)];
 				Err(Error {
 					code,
+					kind: ErrorKind::Other,
 					msg: format!("(failed to convert UTF-8: {err})")
 				})
 			}
@@ -4,7 +4,7 @@ use super::{ArbitrarilyConfigurableExecutionProvider, ExecutionProviderOptions};
 use crate::{
 	error::{Error, Result},
 	execution_providers::{ArenaExtendStrategy, ExecutionProvider, ExecutionProviderDispatch},
-	session::builder::SessionBuilder
+	session::builder::{ArenaConfig, SessionBuilder}
 };
 
 // https://github.com/microsoft/onnxruntime/blob/ffceed9d44f2f3efb9dd69fa75fea51163c91d91/onnxruntime/contrib_ops/cpu/bert/attention_common.h#L160-L171
@@ -82,12 +82,37 @@ impl Default for CUDAExecutionProviderCuDNNConvAlgoSearch {
 	}
 }
 
+/// The [CUDA execution provider](https://onnxruntime.ai/docs/execution-providers/CUDA-ExecutionProvider.html), for
+/// inference on NVIDIA GPUs.
+///
+/// Each `with_*` method corresponds to a field of ONNX Runtime's `OrtCUDAProviderOptionsV2`; options which are not
+/// configured use ONNX Runtime's defaults. Options not covered by a method can be set via
+/// [`ArbitrarilyConfigurableExecutionProvider::with_arbitrary_config`].
+///
+/// ```
+/// # use ort::{execution_providers::{ArenaExtendStrategy, CUDAExecutionProvider, cuda::CUDAExecutionProviderCuDNNConvAlgoSearch}, session::Session};
+/// # fn main() -> ort::Result<()> {
+/// let session = Session::builder()?
+/// 	.with_execution_providers([CUDAExecutionProvider::default()
+/// 		.with_device_id(0)
+/// 		// limit the arena to 2 GiB so multiple sessions can share a GPU
+/// 		.with_memory_limit(2 * 1024 * 1024 * 1024)
+/// 		.with_arena_extend_strategy(ArenaExtendStrategy::SameAsRequested)
+/// 		.with_conv_algorithm_search(CUDAExecutionProviderCuDNNConvAlgoSearch::Heuristic)
+/// 		.with_copy_in_default_stream(true)
+/// 		.build()])?
+/// 	.commit_from_file("tests/data/upsample.onnx")?;
+/// # 	Ok(())
+/// # }
+/// ```
 #[derive(Debug, Default, Clone)]
 pub struct CUDAExecutionProvider {
-	options: ExecutionProviderOptions
+	options: ExecutionProviderOptions,
+	arena_config: Option<ArenaConfig>
 }
 
 impl CUDAExecutionProvider {
+	/// The ID of the CUDA device to run on. Defaults to `0`.
 	#[must_use]
 	pub fn with_device_id(mut self, device_id: i32) -> Self {
 		self.options.set("device_id", device_id.to_string());
@@ -112,6 +137,41 @@ impl CUDAExecutionProvider {
 		self
 	}
 
+	/// Configures the device memory arena in full, overriding [`CUDAExecutionProvider::with_memory_limit`] and
+	/// [`CUDAExecutionProvider::with_arena_extend_strategy`].
+	///
+	/// The arena holds on to device memory once it has been allocated, and by default grows in powers of two, so a
+	/// session may end up reserving far more memory than it needs at any one time. ONNX Runtime offers no way to disable
+	/// the CUDA arena outright, but it can be bounded: [`ArenaExtendStrategy::SameAsRequested`] with a small
+	/// `initial_chunk_size` keeps the arena close to the model's actual usage, and `max_mem_limit` caps it. Memory can
+	/// also be returned to the device after each run by setting the `memory.enable_memory_arena_shrinkage` run option
+	/// to `"gpu:<device_id>"` via [`RunOptions::add_config_entry`](crate::session::RunOptions::add_config_entry).
+	///
+	/// This comes at a cost in latency: a tighter arena means more calls to `cudaMalloc`/`cudaFree` (which synchronize
+	/// the device) during inference, and shrinking the arena forces allocations to be redone on the next run. Prefer
+	/// the defaults when a session has a GPU to itself.
+	///
+	/// ```
+	/// # use ort::{execution_providers::{ArenaExtendStrategy, CUDAExecutionProvider}, session::{builder::ArenaConfig, Session}};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_execution_providers([CUDAExecutionProvider::default()
+	/// 		.with_arena_config(ArenaConfig {
+	/// 			strategy: ArenaExtendStrategy::SameAsRequested,
+	/// 			initial_chunk_size: Some(1024 * 1024),
+	/// 			max_mem_limit: Some(512 * 1024 * 1024)
+	/// 		})
+	/// 		.build()])?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[must_use]
+	pub fn with_arena_config(mut self, config: ArenaConfig) -> Self {
+		self.arena_config = Some(config);
+		self
+	}
+
 	/// ORT leverages cuDNN for convolution operations and the first step in this process is to determine an
 	/// “optimal” convolution algorithm to use while performing the convolution operation for the given input
 	/// configuration (input shape, filter shape, etc.) in each `Conv` node. This option controlls the type of search
@@ -206,32 +266,75 @@ impl CUDAExecutionProvider {
 		self
 	}
 
+	/// Prefer the NHWC layout for operators which support it, which can be faster on GPUs with tensor cores. This
+	/// requires ONNX Runtime to be built with `--enable_cuda_nhwc_ops`.
 	#[must_use]
 	pub fn with_prefer_nhwc(mut self) -> Self {
 		self.options.set("prefer_nhwc", "1");
 		self
 	}
 
+	/// Use the given CUDA stream (i.e. `cudaStream_t`/`CUstream`) for compute instead of a stream created by ONNX
+	/// Runtime.
+	///
+	/// Sharing a stream with your own pre- & post-processing kernels lets them be ordered with respect to inference by
+	/// the stream itself, without synchronizing the device in between. Note that the stream must belong to the same
+	/// device as [`CUDAExecutionProvider::with_device_id`].
+	///
 	/// # Safety
-	/// The provided `stream` must outlive the environment/session created with the execution provider.
+	/// `stream` must be a valid CUDA stream on the configured device, and it must outlive the environment/session
+	/// created with the execution provider. ONNX Runtime does not take ownership of the stream and will not destroy it.
 	#[must_use]
 	pub unsafe fn with_compute_stream(mut self, stream: *mut ()) -> Self {
 		self.options.set("user_compute_stream", (stream as usize).to_string());
 		self
 	}
 
+	/// Configure which kernels may be used for scaled dot-product attention in fused attention operators.
 	#[must_use]
 	pub fn with_attention_backend(mut self, flags: CUDAExecutionProviderAttentionBackend) -> Self {
 		self.options.set("sdpa_kernel", flags.0.to_string());
 		self
 	}
 
+	/// Whether to fuse the bias of `Conv` nodes into the cuDNN convolution, if supported by the cuDNN version.
 	#[must_use]
 	pub fn with_fuse_conv_bias(mut self, enable: bool) -> Self {
 		self.options.set("fuse_conv_bias", if enable { "1" } else { "0" });
 		self
 	}
 
+	/// Whether to use [TunableOp](https://onnxruntime.ai/docs/execution-providers/CUDA-ExecutionProvider.html#tunable_op_enable)
+	/// kernels, which may be faster than the default kernels for some operators. Defaults to `false`.
+	#[must_use]
+	pub fn with_tunable_op(mut self, enable: bool) -> Self {
+		self.options.set("tunable_op_enable", if enable { "1" } else { "0" });
+		self
+	}
+
+	/// Whether TunableOp may benchmark its kernels online to find the fastest one. Only has an effect when TunableOp is
+	/// enabled via [`CUDAExecutionProvider::with_tunable_op`].
+	#[must_use]
+	pub fn with_tunable_op_tuning(mut self, enable: bool) -> Self {
+		self.options.set("tunable_op_tuning_enable", if enable { "1" } else { "0" });
+		self
+	}
+
+	/// Limits the time TunableOp spends tuning each operator, in milliseconds. `0` means no limit.
+	#[must_use]
+	pub fn with_tunable_op_max_tuning_duration(mut self, ms: i32) -> Self {
+		self.options.set("tunable_op_max_tuning_duration_ms", ms.to_string());
+		self
+	}
+
+	/// Whether to use a single unified stream for all threads of this execution provider, rather than one stream per
+	/// thread. Defaults to `false`.
+	#[must_use]
+	pub fn with_ep_level_unified_stream(mut self, enable: bool) -> Self {
+		self.options.set("use_ep_level_unified_stream", if enable { "1" } else { "0" });
+		self
+	}
+
 	// https://github.com/microsoft/onnxruntime/blob/ffceed9d44f2f3efb9dd69fa75fea51163c91d91/onnxruntime/core/providers/cuda/cuda_execution_provider_info.h#L48
 	// https://github.com/microsoft/onnxruntime/blob/fe8a10caa40f64a8fbd144e7049cf5b14c65542d/onnxruntime/core/providers/cuda/cuda_execution_provider_info.cc#L17
 
@@ -278,6 +381,24 @@ impl ExecutionProvider for CUDAExecutionProvider {
 				crate::ortsys![unsafe ReleaseCUDAProviderOptions(cuda_options)];
 				return Err(e);
 			}
+			if let Some(arena_config) = &self.arena_config {
+				// ONNX Runtime keeps a pointer to the arena config rather than copying it, so it must outlive the session.
+				let arena_cfg = match arena_config.create() {
+					Ok(arena_cfg) => arena_cfg,
+					Err(e) => {
+						crate::ortsys![unsafe ReleaseCUDAProviderOptions(cuda_options)];
+						return Err(e);
+					}
+				};
+				let key = std::ffi::CString::new("default_memory_arena_cfg").unwrap_or_else(|_| unreachable!());
+				if let Err(e) = crate::error::status_to_result(
+					crate::ortsys![unsafe UpdateCUDAProviderOptionsWithValue(cuda_options, key.as_ptr(), arena_cfg.ptr().cast())]
+				) {
+					crate::ortsys![unsafe ReleaseCUDAProviderOptions(cuda_options)];
+					return Err(e);
+				}
+				session_builder.retain_execution_provider_resource(std::rc::Rc::new(arena_cfg));
+			}
 
 			let status = crate::ortsys![unsafe SessionOptionsAppendExecutionProvider_CUDA_V2(session_builder.ptr_mut(), cuda_options)];
 			crate::ortsys![unsafe ReleaseCUDAProviderOptions(cuda_options)];
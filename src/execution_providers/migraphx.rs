@@ -6,6 +6,12 @@ use crate::{
 	session::builder::SessionBuilder
 };
 
+/// [MIGraphX execution provider](https://onnxruntime.ai/docs/execution-providers/MIGraphX-ExecutionProvider.html) for
+/// AMD GPUs (including AMD Instinct accelerators) via ROCm's MIGraphX graph compiler.
+///
+/// MIGraphX compiles the whole graph ahead of time, which can take a while for large models; use
+/// [`MIGraphXExecutionProvider::with_save_model`] & [`MIGraphXExecutionProvider::with_load_model`] to cache the compiled
+/// program between runs.
 #[derive(Debug, Default, Clone)]
 pub struct MIGraphXExecutionProvider {
 	device_id: i32,
@@ -25,18 +31,24 @@ impl MIGraphXExecutionProvider {
 		self
 	}
 
+	/// Enables FP16 precision. MIGraphX decides which operators run in FP16; ONNX Runtime does not expose a way to
+	/// restrict this to a specific set of operators.
 	#[must_use]
 	pub fn with_fp16(mut self) -> Self {
 		self.enable_fp16 = true;
 		self
 	}
 
+	/// Enables INT8 precision. This requires a calibration table; see
+	/// [`MIGraphXExecutionProvider::with_native_calibration_table`].
 	#[must_use]
 	pub fn with_int8(mut self) -> Self {
 		self.enable_int8 = true;
 		self
 	}
 
+	/// Uses the INT8 calibration table named `table_name`. The table is expected to be in MIGraphX's native format
+	/// rather than the format generated by ONNX Runtime's calibration tools.
 	#[must_use]
 	pub fn with_native_calibration_table(mut self, table_name: Option<impl AsRef<str>>) -> Self {
 		self.use_native_calibration_table = true;
@@ -44,18 +56,23 @@ impl MIGraphXExecutionProvider {
 		self
 	}
 
+	/// Saves the compiled MIGraphX program to `path` after the model is compiled, so it can be loaded later with
+	/// [`MIGraphXExecutionProvider::with_load_model`] instead of recompiling the model.
 	#[must_use]
 	pub fn with_save_model(mut self, path: impl AsRef<str>) -> Self {
 		self.save_model_path = Some(CString::new(path.as_ref()).expect("invalid string"));
 		self
 	}
 
+	/// Loads a compiled MIGraphX program previously saved to `path` with [`MIGraphXExecutionProvider::with_save_model`].
 	#[must_use]
 	pub fn with_load_model(mut self, path: impl AsRef<str>) -> Self {
 		self.load_model_path = Some(CString::new(path.as_ref()).expect("invalid string"));
 		self
 	}
 
+	/// Enables exhaustive tuning of kernels during compilation, which makes compilation slower but may improve
+	/// inference performance.
 	#[must_use]
 	pub fn with_exhaustive_tune(mut self, enable: bool) -> Self {
 		self.exhaustive_tune = enable;
@@ -16,7 +16,12 @@
 
 use std::{collections::HashMap, ffi::CString, fmt::Debug, os::raw::c_char, sync::Arc};
 
-use crate::{char_p_to_string, error::Result, ortsys, session::builder::SessionBuilder};
+use crate::{
+	char_p_to_string,
+	error::{Error, ErrorCode, Result},
+	ortsys,
+	session::builder::SessionBuilder
+};
 
 pub mod cpu;
 pub use self::cpu::CPUExecutionProvider;
@@ -94,35 +99,43 @@ pub trait ExecutionProvider: Send + Sync {
 	/// enabled), you'll instead want to manually register this EP via [`ExecutionProvider::register`] and detect
 	/// and handle any errors returned by that function.
 	fn is_available(&self) -> Result<bool> {
-		let mut providers: *mut *mut c_char = std::ptr::null_mut();
-		let mut num_providers = 0;
-		ortsys![unsafe GetAvailableProviders(&mut providers, &mut num_providers)?];
-		if providers.is_null() {
-			return Ok(false);
-		}
-
-		for i in 0..num_providers {
-			let avail = match char_p_to_string(unsafe { *providers.offset(i as isize) }) {
-				Ok(avail) => avail,
-				Err(e) => {
-					let _ = ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)];
-					return Err(e);
-				}
-			};
-			if self.as_str() == avail {
-				let _ = ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)];
-				return Ok(true);
-			}
-		}
-
-		let _ = ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)];
-		Ok(false)
+		Ok(list_available()?.iter().any(|provider| provider == self.as_str()))
 	}
 
 	/// Attempts to register this execution provider on the given session.
 	fn register(&self, session_builder: &mut SessionBuilder) -> Result<()>;
 }
 
+/// Returns the identifiers of all execution providers ONNX Runtime was *compiled with support* for, e.g.
+/// `["CUDAExecutionProvider", "CPUExecutionProvider"]`. The identifiers are the same as those returned by
+/// [`ExecutionProvider::as_str`].
+///
+/// This does not require the environment to be initialized, so it can be called before any session is created. See
+/// [`ExecutionProvider::is_available`] for caveats regarding whether an execution provider is actually usable.
+///
+/// ```
+/// # fn main() -> ort::Result<()> {
+/// let providers = ort::execution_providers::list_available()?;
+/// assert!(providers.iter().any(|p| p == "CPUExecutionProvider"));
+/// # 	Ok(())
+/// # }
+/// ```
+#[doc(alias("available_providers", "GetAvailableProviders"))]
+pub fn list_available() -> Result<Vec<String>> {
+	let mut providers: *mut *mut c_char = std::ptr::null_mut();
+	let mut num_providers = 0;
+	ortsys![unsafe GetAvailableProviders(&mut providers, &mut num_providers)?];
+	if providers.is_null() {
+		return Ok(Vec::new());
+	}
+
+	let available = (0..num_providers)
+		.map(|i| char_p_to_string(unsafe { *providers.offset(i as isize) }))
+		.collect::<Result<Vec<_>>>();
+	let _ = ortsys![unsafe ReleaseAvailableProviders(providers, num_providers)];
+	available
+}
+
 /// Trait used for execution providers that can have arbitrary configuration keys applied.
 ///
 /// Most execution providers have a small set of configuration options which don't change between ONNX Runtime releases;
@@ -247,9 +260,14 @@ macro_rules! get_ep_register {
 #[allow(unused)]
 pub(crate) use get_ep_register;
 
+/// Registers the given execution providers to the session builder, in order.
+///
+/// If `strict` is set, failing to register any EP other than the CPU EP returns an error, as if the EP were configured
+/// with [`ExecutionProviderDispatch::error_on_failure`].
 pub(crate) fn apply_execution_providers(
 	session_builder: &mut SessionBuilder,
-	execution_providers: impl Iterator<Item = ExecutionProviderDispatch>
+	execution_providers: impl Iterator<Item = ExecutionProviderDispatch>,
+	strict: bool
 ) -> Result<()> {
 	let execution_providers: Vec<_> = execution_providers.collect();
 	let mut fallback_to_cpu = !execution_providers.is_empty();
@@ -258,6 +276,12 @@ pub(crate) fn apply_execution_providers(
 			if ex.error_on_failure {
 				return Err(e);
 			}
+			if strict && ex.inner.as_str() != "CPUExecutionProvider" {
+				return Err(Error::new_with_code(
+					ErrorCode::ExecutionProviderFailure,
+					format!("Failed to register `{}`, and strict execution provider registration is enabled: {e}", ex.inner.as_str())
+				));
+			}
 
 			if e.message()
 				.ends_with("was not registered because its corresponding Cargo feature is not enabled.")
@@ -272,6 +296,7 @@ pub(crate) fn apply_execution_providers(
 			}
 		} else {
 			tracing::info!("Successfully registered `{}`", ex.inner.as_str());
+			session_builder.record_registered_execution_provider(ex.inner.as_str());
 			fallback_to_cpu = false;
 		}
 	}
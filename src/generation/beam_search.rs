@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+
+use ndarray::{Array2, ArrayView1, Ix3, s};
+
+use crate::{
+	error::{Error, Result},
+	session::Session
+};
+
+/// Decodes sequences from a language model using beam search.
+///
+/// Where greedy decoding only ever follows the single most likely token, beam search keeps track of the `beam_width`
+/// most likely partial sequences ("beams") at each step, which usually produces more coherent output for tasks like
+/// translation and summarization.
+///
+/// The decoder expects a model with a single `int64` input of shape `[batch, sequence]` containing token IDs, whose
+/// first output is a `float32` tensor of logits with shape `[batch, sequence, vocab]`. Each step re-runs the model with
+/// the full sequence of every live beam.
+///
+/// ```no_run
+/// # use ndarray::Array2;
+/// # use ort::{generation::BeamSearchDecoder, session::Session};
+/// # fn main() -> ort::Result<()> {
+/// let session = Session::builder()?.commit_from_file("translator.onnx")?;
+/// let decoder = BeamSearchDecoder::new(&session, 4, 64, 2).with_length_penalty(0.6);
+///
+/// let input_ids = Array2::from_shape_vec((1, 3), vec![0_i64, 1542, 87]).unwrap();
+/// let sequences = decoder.decode(input_ids)?;
+/// // the most likely sequence for the first prompt comes first
+/// let best = &sequences[0][0];
+/// # 	Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BeamSearchDecoder<'s> {
+	session: &'s Session,
+	beam_width: usize,
+	max_len: usize,
+	eos_token: i64,
+	length_penalty: f32
+}
+
+impl<'s> BeamSearchDecoder<'s> {
+	/// Creates a new beam search decoder.
+	///
+	/// - `beam_width` is the number of beams kept alive at each step, and also the maximum number of sequences returned
+	///   per batch item.
+	/// - `max_len` is the maximum length of a generated sequence, *including* the prompt.
+	/// - `eos_token` is the ID of the end-of-sequence token; a beam which produces it is considered finished.
+	///
+	/// # Panics
+	/// Panics if `beam_width` is `0`.
+	pub fn new(session: &'s Session, beam_width: usize, max_len: usize, eos_token: i64) -> Self {
+		assert!(beam_width > 0, "beam width must be at least 1");
+		Self {
+			session,
+			beam_width,
+			max_len,
+			eos_token,
+			length_penalty: 1.0
+		}
+	}
+
+	/// Configures the exponent applied to the length of a sequence when normalizing its log-probability; defaults to
+	/// `1.0`.
+	///
+	/// A sequence's final score is `log_prob / generated_len.powf(length_penalty)`. Values below `1.0` favor shorter
+	/// sequences, while values above `1.0` favor longer sequences. A value of `0.0` disables normalization entirely.
+	pub fn with_length_penalty(mut self, length_penalty: f32) -> Self {
+		self.length_penalty = length_penalty;
+		self
+	}
+
+	/// Runs beam search on a batch of prompts.
+	///
+	/// Returns one entry per row of `input_ids`, each holding up to `beam_width` sequences sorted by descending
+	/// normalized log-probability. Fewer than `beam_width` sequences are returned for a batch item if fewer beams could
+	/// be explored, e.g. if the prompt is already `max_len` tokens long. Each returned sequence includes its prompt, and
+	/// finished sequences include the end-of-sequence token.
+	///
+	/// Decoding of a batch item stops early once `beam_width` sequences have finished and no live beam can score
+	/// higher than them.
+	pub fn decode(&self, input_ids: Array2<i64>) -> Result<Vec<Vec<Vec<i64>>>> {
+		let (_, prompt_len) = input_ids.dim();
+		let mut states: Vec<BeamState> = input_ids
+			.rows()
+			.into_iter()
+			.map(|prompt| BeamState::new(prompt.to_vec(), self.beam_width, self.length_penalty))
+			.collect();
+
+		let mut seq_len = prompt_len;
+		while seq_len < self.max_len {
+			let n_beams: usize = states.iter().filter(|s| !s.done).map(|s| s.active.len()).sum();
+			if n_beams == 0 {
+				break;
+			}
+
+			let input = Array2::from_shape_vec(
+				(n_beams, seq_len),
+				states
+					.iter()
+					.filter(|s| !s.done)
+					.flat_map(|s| s.active.iter().flat_map(|h| h.tokens.iter().copied()))
+					.collect()
+			)
+			.expect("all live beams should have the same length");
+
+			let outputs = self.session.run(crate::inputs![input]?)?;
+			let logits = outputs[0]
+				.try_extract_tensor::<f32>()?
+				.into_dimensionality::<Ix3>()
+				.map_err(|_| Error::new("Beam search expects the model's first output to be logits of shape [batch, sequence, vocab]"))?;
+			if logits.shape()[0] != n_beams {
+				return Err(Error::new(format!("Model produced logits for {} sequences, but {n_beams} were provided", logits.shape()[0])));
+			}
+
+			let mut row = 0;
+			for state in states.iter_mut().filter(|s| !s.done) {
+				let log_probs: Vec<Vec<f32>> = (row..row + state.active.len())
+					.map(|r| log_softmax(logits.slice(s![r, -1, ..])))
+					.collect();
+				row += state.active.len();
+				state.advance(&log_probs, self.eos_token);
+			}
+
+			seq_len += 1;
+		}
+
+		Ok(states.into_iter().map(BeamState::into_sequences).collect())
+	}
+}
+
+#[derive(Debug, Clone)]
+struct Hypothesis {
+	tokens: Vec<i64>,
+	/// Sum of the log-probabilities of all generated tokens.
+	log_prob: f32
+}
+
+/// The beams of a single batch item.
+#[derive(Debug)]
+struct BeamState {
+	prompt_len: usize,
+	beam_width: usize,
+	length_penalty: f32,
+	active: Vec<Hypothesis>,
+	finished: Vec<Hypothesis>,
+	done: bool
+}
+
+impl BeamState {
+	fn new(prompt: Vec<i64>, beam_width: usize, length_penalty: f32) -> Self {
+		Self {
+			prompt_len: prompt.len(),
+			beam_width,
+			length_penalty,
+			active: vec![Hypothesis { tokens: prompt, log_prob: 0.0 }],
+			finished: Vec::new(),
+			done: false
+		}
+	}
+
+	fn score(&self, hypothesis: &Hypothesis) -> f32 {
+		let generated = hypothesis.tokens.len().saturating_sub(self.prompt_len).max(1);
+		hypothesis.log_prob / (generated as f32).powf(self.length_penalty)
+	}
+
+	/// Expands each active beam with the given next-token log-probabilities (one row per active beam).
+	fn advance(&mut self, log_probs: &[Vec<f32>], eos_token: i64) {
+		debug_assert_eq!(log_probs.len(), self.active.len());
+
+		// Taking the top `2 * beam_width` tokens of each beam guarantees we find `beam_width` non-EOS continuations.
+		let mut candidates: Vec<(f32, usize, i64)> = Vec::new();
+		for (beam, row) in log_probs.iter().enumerate() {
+			let mut tokens: Vec<(usize, f32)> = row.iter().copied().enumerate().collect();
+			let k = (2 * self.beam_width).min(tokens.len());
+			if k < tokens.len() {
+				tokens.select_nth_unstable_by(k, |a, b| cmp_desc(a.1, b.1));
+				tokens.truncate(k);
+			}
+			let base = self.active[beam].log_prob;
+			candidates.extend(tokens.into_iter().map(|(token, lp)| (base + lp, beam, token as i64)));
+		}
+		candidates.sort_unstable_by(|a, b| cmp_desc(a.0, b.0));
+
+		let mut next = Vec::with_capacity(self.beam_width);
+		for (rank, (log_prob, beam, token)) in candidates.into_iter().enumerate() {
+			let mut tokens = self.active[beam].tokens.clone();
+			tokens.push(token);
+			let hypothesis = Hypothesis { tokens, log_prob };
+			if token == eos_token {
+				// EOS candidates ranked below the beam width would not have survived as beams either
+				if rank < self.beam_width {
+					self.finished.push(hypothesis);
+				}
+			} else {
+				next.push(hypothesis);
+				if next.len() == self.beam_width {
+					break;
+				}
+			}
+		}
+		self.active = next;
+
+		let mut finished = std::mem::take(&mut self.finished);
+		finished.sort_by(|a, b| cmp_desc(self.score(a), self.score(b)));
+		finished.truncate(self.beam_width);
+		self.finished = finished;
+
+		self.done = self.active.is_empty()
+			|| (self.finished.len() == self.beam_width && {
+				let worst_finished = self.score(&self.finished[self.finished.len() - 1]);
+				let best_active = self.active.iter().map(|h| self.score(h)).fold(f32::NEG_INFINITY, f32::max);
+				worst_finished >= best_active
+			});
+	}
+
+	fn into_sequences(self) -> Vec<Vec<i64>> {
+		let mut hypotheses: Vec<(f32, Hypothesis)> = self
+			.finished
+			.iter()
+			.chain(self.active.iter())
+			.map(|h| (self.score(h), h.clone()))
+			.collect();
+		hypotheses.sort_by(|a, b| cmp_desc(a.0, b.0));
+		hypotheses.into_iter().take(self.beam_width).map(|(_, h)| h.tokens).collect()
+	}
+}
+
+fn cmp_desc(a: f32, b: f32) -> Ordering {
+	b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+}
+
+fn log_softmax(logits: ArrayView1<f32>) -> Vec<f32> {
+	let max = logits.iter().copied().filter(|l| !l.is_nan()).fold(f32::NEG_INFINITY, f32::max);
+	if max == f32::NEG_INFINITY {
+		return vec![f32::NEG_INFINITY; logits.len()];
+	}
+	let log_sum = logits.iter().filter(|l| !l.is_nan()).map(|l| (l - max).exp()).sum::<f32>().ln() + max;
+	logits.iter().map(|&l| if l.is_nan() { f32::NEG_INFINITY } else { l - log_sum }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn ln(p: &[f32]) -> Vec<f32> {
+		p.iter().map(|p| p.ln()).collect()
+	}
+
+	#[test]
+	fn test_log_softmax() {
+		let lp = log_softmax(ndarray::arr1(&[1.0_f32, 1.0, f32::NAN]).view());
+		assert!((lp[0] - 0.5_f32.ln()).abs() < 1e-6);
+		assert!((lp[1] - 0.5_f32.ln()).abs() < 1e-6);
+		assert_eq!(lp[2], f32::NEG_INFINITY);
+	}
+
+	#[test]
+	fn test_beam_expansion() {
+		let mut state = BeamState::new(vec![7], 2, 1.0);
+		// vocab: 0 = EOS, 1, 2, 3
+		state.advance(&[ln(&[0.1, 0.5, 0.3, 0.1])], 0);
+		assert_eq!(state.active.iter().map(|h| h.tokens.clone()).collect::<Vec<_>>(), vec![vec![7, 1], vec![7, 2]]);
+		assert!(state.finished.is_empty());
+
+		// beam [7, 2] now finds a much better continuation than [7, 1]
+		state.advance(&[ln(&[0.1, 0.2, 0.3, 0.4]), ln(&[0.05, 0.05, 0.1, 0.8])], 0);
+		assert_eq!(state.active[0].tokens, vec![7, 2, 3]);
+		assert_eq!(state.active[1].tokens, vec![7, 1, 3]);
+	}
+
+	#[test]
+	fn test_early_stopping() {
+		let mut state = BeamState::new(vec![7], 2, 1.0);
+		state.advance(&[ln(&[0.6, 0.3, 0.05, 0.05])], 0);
+		assert_eq!(state.finished.len(), 1);
+		assert!(!state.done);
+
+		state.advance(&[ln(&[0.9, 0.04, 0.03, 0.03]), ln(&[0.1, 0.2, 0.3, 0.4])], 0);
+		assert_eq!(state.finished.len(), 2);
+		assert!(state.done);
+
+		let sequences = state.into_sequences();
+		assert_eq!(sequences, vec![vec![7, 0], vec![7, 1, 0]]);
+	}
+}
@@ -0,0 +1,233 @@
+use std::borrow::Cow;
+
+use ndarray::{Array, Axis, IxDyn, Slice, concatenate};
+
+use crate::{
+	error::{Error, ErrorCode, Result},
+	session::{Session, SessionInputValue, SessionOutputs},
+	value::{Tensor, TensorValueType}
+};
+
+/// Manages the attention key/value cache of a transformer model between generation steps.
+///
+/// Models exported with a KV cache take the attention keys & values of all previous tokens as `past*` inputs, and
+/// return the updated keys & values as `present*` outputs. By feeding the `present` outputs of one step into the `past`
+/// inputs of the next, each step only needs to process the newly generated token, making generation `O(n)` in context
+/// length instead of `O(n²)`.
+///
+/// The cache tensors are inferred from the session: every input whose name starts with `past` (e.g.
+/// `past_key_values.0.key` or `past_0`) is paired with the output of the same name, with the `past_key_values`/`past`
+/// prefix replaced by `present` (e.g. `present.0.key` or `present_0`). Cache tensors must be `float32` with the
+/// sequence dimension second to last, i.e. `[batch, num_heads, sequence, head_dim]`.
+///
+/// When the model returns the full cache (past + new tokens) as its `present` outputs, as most exported transformer
+/// models do, the output values are kept as-is, so the cache stays on the session's device (e.g. when using the CUDA EP
+/// with outputs bound to device memory). The cache is only copied when it must be appended to or truncated to
+/// `max_length`, which requires it to be in CPU-accessible memory.
+///
+/// ```no_run
+/// # use ort::{generation::KvCacheManager, session::Session, value::Tensor};
+/// # fn main() -> ort::Result<()> {
+/// let session = Session::builder()?.commit_from_file("gpt2_with_past.onnx")?;
+/// let mut cache = KvCacheManager::new(&session, 1024)?;
+///
+/// let mut token = 50256_i64;
+/// for _ in 0..32 {
+/// 	let mut inputs = cache.inputs()?;
+/// 	inputs.push(("input_ids".into(), Tensor::from_array(([1, 1], vec![token]))?.into()));
+/// 	let mut outputs = session.run(inputs)?;
+/// 	cache.update(&mut outputs)?;
+///
+/// 	let logits = outputs["logits"].try_extract_tensor::<f32>()?;
+/// 	// ... sample the next `token` from `logits`
+/// }
+/// # 	Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct KvCacheManager {
+	entries: Vec<KvCacheEntry>,
+	max_length: usize,
+	batch_size: usize
+}
+
+#[derive(Debug)]
+struct KvCacheEntry {
+	input_name: String,
+	output_name: String,
+	dimensions: Vec<i64>,
+	value: Option<Tensor<f32>>
+}
+
+impl KvCacheEntry {
+	fn seq_axis(&self) -> Axis {
+		Axis(self.dimensions.len() - 2)
+	}
+
+	fn seq_len_of(&self, value: &Tensor<f32>) -> Result<usize> {
+		Ok(value.shape()?[self.seq_axis().index()] as usize)
+	}
+
+	fn ensure_cpu_accessible(&self, value: &Tensor<f32>) -> Result<()> {
+		if value.memory_info().is_cpu_accessible() {
+			Ok(())
+		} else {
+			Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("KV cache `{}` must be in CPU-accessible memory to be appended to or truncated", self.input_name)
+			))
+		}
+	}
+}
+
+impl KvCacheManager {
+	/// Creates a new, empty KV cache for the given session, inferring the names of the cache inputs & outputs from the
+	/// session's metadata.
+	///
+	/// Once the cache grows beyond `max_length` tokens, the oldest entries are evicted.
+	pub fn new(session: &Session, max_length: usize) -> Result<Self> {
+		let mut entries = Vec::new();
+		for input in session.inputs.iter().filter(|i| i.name.starts_with("past")) {
+			let suffix = input
+				.name
+				.strip_prefix("past_key_values")
+				.or_else(|| input.name.strip_prefix("past"))
+				.unwrap_or_default();
+			let output_name = format!("present{suffix}");
+			if !session.outputs.iter().any(|o| o.name == output_name) {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Model has KV cache input `{}`, but no corresponding output `{output_name}`", input.name)
+				));
+			}
+
+			let dimensions = match input.input_type.tensor_dimensions() {
+				Some(dimensions) if dimensions.len() >= 3 => dimensions.clone(),
+				_ => {
+					return Err(Error::new_with_code(
+						ErrorCode::InvalidArgument,
+						format!("KV cache input `{}` should be a tensor with at least 3 dimensions; got {}", input.name, input.input_type)
+					));
+				}
+			};
+			entries.push(KvCacheEntry {
+				input_name: input.name.clone(),
+				output_name,
+				dimensions,
+				value: None
+			});
+		}
+
+		if entries.is_empty() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Model has no KV cache (`past*`) inputs"));
+		}
+
+		Ok(Self { entries, max_length, batch_size: 1 })
+	}
+
+	/// Configures the batch size used for the initial empty cache; defaults to `1`.
+	pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+		self.batch_size = batch_size;
+		self
+	}
+
+	/// Returns the maximum number of tokens held by the cache.
+	pub fn max_length(&self) -> usize {
+		self.max_length
+	}
+
+	/// Returns the number of tokens currently held by the cache.
+	pub fn seq_len(&self) -> usize {
+		self.entries
+			.first()
+			.and_then(|e| e.value.as_ref().and_then(|v| e.seq_len_of(v).ok()))
+			.unwrap_or(0)
+	}
+
+	/// Clears the cache, so the next step starts a new sequence.
+	pub fn reset(&mut self) {
+		for entry in &mut self.entries {
+			entry.value = None;
+		}
+	}
+
+	/// Returns the `past` inputs for the next step, which should be combined with the model's other inputs.
+	///
+	/// Before the first call to [`KvCacheManager::update`], this returns empty tensors with a sequence length of `0`.
+	pub fn inputs(&self) -> Result<Vec<(Cow<'_, str>, SessionInputValue<'_>)>> {
+		self.entries
+			.iter()
+			.map(|entry| {
+				let value = match &entry.value {
+					Some(value) => SessionInputValue::from(value.view()),
+					None => {
+						let seq_axis = entry.seq_axis().index();
+						let shape = entry
+							.dimensions
+							.iter()
+							.enumerate()
+							.map(|(i, &d)| match d {
+								_ if i == seq_axis => Ok(0),
+								-1 if i == 0 => Ok(self.batch_size),
+								-1 => Err(Error::new(format!("Cannot infer dimension {i} of KV cache input `{}`", entry.input_name))),
+								d => Ok(d as usize)
+							})
+							.collect::<Result<Vec<_>>>()?;
+						SessionInputValue::from(Tensor::from_array(Array::<f32, _>::zeros(IxDyn(&shape)))?)
+					}
+				};
+				Ok((Cow::Borrowed(entry.input_name.as_str()), value))
+			})
+			.collect()
+	}
+
+	/// Updates the cache with the `present` outputs of a step, removing them from `outputs`.
+	///
+	/// Outputs which are longer than the current cache are assumed to contain the full cache (past + new tokens) and
+	/// replace it; shorter outputs are assumed to only contain the keys & values of the new tokens, and are appended
+	/// to the cache.
+	///
+	/// The cache & `outputs` are only modified if all outputs could be processed; if an error is returned, both are left
+	/// as they were before the call.
+	pub fn update(&mut self, outputs: &mut SessionOutputs<'_, '_>) -> Result<()> {
+		// compute the new cache values from borrowed outputs first, so nothing is removed from `outputs` if any of them
+		// fails; `None` means the output replaces the cache as-is
+		let mut new_values: Vec<Option<Tensor<f32>>> = Vec::with_capacity(self.entries.len());
+		for entry in &self.entries {
+			let present = outputs
+				.get(&entry.output_name)
+				.ok_or_else(|| Error::new(format!("Session outputs are missing KV cache output `{}`", entry.output_name)))?
+				.downcast_ref::<TensorValueType<f32>>()?;
+
+			let seq_axis = entry.seq_axis();
+			let present_len = entry.seq_len_of(&present)?;
+			let mut value = match &entry.value {
+				Some(past) if present_len <= entry.seq_len_of(past)? => {
+					entry.ensure_cpu_accessible(past)?;
+					entry.ensure_cpu_accessible(&present)?;
+					let combined = concatenate(seq_axis, &[past.extract_tensor(), present.extract_tensor()]).map_err(Error::wrap)?;
+					Some(Tensor::from_array(combined)?)
+				}
+				_ => None
+			};
+
+			let full = value.as_ref().unwrap_or(&*present);
+			let len = entry.seq_len_of(full)?;
+			if len > self.max_length {
+				entry.ensure_cpu_accessible(full)?;
+				let recent = full.extract_tensor().slice_axis(seq_axis, Slice::from(len - self.max_length..)).to_owned();
+				value = Some(Tensor::from_array(recent)?);
+			}
+			new_values.push(value);
+		}
+
+		for (entry, value) in self.entries.iter_mut().zip(new_values) {
+			let present = outputs.remove(&entry.output_name).expect("output presence was checked above");
+			entry.value = Some(match value {
+				Some(value) => value,
+				None => present.downcast::<TensorValueType<f32>>().expect("output type was checked above")
+			});
+		}
+		Ok(())
+	}
+}
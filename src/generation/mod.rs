@@ -0,0 +1,7 @@
+//! Helpers for autoregressive generation with language models.
+
+mod beam_search;
+mod kv_cache;
+mod stream;
+
+pub use self::{beam_search::BeamSearchDecoder, kv_cache::KvCacheManager, stream::{GenerationInputs, GenerationStream}};
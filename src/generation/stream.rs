@@ -0,0 +1,194 @@
+use std::borrow::Cow;
+
+use ndarray::{ArrayView1, Ix2, Ix3, s};
+
+use crate::{
+	error::{Error, ErrorCode, Result},
+	session::{Session, SessionInputValue}
+};
+
+/// The inputs for one step of a [`GenerationStream`], as produced by the `ort::inputs!["name" => value, ...]` macro.
+pub type GenerationInputs = Vec<(Cow<'static, str>, SessionInputValue<'static>)>;
+
+/// An iterator which autoregressively generates tokens from a language model, one token per step.
+///
+/// Each step, the stream calls `make_inputs` with all tokens so far (the prompt, followed by all generated tokens) to
+/// create the model's inputs, runs the session, and picks the next token from the logits of the last position using
+/// the sampler. The model's first output must be a `float32` tensor of logits with shape `[1, sequence, vocab]` or
+/// `[1, vocab]`.
+///
+/// By default, tokens are picked greedily (i.e. the most likely token is always chosen); use
+/// [`GenerationStream::with_sampler`] to use a different sampling strategy, like `ort::sampling::top_k_sample` (with
+/// the `sampling` feature).
+///
+/// The stream ends when the end-of-sequence token is generated (see [`GenerationStream::with_eos_token`]), or after
+/// the first error; use [`Iterator::take`] to limit the number of generated tokens.
+///
+/// ```no_run
+/// # use ort::{generation::GenerationStream, session::Session, value::Tensor};
+/// # fn main() -> ort::Result<()> {
+/// let session = Session::builder()?.commit_from_file("gpt2.onnx")?;
+/// let stream = GenerationStream::new(&session, vec![464, 2068, 7586], |tokens| {
+/// 	ort::inputs!["input_ids" => Tensor::from_array(([1, tokens.len()], tokens.to_vec()))?]
+/// })
+/// .with_eos_token(50256);
+///
+/// let generated = stream.take(50).collect::<ort::Result<Vec<i64>>>()?;
+/// # 	Ok(())
+/// # }
+/// ```
+pub struct GenerationStream<'s, F, S = fn(ArrayView1<'_, f32>) -> usize> {
+	session: &'s Session,
+	tokens: Vec<i64>,
+	prompt_len: usize,
+	make_inputs: F,
+	sampler: S,
+	eos_token: Option<i64>,
+	finished: bool
+}
+
+impl<'s, F> GenerationStream<'s, F>
+where
+	F: FnMut(&[i64]) -> Result<GenerationInputs>
+{
+	/// Creates a new generation stream, which starts generating after the given `prompt` tokens.
+	pub fn new(session: &'s Session, prompt: impl Into<Vec<i64>>, make_inputs: F) -> Self {
+		let tokens = prompt.into();
+		Self {
+			session,
+			prompt_len: tokens.len(),
+			tokens,
+			make_inputs,
+			sampler: greedy,
+			eos_token: None,
+			finished: false
+		}
+	}
+}
+
+impl<'s, F, S> GenerationStream<'s, F, S> {
+	/// Configures the function used to pick the next token from the logits of the last position.
+	///
+	/// ```no_run
+	/// # use ort::{generation::GenerationStream, session::Session, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// # let session = Session::builder()?.commit_from_file("gpt2.onnx")?;
+	/// // Never pick token 0.
+	/// let stream = GenerationStream::new(&session, vec![464], |tokens| {
+	/// 	ort::inputs!["input_ids" => Tensor::from_array(([1, tokens.len()], tokens.to_vec()))?]
+	/// })
+	/// .with_sampler(|logits| {
+	/// 	logits
+	/// 		.iter()
+	/// 		.enumerate()
+	/// 		.skip(1)
+	/// 		.max_by(|(_, a), (_, b)| a.total_cmp(b))
+	/// 		.map_or(0, |(i, _)| i)
+	/// });
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_sampler<S2>(self, sampler: S2) -> GenerationStream<'s, F, S2>
+	where
+		S2: FnMut(ArrayView1<'_, f32>) -> usize
+	{
+		GenerationStream {
+			session: self.session,
+			tokens: self.tokens,
+			prompt_len: self.prompt_len,
+			make_inputs: self.make_inputs,
+			sampler,
+			eos_token: self.eos_token,
+			finished: self.finished
+		}
+	}
+
+	/// Configures the end-of-sequence token; the stream ends (without yielding it) once this token is generated.
+	pub fn with_eos_token(mut self, eos_token: i64) -> Self {
+		self.eos_token = Some(eos_token);
+		self
+	}
+
+	/// Returns all tokens so far, including the prompt.
+	pub fn tokens(&self) -> &[i64] {
+		&self.tokens
+	}
+
+	/// Returns only the generated tokens, excluding the prompt.
+	pub fn generated(&self) -> &[i64] {
+		&self.tokens[self.prompt_len..]
+	}
+}
+
+impl<F, S> GenerationStream<'_, F, S>
+where
+	F: FnMut(&[i64]) -> Result<GenerationInputs>,
+	S: FnMut(ArrayView1<'_, f32>) -> usize
+{
+	fn step(&mut self) -> Result<i64> {
+		let inputs = (self.make_inputs)(&self.tokens)?;
+		let outputs = self.session.run(inputs)?;
+		let logits = outputs[0].try_extract_tensor::<f32>()?;
+		let token = match logits.ndim() {
+			3 => (self.sampler)(logits.into_dimensionality::<Ix3>().map_err(Error::wrap)?.slice(s![0, -1, ..])),
+			2 => (self.sampler)(logits.into_dimensionality::<Ix2>().map_err(Error::wrap)?.slice(s![-1, ..])),
+			_ => {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Expected logits of shape [1, sequence, vocab] or [1, vocab]; got {:?}", logits.shape())
+				));
+			}
+		};
+		Ok(token as i64)
+	}
+}
+
+impl<F, S> Iterator for GenerationStream<'_, F, S>
+where
+	F: FnMut(&[i64]) -> Result<GenerationInputs>,
+	S: FnMut(ArrayView1<'_, f32>) -> usize
+{
+	type Item = Result<i64>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.finished {
+			return None;
+		}
+
+		match self.step() {
+			Ok(token) if Some(token) == self.eos_token => {
+				self.finished = true;
+				None
+			}
+			Ok(token) => {
+				self.tokens.push(token);
+				Some(Ok(token))
+			}
+			Err(e) => {
+				self.finished = true;
+				Some(Err(e))
+			}
+		}
+	}
+}
+
+fn greedy(logits: ArrayView1<'_, f32>) -> usize {
+	logits
+		.iter()
+		.enumerate()
+		.fold((0, f32::NEG_INFINITY), |(best, best_logit), (i, &logit)| if logit > best_logit { (i, logit) } else { (best, best_logit) })
+		.0
+}
+
+#[cfg(test)]
+mod tests {
+	use ndarray::arr1;
+
+	use super::greedy;
+
+	#[test]
+	fn test_greedy() {
+		assert_eq!(greedy(arr1(&[0.1, 2.5, -1.0, 2.4]).view()), 1);
+		assert_eq!(greedy(arr1(&[f32::NEG_INFINITY, -3.0]).view()), 1);
+	}
+}
@@ -19,10 +19,20 @@ pub mod adapter;
 pub mod environment;
 pub mod error;
 pub mod execution_providers;
+#[cfg(feature = "ndarray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+pub mod generation;
 pub mod io_binding;
 pub mod memory;
 pub mod metadata;
+pub(crate) mod model_proto;
 pub mod operator;
+#[cfg(feature = "ndarray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+pub mod preprocessing;
+#[cfg(feature = "sampling")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sampling")))]
+pub mod sampling;
 pub mod session;
 pub mod tensor;
 #[cfg(feature = "training")]
@@ -36,7 +46,7 @@ use std::sync::Arc;
 use std::{
 	ffi::CStr,
 	os::raw::c_char,
-	ptr::{self, NonNull},
+	ptr::NonNull,
 	sync::OnceLock
 };
 
@@ -46,7 +56,7 @@ pub use ort_sys as sys;
 pub use self::environment::init_from;
 pub use self::{
 	environment::init,
-	error::{Error, ErrorCode, Result}
+	error::{Error, ErrorCode, ErrorKind, Result}
 };
 
 #[cfg(not(all(target_arch = "x86", target_os = "windows")))]
@@ -66,7 +76,9 @@ macro_rules! extern_system_fn {
 
 pub(crate) use extern_system_fn;
 
-/// The minor version of ONNX Runtime used by this version of `ort`.
+/// The minor version of ONNX Runtime used by this version of `ort`, i.e. the version of the C API `ort` requests from the
+/// linked library. The version of the library actually linked can be queried with [`runtime_version`].
+#[doc(alias = "api_version")]
 pub const MINOR_VERSION: u32 = ort_sys::ORT_API_VERSION;
 
 #[cfg(feature = "load-dynamic")]
@@ -117,6 +129,9 @@ pub(crate) fn lib_handle() -> &'static libloading::Library {
 /// println!("{}", ort::info());
 /// // ORT Build Info: git-branch=rel-1.19.0, git-commit-id=26250ae, build type=Release, cmake cxx flags: /DWIN32 /D_WINDOWS /EHsc /Zc:__cplusplus /EHsc /wd26812 -DEIGEN_HAS_C99_MATH -DCPUINFO_SUPPORTED
 /// ```
+///
+/// This is useful to include in logs or bug reports. For a parsed version, see [`runtime_build_info`].
+#[doc(alias = "build_info")]
 pub fn info() -> &'static str {
 	let str = unsafe { ortsys![GetBuildInfoString]() };
 	let mut len = 0;
@@ -126,6 +141,105 @@ pub fn info() -> &'static str {
 	unsafe { std::str::from_utf8_unchecked(std::slice::from_raw_parts(str.cast::<u8>(), len)) }
 }
 
+fn api_base() -> &'static ort_sys::OrtApiBase {
+	#[cfg(feature = "load-dynamic")]
+	let base: *const ort_sys::OrtApiBase = unsafe {
+		let base_getter: libloading::Symbol<unsafe extern "C" fn() -> *const ort_sys::OrtApiBase> = lib_handle()
+			.get(b"OrtGetApiBase")
+			.expect("`OrtGetApiBase` must be present in ONNX Runtime dylib");
+		base_getter()
+	};
+	#[cfg(not(feature = "load-dynamic"))]
+	let base: *const ort_sys::OrtApiBase = unsafe { ort_sys::OrtGetApiBase() };
+	unsafe { base.as_ref() }.expect("`OrtGetApiBase` returned null")
+}
+
+/// Returns the version of the ONNX Runtime binary `ort` is linked to, e.g. `1.20.1`.
+///
+/// Unlike [`MINOR_VERSION`], which is the version `ort` was *built for*, this queries the library at runtime, which
+/// may differ when using the `load-dynamic` feature.
+///
+/// ```
+/// println!("ONNX Runtime v{}", ort::runtime_version());
+/// ```
+pub fn runtime_version() -> &'static str {
+	let get_version_string: extern_system_fn! { unsafe fn () -> *const c_char } =
+		api_base().GetVersionString.expect("`GetVersionString` must be present in `OrtApiBase`");
+	unsafe { CStr::from_ptr(get_version_string()) }.to_str().unwrap_or("<invalid version>")
+}
+
+fn runtime_minor_version() -> u32 {
+	runtime_version().split('.').nth(1).map_or(0, |x| x.parse::<u32>().unwrap_or(0))
+}
+
+/// Checks that the linked ONNX Runtime binary supports the version of the C API `ort` was built for, returning an
+/// [`ErrorKind::VersionMismatch`] error if it is too old.
+pub(crate) fn check_runtime_version() -> Result<()> {
+	if runtime_minor_version() >= MINOR_VERSION {
+		return Ok(());
+	}
+
+	let expected = format!("1.{MINOR_VERSION}.x");
+	let found = runtime_version().to_owned();
+	#[cfg(feature = "load-dynamic")]
+	let location = format!(" found at `{}`", dylib_path());
+	#[cfg(not(feature = "load-dynamic"))]
+	let location = String::new();
+	let message = format!(
+		"ort {} is not compatible with the ONNX Runtime binary{location}; expected ONNX Runtime v{expected}, but found v{found}",
+		env!("CARGO_PKG_VERSION")
+	);
+	Err(Error::new_with_kind(ErrorCode::GenericFailure, ErrorKind::VersionMismatch { expected, found }, message))
+}
+
+/// Information about the build of ONNX Runtime `ort` is linked to; see [`runtime_build_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BuildInfo {
+	/// The version of ONNX Runtime, as returned by [`runtime_version`].
+	pub ort_version: String,
+	/// The Git branch ONNX Runtime was built from, e.g. `rel-1.20.1`.
+	pub git_branch: Option<String>,
+	/// The (abbreviated) Git commit hash ONNX Runtime was built from.
+	pub git_commit_id: Option<String>,
+	/// The CMake build type, e.g. `Release` or `RelWithDebInfo`.
+	pub build_type: Option<String>
+}
+
+impl BuildInfo {
+	fn parse(ort_version: &str, info: &str) -> Self {
+		let mut build_info = BuildInfo {
+			ort_version: ort_version.to_owned(),
+			git_branch: None,
+			git_commit_id: None,
+			build_type: None
+		};
+		let info = info.strip_prefix("ORT Build Info:").unwrap_or(info);
+		for (key, value) in info.split(',').filter_map(|field| field.split_once('=')) {
+			let value = Some(value.trim().to_owned());
+			match key.trim() {
+				"git-branch" => build_info.git_branch = value,
+				"git-commit-id" => build_info.git_commit_id = value,
+				"build type" => build_info.build_type = value,
+				_ => {}
+			}
+		}
+		build_info
+	}
+}
+
+/// Returns structured information about the build of ONNX Runtime `ort` is linked to, parsed from [`info`].
+///
+/// Note that ONNX Runtime does not report the versions of any dependencies it was built against (e.g. CUDA or cuDNN).
+///
+/// ```
+/// let build_info = ort::runtime_build_info();
+/// println!("ONNX Runtime v{} ({:?})", build_info.ort_version, build_info.git_commit_id);
+/// ```
+pub fn runtime_build_info() -> BuildInfo {
+	BuildInfo::parse(runtime_version(), info())
+}
+
 /// Returns a pointer to the global [`ort_sys::OrtApi`] object.
 ///
 /// # Panics
@@ -158,49 +272,26 @@ pub fn api() -> &'static ort_sys::OrtApi {
 
 	let ptr = G_ORT_API
 		.get_or_init(|| {
+			let base = api_base();
 			#[cfg(feature = "load-dynamic")]
-			unsafe {
-				let dylib = lib_handle();
-				let base_getter: libloading::Symbol<unsafe extern "C" fn() -> *const ort_sys::OrtApiBase> = dylib
-					.get(b"OrtGetApiBase")
-					.expect("`OrtGetApiBase` must be present in ONNX Runtime dylib");
-				let base: *const ort_sys::OrtApiBase = base_getter();
-				assert_ne!(base, ptr::null());
-
-				let get_version_string: extern_system_fn! { unsafe fn () -> *const c_char } =
-					(*base).GetVersionString.expect("`GetVersionString` must be present in `OrtApiBase`");
-				let version_string = get_version_string();
-				let version_string = CStr::from_ptr(version_string).to_string_lossy();
+			{
+				let version_string = runtime_version();
 				tracing::info!("Loaded ONNX Runtime dylib with version '{version_string}'");
 
-				let lib_minor_version = version_string.split('.').nth(1).map_or(0, |x| x.parse::<u32>().unwrap_or(0));
-				match lib_minor_version.cmp(&MINOR_VERSION) {
-					std::cmp::Ordering::Less => panic!(
-						"ort {} is not compatible with the ONNX Runtime binary found at `{}`; expected GetVersionString to return '1.{MINOR_VERSION}.x', but got '{version_string}'",
-						env!("CARGO_PKG_VERSION"),
-						dylib_path()
-					),
-					std::cmp::Ordering::Greater => tracing::warn!(
+				if let Err(e) = check_runtime_version() {
+					panic!("{e}");
+				}
+				if runtime_minor_version() > MINOR_VERSION {
+					tracing::warn!(
 						"ort {} may have compatibility issues with the ONNX Runtime binary found at `{}`; expected GetVersionString to return '1.{MINOR_VERSION}.x', but got '{version_string}'",
 						env!("CARGO_PKG_VERSION"),
 						dylib_path()
-					),
-					std::cmp::Ordering::Equal => {}
-				};
-				let get_api: extern_system_fn! { unsafe fn(u32) -> *const ort_sys::OrtApi } =
-					(*base).GetApi.expect("`GetApi` must be present in `OrtApiBase`");
-				let api: *const ort_sys::OrtApi = get_api(ort_sys::ORT_API_VERSION);
-				ApiPointer(NonNull::new(api.cast_mut()).expect("Failed to initialize ORT API"))
-			}
-			#[cfg(not(feature = "load-dynamic"))]
-			unsafe {
-				let base: *const ort_sys::OrtApiBase = ort_sys::OrtGetApiBase();
-				assert_ne!(base, ptr::null());
-				let get_api: extern_system_fn! { unsafe fn(u32) -> *const ort_sys::OrtApi } =
-					(*base).GetApi.expect("`GetApi` must be present in `OrtApiBase`");
-				let api: *const ort_sys::OrtApi = get_api(ort_sys::ORT_API_VERSION);
-				ApiPointer(NonNull::new(api.cast_mut()).expect("Failed to initialize ORT API"))
+					);
+				}
 			}
+			let get_api: extern_system_fn! { unsafe fn(u32) -> *const ort_sys::OrtApi } = base.GetApi.expect("`GetApi` must be present in `OrtApiBase`");
+			let api: *const ort_sys::OrtApi = unsafe { get_api(ort_sys::ORT_API_VERSION) };
+			ApiPointer(NonNull::new(api.cast_mut()).expect("Failed to initialize ORT API"))
 		})
 		.0;
 	unsafe { ptr.as_ref() }
@@ -303,4 +394,11 @@ mod test {
 		let ptr = s.as_c_str().as_ptr();
 		assert_eq!("foo", char_p_to_string(ptr).expect("failed to convert string"));
 	}
+
+	#[test]
+	fn test_available_providers() {
+		let providers = execution_providers::list_available().expect("failed to get available providers");
+		assert!(providers.iter().any(|p| p == "CPUExecutionProvider"));
+		assert!(info().starts_with("ORT Build Info"));
+	}
 }
@@ -65,6 +65,11 @@ use crate::{
 /// # }
 /// ```
 ///
+/// # Memory usage
+/// The version of the ONNX Runtime API `ort` targets does not expose allocator statistics, so there is no way to query
+/// how much memory a session or its allocators are using through `ort`. To measure how much device memory a model
+/// takes, query the device's driver instead (e.g. NVML for CUDA devices) before & after committing the session.
+///
 /// [`Value`]: crate::value::Value
 #[derive(Debug)]
 pub struct Allocator {
@@ -97,7 +102,7 @@ impl Allocator {
 	/// Allocates a block of memory, of size `size_of::<T>() * len` bytes, using this allocator.
 	/// The memory will be automatically freed when the returned `AllocatedBlock` goes out of scope.
 	///
-	/// May return `None` if the allocation fails.
+	/// May return `None` if the allocation fails, or if the size of the allocation overflows `usize`.
 	///
 	/// # Example
 	/// ```
@@ -110,11 +115,12 @@ impl Allocator {
 	/// };
 	/// ```
 	pub fn alloc<T>(&self, len: usize) -> Option<AllocatedBlock<'_>> {
+		let size = len.checked_mul(mem::size_of::<T>())?;
 		let ptr = unsafe {
 			self.ptr
 				.as_ref()
 				.Alloc
-				.unwrap_or_else(|| unreachable!("Allocator method `Alloc` is null"))(self.ptr.as_ptr(), (len * mem::size_of::<T>()) as _)
+				.unwrap_or_else(|| unreachable!("Allocator method `Alloc` is null"))(self.ptr.as_ptr(), size as _)
 		};
 		if !ptr.is_null() { Some(AllocatedBlock { ptr, allocator: self }) } else { None }
 	}
@@ -142,7 +148,20 @@ impl Allocator {
 		self.ptr.as_ref().Free.unwrap_or_else(|| unreachable!("Allocator method `Free` is null"))(self.ptr.as_ptr(), ptr.cast());
 	}
 
-	/// Returns the [`MemoryInfo`] describing this allocator.
+	/// Returns the [`MemoryInfo`] describing this allocator, which can be used to check which device it allocates memory
+	/// on.
+	///
+	/// ```
+	/// # use ort::memory::{Allocator, AllocationDevice, DeviceType};
+	/// # fn main() -> ort::Result<()> {
+	/// let allocator = Allocator::default();
+	/// let memory_info = allocator.memory_info();
+	/// assert_eq!(memory_info.allocation_device(), AllocationDevice::CPU);
+	/// assert_eq!(memory_info.device_type(), DeviceType::CPU);
+	/// assert_eq!(memory_info.device_id(), 0);
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn memory_info(&self) -> MemoryInfo {
 		let memory_info_ptr = unsafe { self.ptr.as_ref().Info.unwrap_or_else(|| unreachable!("Allocator method `Info` is null"))(self.ptr.as_ptr()) };
 		MemoryInfo::from_raw(unsafe { NonNull::new_unchecked(memory_info_ptr.cast_mut()) }, false)
@@ -150,6 +169,12 @@ impl Allocator {
 
 	/// Creates a new [`Allocator`] for the given session, to allocate memory on the device described in the
 	/// [`MemoryInfo`].
+	///
+	/// The session must have been created with an execution provider for the given device (e.g. the CUDA execution
+	/// provider for [`AllocationDevice::CUDA`]); otherwise, this returns an error. The allocator keeps the session
+	/// alive, so it can be used to allocate buffers that are reused across runs.
+	#[doc(alias = "allocator_for")]
+	#[doc(alias = "new_for_device")]
 	pub fn new(session: &Session, memory_info: MemoryInfo) -> Result<Self> {
 		let mut allocator_ptr: *mut ort_sys::OrtAllocator = std::ptr::null_mut();
 		ortsys![unsafe CreateAllocator(session.ptr(), memory_info.ptr.as_ptr(), &mut allocator_ptr)?; nonNull(allocator_ptr)];
@@ -464,6 +489,10 @@ impl MemoryInfo {
 	/// # Ok(())
 	/// # }
 	/// ```
+	///
+	/// The name of the device, as used by ONNX Runtime to identify its allocators, can be retrieved with
+	/// [`AllocationDevice::as_str`].
+	#[doc(alias = "allocator_name")]
 	pub fn allocation_device(&self) -> AllocationDevice {
 		let mut name_ptr: *const c_char = std::ptr::null_mut();
 		ortsys![unsafe MemoryInfoGetName(self.ptr.as_ptr(), &mut name_ptr)];
@@ -0,0 +1,627 @@
+//! Minimal, dependency-free editing of serialized ONNX `ModelProto`s.
+//!
+//! Only the bare minimum of the protobuf wire format required to patch a model's graph is implemented here; fields we
+//! don't touch are copied through verbatim.
+
+use crate::{
+	error::{Error, ErrorCode, Result},
+	session::GraphNode
+};
+
+/// `ModelProto.graph`
+const MODEL_GRAPH_FIELD: u64 = 7;
+/// `GraphProto.node`
+const GRAPH_NODE_FIELD: u64 = 1;
+/// `GraphProto.initializer`
+const GRAPH_INITIALIZER_FIELD: u64 = 5;
+/// `GraphProto.input`
+#[cfg(any(feature = "half", feature = "training"))]
+const GRAPH_INPUT_FIELD: u64 = 11;
+/// `GraphProto.output`
+const GRAPH_OUTPUT_FIELD: u64 = 12;
+/// `ValueInfoProto.name`
+const VALUE_INFO_NAME_FIELD: u64 = 1;
+
+const WIRE_VARINT: u64 = 0;
+const WIRE_I64: u64 = 1;
+const WIRE_LEN: u64 = 2;
+const WIRE_I32: u64 = 5;
+
+fn invalid_model() -> Error {
+	Error::new_with_code(ErrorCode::InvalidProtobuf, "Could not parse model as an ONNX protobuf; only `.onnx` models can be edited")
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64> {
+	let mut value = 0u64;
+	for shift in (0..64).step_by(7) {
+		let byte = *bytes.get(*pos).ok_or_else(invalid_model)?;
+		*pos += 1;
+		value |= u64::from(byte & 0x7f) << shift;
+		if byte & 0x80 == 0 {
+			return Ok(value);
+		}
+	}
+	Err(invalid_model())
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+	while value >= 0x80 {
+		out.push((value as u8) | 0x80);
+		value >>= 7;
+	}
+	out.push(value as u8);
+}
+
+fn write_len_field(out: &mut Vec<u8>, field: u64, data: &[u8]) {
+	write_varint(out, (field << 3) | WIRE_LEN);
+	write_varint(out, data.len() as u64);
+	out.extend_from_slice(data);
+}
+
+fn write_varint_field(out: &mut Vec<u8>, field: u64, value: u64) {
+	write_varint(out, (field << 3) | WIRE_VARINT);
+	write_varint(out, value);
+}
+
+/// A single field of a serialized protobuf message.
+struct Field<'a> {
+	number: u64,
+	wire_type: u64,
+	/// The entire encoded field, including its tag.
+	raw: &'a [u8],
+	/// The field's payload; for length-delimited fields, this excludes the length prefix.
+	payload: &'a [u8]
+}
+
+impl Field<'_> {
+	fn as_varint(&self) -> Result<u64> {
+		read_varint(self.payload, &mut 0)
+	}
+}
+
+fn fields(message: &[u8]) -> impl Iterator<Item = Result<Field<'_>>> {
+	let mut pos = 0;
+	std::iter::from_fn(move || {
+		if pos >= message.len() {
+			return None;
+		}
+		let mut parse = || {
+			let start = pos;
+			let tag = read_varint(message, &mut pos)?;
+			let wire_type = tag & 7;
+			let payload_start = match wire_type {
+				WIRE_VARINT => {
+					let payload_start = pos;
+					read_varint(message, &mut pos)?;
+					payload_start
+				}
+				WIRE_I64 => {
+					pos += 8;
+					pos - 8
+				}
+				WIRE_I32 => {
+					pos += 4;
+					pos - 4
+				}
+				WIRE_LEN => {
+					let len = read_varint(message, &mut pos)? as usize;
+					let payload_start = pos;
+					pos = pos.checked_add(len).ok_or_else(invalid_model)?;
+					payload_start
+				}
+				_ => return Err(invalid_model())
+			};
+			Ok(Field {
+				number: tag >> 3,
+				wire_type,
+				raw: message.get(start..pos).ok_or_else(invalid_model)?,
+				payload: message.get(payload_start..pos).ok_or_else(invalid_model)?
+			})
+		};
+		let field = parse();
+		if field.is_err() {
+			// stop iterating after an error
+			pos = message.len();
+		}
+		Some(field)
+	})
+}
+
+/// Rewrites the (first) graph of a serialized `ModelProto` using `edit`, copying all other fields verbatim.
+fn edit_graph(model: &[u8], edit: impl FnOnce(&[u8]) -> Result<Vec<u8>>) -> Result<Vec<u8>> {
+	let mut out = Vec::with_capacity(model.len());
+	let mut edit = Some(edit);
+	for field in fields(model) {
+		let field = field?;
+		if field.number == MODEL_GRAPH_FIELD && field.wire_type == WIRE_LEN {
+			if let Some(edit) = edit.take() {
+				write_len_field(&mut out, MODEL_GRAPH_FIELD, &edit(field.payload)?);
+				continue;
+			}
+		}
+		out.extend_from_slice(field.raw);
+	}
+
+	if edit.is_some() {
+		return Err(Error::new_with_code(ErrorCode::InvalidGraph, "Model does not contain a graph"));
+	}
+	Ok(out)
+}
+
+/// Appends outputs with the given names to the graph of a serialized `ModelProto`.
+///
+/// The outputs are added without type information, which ONNX Runtime will infer when the session is created.
+pub(crate) fn add_graph_outputs(model: &[u8], names: &[String]) -> Result<Vec<u8>> {
+	edit_graph(model, |graph| {
+		let mut graph = graph.to_vec();
+		for name in names {
+			let mut value_info = Vec::with_capacity(name.len() + 2);
+			write_len_field(&mut value_info, VALUE_INFO_NAME_FIELD, name.as_bytes());
+			write_len_field(&mut graph, GRAPH_OUTPUT_FIELD, &value_info);
+		}
+		Ok(graph)
+	})
+}
+
+fn string(bytes: &[u8]) -> String {
+	String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Returns the serialized main graph of a serialized `ModelProto`.
+fn main_graph(model: &[u8]) -> Result<&[u8]> {
+	fields(model)
+		.collect::<Result<Vec<_>>>()?
+		.into_iter()
+		.find(|f| f.number == MODEL_GRAPH_FIELD && f.wire_type == WIRE_LEN)
+		.map(|f| f.payload)
+		.ok_or_else(|| Error::new_with_code(ErrorCode::InvalidGraph, "Model does not contain a graph"))
+}
+
+/// Parses the names of the inputs of the main graph of a serialized `ModelProto`.
+#[cfg(feature = "training")]
+pub(crate) fn graph_input_names(model: &[u8]) -> Result<Vec<String>> {
+	let mut names = Vec::new();
+	for field in fields(main_graph(model)?) {
+		let field = field?;
+		if field.number != GRAPH_INPUT_FIELD || field.wire_type != WIRE_LEN {
+			continue;
+		}
+		for value_info_field in fields(field.payload) {
+			let value_info_field = value_info_field?;
+			if value_info_field.number == VALUE_INFO_NAME_FIELD && value_info_field.wire_type == WIRE_LEN {
+				names.push(string(value_info_field.payload));
+			}
+		}
+	}
+	Ok(names)
+}
+
+/// The declared type & shape of an initializer in a serialized `ModelProto`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct InitializerInfo {
+	pub name: String,
+	/// The `TensorProto.DataType` of the initializer, which shares its values with `ONNXTensorElementDataType`.
+	pub data_type: u64,
+	pub dims: Vec<i64>
+}
+
+/// Parses the names, types, and shapes of the initializers of the main graph of a serialized `ModelProto`.
+pub(crate) fn graph_initializers(model: &[u8]) -> Result<Vec<InitializerInfo>> {
+	let mut initializers = Vec::new();
+	for field in fields(main_graph(model)?) {
+		let field = field?;
+		if field.number != GRAPH_INITIALIZER_FIELD || field.wire_type != WIRE_LEN {
+			continue;
+		}
+
+		let mut initializer = InitializerInfo { name: String::new(), data_type: 0, dims: Vec::new() };
+		for tensor_field in fields(field.payload) {
+			let tensor_field = tensor_field?;
+			// see `TensorProto` in onnx.proto
+			match (tensor_field.number, tensor_field.wire_type) {
+				(1, WIRE_VARINT) => initializer.dims.push(tensor_field.as_varint()? as i64),
+				(1, WIRE_LEN) => {
+					let mut pos = 0;
+					while pos < tensor_field.payload.len() {
+						initializer.dims.push(read_varint(tensor_field.payload, &mut pos)? as i64);
+					}
+				}
+				(2, WIRE_VARINT) => initializer.data_type = tensor_field.as_varint()?,
+				(8, WIRE_LEN) => initializer.name = string(tensor_field.payload),
+				_ => {}
+			}
+		}
+		initializers.push(initializer);
+	}
+	Ok(initializers)
+}
+
+/// Parses the nodes of the main graph of a serialized `ModelProto`.
+pub(crate) fn graph_nodes(model: &[u8]) -> Result<Vec<GraphNode>> {
+	let mut nodes = Vec::new();
+	for field in fields(main_graph(model)?) {
+		let field = field?;
+		if field.number != GRAPH_NODE_FIELD || field.wire_type != WIRE_LEN {
+			continue;
+		}
+
+		let mut node = GraphNode::default();
+		for node_field in fields(field.payload) {
+			let node_field = node_field?;
+			if node_field.wire_type != WIRE_LEN {
+				continue;
+			}
+			// see `NodeProto` in onnx.proto
+			match node_field.number {
+				1 => node.inputs.push(string(node_field.payload)),
+				2 => node.outputs.push(string(node_field.payload)),
+				3 => node.name = string(node_field.payload),
+				4 => node.op_type = string(node_field.payload),
+				5 => {
+					for attribute_field in fields(node_field.payload) {
+						let attribute_field = attribute_field?;
+						if attribute_field.number == 1 && attribute_field.wire_type == WIRE_LEN {
+							node.attributes.push(string(attribute_field.payload));
+						}
+					}
+				}
+				7 => node.domain = string(node_field.payload),
+				_ => {}
+			}
+		}
+		nodes.push(node);
+	}
+	Ok(nodes)
+}
+
+/// Renames outputs of the main graph of a serialized `ModelProto`, given pairs of `(current name, new name)`.
+///
+/// Rather than renaming the value throughout the graph, an `Identity` node producing the new name from the old one is
+/// appended to the graph, and the output is redirected to it. The new names must therefore not already name a value in
+/// the graph (including the outputs being renamed).
+#[cfg(feature = "training")]
+pub(crate) fn rename_graph_outputs(model: &[u8], renames: &[(&str, &str)]) -> Result<Vec<u8>> {
+	let nodes = graph_nodes(model)?;
+	let inputs = graph_input_names(model)?;
+	let initializers = graph_initializers(model)?;
+	for (from, to) in renames {
+		let exists = renames.iter().any(|(from, _)| from == to)
+			|| nodes.iter().any(|node| node.outputs.iter().any(|output| output == to))
+			|| inputs.iter().any(|input| input == to)
+			|| initializers.iter().any(|initializer| initializer.name == *to);
+		if exists {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot rename output `{from}` to `{to}`, since the graph already has a value named `{to}`")
+			));
+		}
+	}
+
+	edit_graph(model, |graph| {
+		let mut renamed = vec![false; renames.len()];
+		let mut out = Vec::with_capacity(graph.len());
+		for field in fields(graph) {
+			let field = field?;
+			if field.number != GRAPH_OUTPUT_FIELD || field.wire_type != WIRE_LEN {
+				out.extend_from_slice(field.raw);
+				continue;
+			}
+
+			let mut value_info = Vec::with_capacity(field.payload.len());
+			for value_info_field in fields(field.payload) {
+				let value_info_field = value_info_field?;
+				if value_info_field.number == VALUE_INFO_NAME_FIELD && value_info_field.wire_type == WIRE_LEN {
+					if let Some(i) = renames.iter().position(|(from, _)| from.as_bytes() == value_info_field.payload) {
+						renamed[i] = true;
+						write_len_field(&mut value_info, VALUE_INFO_NAME_FIELD, renames[i].1.as_bytes());
+						continue;
+					}
+				}
+				value_info.extend_from_slice(value_info_field.raw);
+			}
+			write_len_field(&mut out, GRAPH_OUTPUT_FIELD, &value_info);
+		}
+
+		if let Some(i) = renamed.iter().position(|r| !r) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Model has no output named `{}`", renames[i].0)));
+		}
+
+		for (from, to) in renames {
+			let mut node = Vec::new();
+			write_len_field(&mut node, 1, from.as_bytes());
+			write_len_field(&mut node, 2, to.as_bytes());
+			write_len_field(&mut node, 3, format!("{to}_rename").as_bytes());
+			write_len_field(&mut node, 4, b"Identity");
+			write_len_field(&mut out, GRAPH_NODE_FIELD, &node);
+		}
+		Ok(out)
+	})
+}
+
+/// Converts all `float32` initializers in the main graph of a serialized `ModelProto` to `float16`.
+///
+/// Each converted initializer is renamed, and a `Cast` node converting it back to `float32` under its original name is
+/// inserted at the start of the graph, so the rest of the graph is left untouched. Initializers which are also graph
+/// inputs (and can thus be overridden) or which are stored as external data are not converted.
+#[cfg(feature = "half")]
+pub(crate) fn convert_initializers_to_fp16(model: &[u8]) -> Result<Vec<u8>> {
+	/// `TensorProto` fields
+	const TENSOR_DATA_TYPE_FIELD: u64 = 2;
+	const TENSOR_FLOAT_DATA_FIELD: u64 = 4;
+	const TENSOR_NAME_FIELD: u64 = 8;
+	const TENSOR_RAW_DATA_FIELD: u64 = 9;
+	const TENSOR_DATA_LOCATION_FIELD: u64 = 14;
+	/// `TensorProto.DataType` values
+	const FLOAT: u64 = 1;
+	const FLOAT16: u64 = 10;
+
+	/// Returns the converted tensor & its new name, or `None` if the tensor should be left as is.
+	fn convert_tensor(tensor: &[u8], graph_inputs: &[&[u8]]) -> Result<Option<(Vec<u8>, Vec<u8>, Vec<u8>)>> {
+		let mut rest = Vec::with_capacity(tensor.len() / 2);
+		let mut data_type = 0;
+		let mut name: &[u8] = &[];
+		let mut values = Vec::new();
+		for field in fields(tensor) {
+			let field = field?;
+			match (field.number, field.wire_type) {
+				(TENSOR_DATA_TYPE_FIELD, WIRE_VARINT) => data_type = field.as_varint()?,
+				(TENSOR_NAME_FIELD, WIRE_LEN) => name = field.payload,
+				(TENSOR_DATA_LOCATION_FIELD, WIRE_VARINT) if field.as_varint()? != 0 => return Ok(None),
+				// `raw_data`, or packed `float_data`; both are little-endian `f32`s
+				(TENSOR_RAW_DATA_FIELD | TENSOR_FLOAT_DATA_FIELD, WIRE_LEN) => values.extend_from_slice(field.payload),
+				(TENSOR_FLOAT_DATA_FIELD, WIRE_I32) => values.extend_from_slice(field.payload),
+				_ => rest.extend_from_slice(field.raw)
+			}
+		}
+		if data_type != FLOAT || graph_inputs.contains(&name) || values.len() % 4 != 0 {
+			return Ok(None);
+		}
+
+		let converted: Vec<u8> = values
+			.chunks_exact(4)
+			.flat_map(|b| half::f16::from_f32(f32::from_le_bytes([b[0], b[1], b[2], b[3]])).to_le_bytes())
+			.collect();
+		let new_name = [name, b"_fp16".as_slice()].concat();
+		write_varint_field(&mut rest, TENSOR_DATA_TYPE_FIELD, FLOAT16);
+		write_len_field(&mut rest, TENSOR_NAME_FIELD, &new_name);
+		write_len_field(&mut rest, TENSOR_RAW_DATA_FIELD, &converted);
+		Ok(Some((rest, new_name, name.to_vec())))
+	}
+
+	fn cast_node(input: &[u8], output: &[u8]) -> Vec<u8> {
+		// `AttributeProto { name: "to", i: FLOAT, type: INT }`
+		let mut to = Vec::new();
+		write_len_field(&mut to, 1, b"to");
+		write_varint_field(&mut to, 3, FLOAT);
+		write_varint_field(&mut to, 20, 2);
+
+		let mut node = Vec::new();
+		write_len_field(&mut node, 1, input);
+		write_len_field(&mut node, 2, output);
+		write_len_field(&mut node, 3, &[output, b"_cast".as_slice()].concat());
+		write_len_field(&mut node, 4, b"Cast");
+		write_len_field(&mut node, 5, &to);
+		node
+	}
+
+	edit_graph(model, |graph| {
+		let mut graph_inputs = Vec::new();
+		for field in fields(graph) {
+			let field = field?;
+			if field.number == GRAPH_INPUT_FIELD && field.wire_type == WIRE_LEN {
+				for value_info_field in fields(field.payload) {
+					let value_info_field = value_info_field?;
+					if value_info_field.number == VALUE_INFO_NAME_FIELD {
+						graph_inputs.push(value_info_field.payload);
+					}
+				}
+			}
+		}
+
+		let mut cast_nodes = Vec::new();
+		let mut rest = Vec::with_capacity(graph.len());
+		for field in fields(graph) {
+			let field = field?;
+			if field.number == GRAPH_INITIALIZER_FIELD && field.wire_type == WIRE_LEN {
+				if let Some((tensor, new_name, name)) = convert_tensor(field.payload, &graph_inputs)? {
+					write_len_field(&mut rest, GRAPH_INITIALIZER_FIELD, &tensor);
+					write_len_field(&mut cast_nodes, GRAPH_NODE_FIELD, &cast_node(&new_name, &name));
+					continue;
+				}
+			}
+			rest.extend_from_slice(field.raw);
+		}
+
+		// ONNX requires nodes to be topologically sorted, so the casts must come before any of their consumers.
+		cast_nodes.extend_from_slice(&rest);
+		Ok(cast_nodes)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn model_with_graph(graph: &[u8]) -> Vec<u8> {
+		// ir_version = 8
+		let mut model = vec![0x08, 0x08];
+		write_len_field(&mut model, MODEL_GRAPH_FIELD, graph);
+		model
+	}
+
+	#[test]
+	fn test_add_graph_outputs() -> Result<()> {
+		let mut graph = Vec::new();
+		write_len_field(&mut graph, 2, b"graph_name");
+		let model = model_with_graph(&graph);
+
+		let patched = add_graph_outputs(&model, &["a".to_string(), "hidden".to_string()])?;
+
+		let mut expected_graph = graph.clone();
+		expected_graph.extend_from_slice(&[0x62, 0x03, 0x0a, 0x01, b'a']);
+		expected_graph.extend_from_slice(&[0x62, 0x08, 0x0a, 0x06]);
+		expected_graph.extend_from_slice(b"hidden");
+		assert_eq!(patched, model_with_graph(&expected_graph));
+		Ok(())
+	}
+
+	#[test]
+	fn test_graph_initializers() -> Result<()> {
+		// `TensorProto { dims: [2, 3] (packed), data_type: FLOAT, name: "weight" }`
+		let mut tensor = Vec::new();
+		write_len_field(&mut tensor, 1, &[0x02, 0x03]);
+		write_varint_field(&mut tensor, 2, 1);
+		write_len_field(&mut tensor, 8, b"weight");
+		// `TensorProto { dims: [4] (unpacked), data_type: INT64, name: "bias" }`
+		let mut tensor2 = Vec::new();
+		write_varint_field(&mut tensor2, 1, 4);
+		write_varint_field(&mut tensor2, 2, 7);
+		write_len_field(&mut tensor2, 8, b"bias");
+
+		let mut graph = Vec::new();
+		write_len_field(&mut graph, GRAPH_INITIALIZER_FIELD, &tensor);
+		write_len_field(&mut graph, GRAPH_INITIALIZER_FIELD, &tensor2);
+
+		let initializers = graph_initializers(&model_with_graph(&graph))?;
+		assert_eq!(initializers, [
+			InitializerInfo {
+				name: "weight".to_string(),
+				data_type: 1,
+				dims: vec![2, 3]
+			},
+			InitializerInfo {
+				name: "bias".to_string(),
+				data_type: 7,
+				dims: vec![4]
+			}
+		]);
+		Ok(())
+	}
+
+	#[test]
+	fn test_invalid_model() {
+		assert!(add_graph_outputs(&[0x08], &["a".to_string()]).is_err());
+		assert!(add_graph_outputs(&[0x08, 0x08], &["a".to_string()]).is_err());
+	}
+
+	#[test]
+	fn test_graph_nodes() -> Result<()> {
+		let mut attribute = Vec::new();
+		write_len_field(&mut attribute, 1, b"alpha");
+		let mut node = Vec::new();
+		write_len_field(&mut node, 1, b"x");
+		write_len_field(&mut node, 1, b"");
+		write_len_field(&mut node, 2, b"y");
+		write_len_field(&mut node, 3, b"relu_0");
+		write_len_field(&mut node, 4, b"LeakyRelu");
+		write_len_field(&mut node, 5, &attribute);
+		let mut graph = Vec::new();
+		write_len_field(&mut graph, 2, b"graph_name");
+		write_len_field(&mut graph, GRAPH_NODE_FIELD, &node);
+
+		let nodes = graph_nodes(&model_with_graph(&graph))?;
+		assert_eq!(nodes, vec![GraphNode {
+			name: "relu_0".to_string(),
+			op_type: "LeakyRelu".to_string(),
+			domain: String::new(),
+			inputs: vec!["x".to_string(), String::new()],
+			outputs: vec!["y".to_string()],
+			attributes: vec!["alpha".to_string()]
+		}]);
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "training")]
+	fn test_rename_graph_outputs() -> Result<()> {
+		let mut value_info = Vec::new();
+		write_len_field(&mut value_info, VALUE_INFO_NAME_FIELD, b"logits");
+		let mut graph = Vec::new();
+		write_len_field(&mut graph, GRAPH_OUTPUT_FIELD, &value_info);
+		let model = model_with_graph(&graph);
+
+		let renamed = rename_graph_outputs(&model, &[("logits", "probs")])?;
+		let nodes = graph_nodes(&renamed)?;
+		assert_eq!(nodes.len(), 1);
+		assert_eq!(nodes[0].op_type, "Identity");
+		assert_eq!(nodes[0].inputs, ["logits"]);
+		assert_eq!(nodes[0].outputs, ["probs"]);
+
+		assert!(rename_graph_outputs(&model, &[("loss", "probs")]).is_err());
+
+		// new names must not clash with existing values
+		let mut node = Vec::new();
+		write_len_field(&mut node, 2, b"hidden");
+		let mut graph = graph.clone();
+		write_len_field(&mut graph, GRAPH_NODE_FIELD, &node);
+		let mut value_info = Vec::new();
+		write_len_field(&mut value_info, VALUE_INFO_NAME_FIELD, b"hidden");
+		write_len_field(&mut graph, GRAPH_OUTPUT_FIELD, &value_info);
+		let model = model_with_graph(&graph);
+		assert!(rename_graph_outputs(&model, &[("logits", "hidden")]).is_err());
+		assert!(rename_graph_outputs(&model, &[("logits", "logits")]).is_err());
+		assert!(rename_graph_outputs(&model, &[("logits", "out"), ("hidden", "logits")]).is_err());
+		assert!(rename_graph_outputs(&model, &[("logits", "out"), ("hidden", "out2")]).is_ok());
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "training")]
+	fn test_graph_input_names() -> Result<()> {
+		let mut graph = Vec::new();
+		for name in [&b"input"[..], b"fc.weight"] {
+			let mut value_info = Vec::new();
+			write_len_field(&mut value_info, VALUE_INFO_NAME_FIELD, name);
+			write_len_field(&mut graph, GRAPH_INPUT_FIELD, &value_info);
+		}
+		write_len_field(&mut graph, GRAPH_NODE_FIELD, &[]);
+
+		assert_eq!(graph_input_names(&model_with_graph(&graph))?, vec!["input".to_string(), "fc.weight".to_string()]);
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "half")]
+	fn test_convert_initializers_to_fp16() -> Result<()> {
+		fn tensor(name: &[u8], data_type: u64, raw_data: &[u8]) -> Vec<u8> {
+			let mut tensor = Vec::new();
+			write_varint_field(&mut tensor, 1, 2);
+			write_varint_field(&mut tensor, 2, data_type);
+			write_len_field(&mut tensor, 8, name);
+			write_len_field(&mut tensor, 9, raw_data);
+			tensor
+		}
+
+		let weight: Vec<u8> = [1.0_f32, -2.5].iter().flat_map(|f| f.to_le_bytes()).collect();
+		let indices: Vec<u8> = [1_i64, 2].iter().flat_map(|i| i.to_le_bytes()).collect();
+		let mut graph = Vec::new();
+		write_len_field(&mut graph, 5, &tensor(b"weight", 1, &weight));
+		write_len_field(&mut graph, 5, &tensor(b"indices", 7, &indices));
+		let model = model_with_graph(&graph);
+
+		let converted = convert_initializers_to_fp16(&model)?;
+
+		let graph = fields(&converted)
+			.collect::<Result<Vec<_>>>()?
+			.into_iter()
+			.find(|f| f.number == MODEL_GRAPH_FIELD)
+			.expect("model should have a graph")
+			.payload
+			.to_vec();
+		let graph_fields: Vec<Field<'_>> = fields(&graph).collect::<Result<_>>()?;
+		assert_eq!(graph_fields.len(), 3);
+		// cast node comes first
+		assert_eq!(graph_fields[0].number, GRAPH_NODE_FIELD);
+		// weight was converted and renamed
+		let weight_fp16: Vec<u8> = [1.0_f32, -2.5].iter().flat_map(|f| half::f16::from_f32(*f).to_le_bytes()).collect();
+		let mut expected = Vec::new();
+		write_varint_field(&mut expected, 1, 2);
+		write_varint_field(&mut expected, 2, 10);
+		write_len_field(&mut expected, 8, b"weight_fp16");
+		write_len_field(&mut expected, 9, &weight_fp16);
+		assert_eq!(graph_fields[1].payload, expected.as_slice());
+		// non-float initializers are left alone
+		assert_eq!(graph_fields[2].payload, tensor(b"indices", 7, &indices).as_slice());
+		Ok(())
+	}
+}
@@ -1,9 +1,15 @@
 use std::{
+	cell::RefCell,
 	ffi::{CString, c_char, c_void},
 	ops::{Deref, DerefMut},
 	ptr::{self, NonNull}
 };
 
+#[cfg(feature = "ndarray")]
+use ndarray::{ArrayViewD, ArrayViewMutD, IxDyn};
+
+#[cfg(feature = "ndarray")]
+use crate::{error::ErrorCode, tensor::PrimitiveTensorElementType};
 use crate::{
 	AsPointer,
 	error::{Error, Result, status_to_result},
@@ -217,13 +223,17 @@ impl<T> Drop for ScratchBuffer<T> {
 }
 
 pub struct KernelContext {
-	ptr: NonNull<ort_sys::OrtKernelContext>
+	ptr: NonNull<ort_sys::OrtKernelContext>,
+	/// Indices of outputs which have already been borrowed mutably via [`KernelContext::output`] or
+	/// [`KernelContext::output_tensor`].
+	borrowed_outputs: RefCell<Vec<usize>>
 }
 
 impl KernelContext {
 	pub(crate) fn new(ctx: *mut ort_sys::OrtKernelContext) -> Self {
 		Self {
-			ptr: NonNull::from(unsafe { &mut *ctx })
+			ptr: NonNull::from(unsafe { &mut *ctx }),
+			borrowed_outputs: RefCell::new(Vec::new())
 		}
 	}
 
@@ -233,11 +243,82 @@ impl KernelContext {
 		Ok(NonNull::new(value_ptr.cast_mut()).map(|c| ValueRef::new(unsafe { Value::from_ptr_nodrop(c, None) })))
 	}
 
+	/// Allocates the output at index `idx` with the given `shape`, returning a mutable reference to it.
+	///
+	/// Each output can only be retrieved once per call to [`Kernel::compute`], either via this method or
+	/// [`KernelContext::output_tensor`]; retrieving it again returns an error.
 	pub fn output(&self, idx: usize, shape: impl IntoIterator<Item = i64>) -> Result<Option<ValueRefMut<'_>>> {
+		if self.borrowed_outputs.borrow().contains(&idx) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Output {idx} was already retrieved")));
+		}
+
 		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
 		let shape = shape.into_iter().collect::<Vec<i64>>();
 		ortsys![unsafe KernelContext_GetOutput(self.ptr.as_ptr(), idx, shape.as_ptr(), shape.len(), &mut value_ptr)?];
-		Ok(NonNull::new(value_ptr).map(|c| ValueRefMut::new(unsafe { Value::from_ptr_nodrop(c, None) })))
+		let value = NonNull::new(value_ptr).map(|c| ValueRefMut::new(unsafe { Value::from_ptr_nodrop(c, None) }));
+		if value.is_some() {
+			self.borrowed_outputs.borrow_mut().push(idx);
+		}
+		Ok(value)
+	}
+
+	/// Returns a view of the input at index `idx` as an array of `T`.
+	///
+	/// Returns an error if `idx` is out of bounds, if the input is an optional input that was not provided, or if `T`
+	/// does not match the input's element type.
+	///
+	/// ```ignore
+	/// fn compute(&mut self, ctx: &KernelContext) -> ort::Result<()> {
+	/// 	let x = ctx.input_tensor::<f32>(0)?;
+	/// 	let mut y = ctx.output_tensor::<f32>(0, x.shape().iter().map(|&d| d as i64))?;
+	/// 	y.assign(&x.mapv(|v| v.max(0.0)));
+	/// 	Ok(())
+	/// }
+	/// ```
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn input_tensor<T: PrimitiveTensorElementType>(&self, idx: usize) -> Result<ArrayViewD<'_, T>> {
+		let count = self.input_count()?;
+		if idx >= count {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Input index {idx} is out of bounds; the kernel has {count} inputs")));
+		}
+
+		let value = self
+			.input(idx)?
+			.ok_or_else(|| Error::new_with_code(ErrorCode::InvalidArgument, format!("Optional input {idx} was not provided")))?;
+		let (shape, data) = value.try_extract_raw_tensor::<T>()?;
+		let shape: Vec<usize> = shape.iter().map(|&d| d as usize).collect();
+		// SAFETY: `value` is only a non-owning handle; the data it points to is owned by ONNX Runtime and lives for as long
+		// as this context.
+		Ok(unsafe { ArrayViewD::from_shape_ptr(IxDyn(&shape), data.as_ptr()) })
+	}
+
+	/// Allocates the output at index `idx` with the given `shape`, returning a mutable view of it as an array of `T`.
+	///
+	/// Each output can only be retrieved once per call to [`Kernel::compute`], either via this method or
+	/// [`KernelContext::output`]. Returns an error if `idx` is out of bounds, if the output was already retrieved, or if
+	/// `T` does not match the output's element type.
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn output_tensor<T: PrimitiveTensorElementType>(&self, idx: usize, shape: impl IntoIterator<Item = i64>) -> Result<ArrayViewMutD<'_, T>> {
+		let count = self.num_outputs()?;
+		if idx >= count {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Output index {idx} is out of bounds; the kernel has {count} outputs")));
+		}
+
+		let mut value = self
+			.output(idx, shape)?
+			.ok_or_else(|| Error::new_with_code(ErrorCode::InvalidArgument, format!("Output {idx} could not be allocated")))?;
+		let (shape, data) = value.try_extract_raw_tensor_mut::<T>()?;
+		let shape: Vec<usize> = shape.iter().map(|&d| d as usize).collect();
+		// SAFETY: see `input_tensor`; `output` records the output in `borrowed_outputs`, which guarantees this is the only
+		// view of the output's data.
+		Ok(unsafe { ArrayViewMutD::from_shape_ptr(IxDyn(&shape), data.as_mut_ptr()) })
+	}
+
+	/// Returns the number of inputs passed to this kernel, including optional inputs which were not provided.
+	pub fn input_count(&self) -> Result<usize> {
+		self.num_inputs()
 	}
 
 	pub fn num_inputs(&self) -> Result<usize> {
@@ -127,6 +127,66 @@ impl AsPointer for ShapeInferenceContext {
 	}
 }
 
+/// A domain of custom [`Operator`]s implemented in Rust, which can be registered with a session via
+/// [`SessionBuilder::with_operators`](crate::session::builder::SessionBuilder::with_operators).
+///
+/// Operators in the graph refer to the domain by name, e.g. a node with domain `my.domain` and op type `Clamp` will
+/// use the `Clamp` operator from `OperatorDomain::new("my.domain")`.
+///
+/// ```no_run
+/// # use ort::{
+/// # 	operator::{
+/// # 		Operator, OperatorDomain,
+/// # 		io::{OperatorInput, OperatorOutput},
+/// # 		kernel::{Kernel, KernelAttributes, KernelContext}
+/// # 	},
+/// # 	session::Session,
+/// # 	tensor::TensorElementType
+/// # };
+/// struct Relu;
+/// struct ReluKernel;
+///
+/// impl Operator for Relu {
+/// 	type Kernel = ReluKernel;
+///
+/// 	fn name() -> &'static str {
+/// 		"Relu"
+/// 	}
+///
+/// 	fn inputs() -> Vec<OperatorInput> {
+/// 		vec![OperatorInput::required(TensorElementType::Float32)]
+/// 	}
+///
+/// 	fn outputs() -> Vec<OperatorOutput> {
+/// 		vec![OperatorOutput::required(TensorElementType::Float32)]
+/// 	}
+///
+/// 	fn create_kernel(_: &KernelAttributes) -> ort::Result<Self::Kernel> {
+/// 		Ok(ReluKernel)
+/// 	}
+/// }
+///
+/// impl Kernel for ReluKernel {
+/// 	fn compute(&mut self, ctx: &KernelContext) -> ort::Result<()> {
+/// 		let x = ctx.input(0)?.ok_or_else(|| ort::Error::new("missing input"))?;
+/// 		let (shape, x) = x.try_extract_raw_tensor::<f32>()?;
+/// 		let mut y = ctx.output(0, shape.to_vec())?.ok_or_else(|| ort::Error::new("missing output"))?;
+/// 		let (_, y) = y.try_extract_raw_tensor_mut::<f32>()?;
+/// 		for (y, x) in y.iter_mut().zip(x) {
+/// 			*y = x.max(0.0);
+/// 		}
+/// 		Ok(())
+/// 	}
+/// }
+///
+/// # fn main() -> ort::Result<()> {
+/// let session = Session::builder()?
+/// 	.with_operators(OperatorDomain::new("my.domain")?.add::<Relu>()?)?
+/// 	.commit_from_file("model_with_custom_ops.onnx")?;
+/// # 	Ok(())
+/// # }
+/// ```
+#[doc(alias = "CustomOpDomain")]
 pub struct OperatorDomain {
 	ptr: NonNull<ort_sys::OrtCustomOpDomain>,
 	_name: CString,
@@ -134,6 +194,7 @@ pub struct OperatorDomain {
 }
 
 impl OperatorDomain {
+	/// Creates a new, empty operator domain with the given name.
 	pub fn new(name: impl AsRef<str>) -> Result<Self> {
 		let name = CString::new(name.as_ref())?;
 		let mut ptr: *mut ort_sys::OrtCustomOpDomain = ptr::null_mut();
@@ -145,6 +206,8 @@ impl OperatorDomain {
 		})
 	}
 
+	/// Adds the operator `O` to this domain.
+	#[doc(alias = "register")]
 	#[allow(clippy::should_implement_trait)]
 	pub fn add<O: Operator>(mut self) -> Result<Self> {
 		let name = O::name();
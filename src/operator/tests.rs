@@ -1,4 +1,4 @@
-use ndarray::{Array2, arr2};
+use ndarray::{Array2, Zip, arr2};
 
 use crate::{
 	Result,
@@ -101,3 +101,93 @@ fn test_custom_ops() -> crate::Result<()> {
 
 	Ok(())
 }
+
+/// Same as [`CustomOpOne`], but implemented via [`KernelContext::input_tensor`] & [`KernelContext::output_tensor`].
+struct ArrayViewOpOne;
+struct ArrayViewOpOneKernel;
+
+impl Operator for ArrayViewOpOne {
+	type Kernel = ArrayViewOpOneKernel;
+
+	fn name() -> &'static str {
+		"CustomOpOne"
+	}
+
+	fn create_kernel(_: &KernelAttributes) -> Result<Self::Kernel> {
+		Ok(ArrayViewOpOneKernel)
+	}
+
+	fn inputs() -> Vec<OperatorInput> {
+		CustomOpOne::inputs()
+	}
+
+	fn outputs() -> Vec<OperatorOutput> {
+		CustomOpOne::outputs()
+	}
+}
+
+impl Kernel for ArrayViewOpOneKernel {
+	fn compute(&mut self, ctx: &KernelContext) -> Result<()> {
+		assert_eq!(ctx.input_count()?, 2);
+		assert!(ctx.input_tensor::<f32>(2).is_err());
+		assert!(ctx.input_tensor::<i32>(0).is_err());
+
+		let x = ctx.input_tensor::<f32>(0)?;
+		let y = ctx.input_tensor::<f32>(1)?;
+		let mut z = ctx.output_tensor::<f32>(0, x.shape().iter().map(|&d| d as i64))?;
+		assert!(ctx.output_tensor::<f32>(0, x.shape().iter().map(|&d| d as i64)).is_err());
+		assert!(ctx.output(0, x.shape().iter().map(|&d| d as i64)).is_err());
+		for (i, (z, (x, y))) in z.iter_mut().zip(x.iter().zip(y.iter())).enumerate() {
+			*z = if i % 2 == 0 { *x } else { *y };
+		}
+		Ok(())
+	}
+}
+
+struct ArrayViewOpTwo;
+struct ArrayViewOpTwoKernel;
+
+impl Operator for ArrayViewOpTwo {
+	type Kernel = ArrayViewOpTwoKernel;
+
+	fn name() -> &'static str {
+		"CustomOpTwo"
+	}
+
+	fn create_kernel(_: &KernelAttributes) -> Result<Self::Kernel> {
+		Ok(ArrayViewOpTwoKernel)
+	}
+
+	fn inputs() -> Vec<OperatorInput> {
+		CustomOpTwo::inputs()
+	}
+
+	fn outputs() -> Vec<OperatorOutput> {
+		CustomOpTwo::outputs()
+	}
+}
+
+impl Kernel for ArrayViewOpTwoKernel {
+	fn compute(&mut self, ctx: &KernelContext) -> Result<()> {
+		let x = ctx.input_tensor::<f32>(0)?;
+		let mut z = ctx.output_tensor::<i32>(0, x.shape().iter().map(|&d| d as i64))?;
+		let mut i = 0;
+		Zip::from(&mut z).and(&x).for_each(|z, x| {
+			*z = (x * i as f32) as i32;
+			i += 1;
+		});
+		Ok(())
+	}
+}
+
+#[test]
+fn test_custom_ops_array_views() -> crate::Result<()> {
+	let session = Session::builder()?
+		.with_operators(OperatorDomain::new("test.customop")?.add::<ArrayViewOpOne>()?.add::<ArrayViewOpTwo>()?)?
+		.commit_from_file("tests/data/custom_op_test.onnx")?;
+
+	let values = session.run(crate::inputs![Array2::<f32>::zeros((3, 5)), Array2::<f32>::ones((3, 5))]?)?;
+	assert_eq!(values[0].try_extract_tensor::<i32>()?, arr2(&[[0, 1, 0, 3, 0], [5, 0, 7, 0, 9], [0, 11, 0, 13, 0]]).view().into_dyn());
+
+	Ok(())
+}
@@ -0,0 +1,285 @@
+//! Composable pre- & post-processing steps which run on the CPU before or after a model.
+//!
+//! Each step implements [`Preprocessor`], taking a [`DynValue`] and producing a new one. Steps can be chained together
+//! with a [`Pipeline`], which is itself a [`Preprocessor`].
+//!
+//! ```
+//! # use ort::{preprocessing::{InterpolationMode, Normalize, Pipeline, Preprocessor, Resize, Transpose}, value::Tensor};
+//! # fn main() -> ort::Result<()> {
+//! let pipeline = Pipeline::new(vec![
+//! 	// HWC -> CHW
+//! 	Box::new(Transpose { axes: vec![2, 0, 1] }),
+//! 	Box::new(Resize { height: 224, width: 224, mode: InterpolationMode::Bilinear }),
+//! 	Box::new(Normalize {
+//! 		mean: vec![0.485, 0.456, 0.406],
+//! 		std: vec![0.229, 0.224, 0.225]
+//! 	}),
+//! ]);
+//!
+//! let image = Tensor::from_array(ndarray::Array3::<f32>::zeros((480, 640, 3)))?;
+//! let input = pipeline.process(image.into_dyn())?;
+//! assert_eq!(input.shape()?, [3, 224, 224]);
+//! # 	Ok(())
+//! # }
+//! ```
+//!
+//! All built-in steps operate on `f32` tensors & return an error for tensors of any other type.
+
+use ndarray::{ArrayD, Axis, IxDyn};
+
+use crate::{
+	error::{Error, ErrorCode, Result},
+	value::{DynValue, Tensor}
+};
+
+/// A single processing step, transforming one [`DynValue`] into another.
+pub trait Preprocessor {
+	/// Processes `input`, returning the transformed value.
+	fn process(&self, input: DynValue) -> Result<DynValue>;
+}
+
+impl<F: Fn(DynValue) -> Result<DynValue>> Preprocessor for F {
+	fn process(&self, input: DynValue) -> Result<DynValue> {
+		self(input)
+	}
+}
+
+/// A sequence of [`Preprocessor`]s, applied in order.
+#[derive(Default)]
+pub struct Pipeline {
+	steps: Vec<Box<dyn Preprocessor>>
+}
+
+impl Pipeline {
+	/// Creates a new pipeline which applies `steps` in order.
+	pub fn new(steps: Vec<Box<dyn Preprocessor>>) -> Self {
+		Self { steps }
+	}
+
+	/// Appends a step to the end of the pipeline.
+	#[must_use]
+	pub fn with_step(mut self, step: impl Preprocessor + 'static) -> Self {
+		self.steps.push(Box::new(step));
+		self
+	}
+
+	/// Returns the number of steps in this pipeline.
+	pub fn len(&self) -> usize {
+		self.steps.len()
+	}
+
+	/// Returns `true` if this pipeline has no steps, in which case [`Pipeline::process`] returns its input unchanged.
+	pub fn is_empty(&self) -> bool {
+		self.steps.is_empty()
+	}
+}
+
+impl Preprocessor for Pipeline {
+	fn process(&self, input: DynValue) -> Result<DynValue> {
+		self.steps.iter().try_fold(input, |value, step| step.process(value))
+	}
+}
+
+/// Normalizes each channel of an image tensor via `(x - mean[c]) / std[c]`.
+///
+/// The tensor is expected to be in `CHW` or `NCHW` layout, i.e. the channel axis is the third-to-last axis. `mean` and
+/// `std` must either have one element per channel, or a single element which is applied to all channels.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Normalize {
+	/// The value subtracted from each channel.
+	pub mean: Vec<f32>,
+	/// The value each channel is divided by after subtracting the mean.
+	pub std: Vec<f32>
+}
+
+impl Preprocessor for Normalize {
+	fn process(&self, input: DynValue) -> Result<DynValue> {
+		let mut array = input.try_extract_tensor::<f32>()?.to_owned();
+		if array.ndim() < 3 {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("`Normalize` expects a tensor in CHW or NCHW layout, got shape {:?}", array.shape())
+			));
+		}
+
+		let channel_axis = Axis(array.ndim() - 3);
+		let channels = array.len_of(channel_axis);
+		for (name, values) in [("mean", &self.mean), ("std", &self.std)] {
+			if values.len() != 1 && values.len() != channels {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("`Normalize` has {} {name} values, but the tensor has {channels} channels", values.len())
+				));
+			}
+		}
+
+		for (c, mut channel) in array.axis_iter_mut(channel_axis).enumerate() {
+			let mean = self.mean[if self.mean.len() == 1 { 0 } else { c }];
+			let std = self.std[if self.std.len() == 1 { 0 } else { c }];
+			channel.mapv_inplace(|x| (x - mean) / std);
+		}
+		Ok(Tensor::from_array(array)?.into_dyn())
+	}
+}
+
+/// The interpolation method used by [`Resize`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InterpolationMode {
+	/// Uses the value of the nearest source pixel.
+	Nearest,
+	/// Linearly interpolates between the 4 nearest source pixels.
+	#[default]
+	Bilinear
+}
+
+/// Resizes the last two axes (height & width) of a tensor to `height` x `width`.
+///
+/// Pixel centers are aligned like ONNX's `Resize` operator with `coordinate_transformation_mode = half_pixel`, so
+/// resizing with this step matches resizing inside the model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resize {
+	/// The height of the output, in pixels.
+	pub height: usize,
+	/// The width of the output, in pixels.
+	pub width: usize,
+	/// How output pixels are computed from the source pixels.
+	pub mode: InterpolationMode
+}
+
+impl Preprocessor for Resize {
+	fn process(&self, input: DynValue) -> Result<DynValue> {
+		let array = input.try_extract_tensor::<f32>()?;
+		let shape = array.shape().to_vec();
+		let rank = shape.len();
+		if rank < 2 || shape[rank - 2] == 0 || shape[rank - 1] == 0 {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("`Resize` expects a tensor with non-empty height & width axes, got shape {shape:?}")
+			));
+		}
+		if self.height == 0 || self.width == 0 {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "`Resize` target height & width must be non-zero"));
+		}
+
+		let (in_h, in_w) = (shape[rank - 2], shape[rank - 1]);
+		let (scale_y, scale_x) = (in_h as f32 / self.height as f32, in_w as f32 / self.width as f32);
+		let array = array.as_standard_layout();
+		let data = array.as_slice().unwrap_or_else(|| unreachable!());
+
+		let mut out = Vec::with_capacity(data.len() / (in_h * in_w) * self.height * self.width);
+		for plane in data.chunks_exact(in_h * in_w) {
+			let at = |y: usize, x: usize| plane[y * in_w + x];
+			for y in 0..self.height {
+				let sy = (y as f32 + 0.5) * scale_y - 0.5;
+				for x in 0..self.width {
+					let sx = (x as f32 + 0.5) * scale_x - 0.5;
+					out.push(match self.mode {
+						InterpolationMode::Nearest => at(nearest(sy, in_h), nearest(sx, in_w)),
+						InterpolationMode::Bilinear => {
+							let (y0, y1, dy) = neighbours(sy, in_h);
+							let (x0, x1, dx) = neighbours(sx, in_w);
+							let top = at(y0, x0) * (1.0 - dx) + at(y0, x1) * dx;
+							let bottom = at(y1, x0) * (1.0 - dx) + at(y1, x1) * dx;
+							top * (1.0 - dy) + bottom * dy
+						}
+					});
+				}
+			}
+		}
+
+		let mut out_shape = shape;
+		out_shape[rank - 2] = self.height;
+		out_shape[rank - 1] = self.width;
+		let array = ArrayD::from_shape_vec(IxDyn(&out_shape), out).map_err(Error::wrap)?;
+		Ok(Tensor::from_array(array)?.into_dyn())
+	}
+}
+
+/// Returns the index of the source pixel nearest to the source coordinate `s`.
+fn nearest(s: f32, len: usize) -> usize {
+	(s + 0.5).floor().clamp(0.0, (len - 1) as f32) as usize
+}
+
+/// Returns the indices of the two source pixels surrounding the source coordinate `s`, and the weight of the second.
+fn neighbours(s: f32, len: usize) -> (usize, usize, f32) {
+	let s = s.clamp(0.0, (len - 1) as f32);
+	let i0 = s.floor() as usize;
+	let i1 = (i0 + 1).min(len - 1);
+	(i0, i1, s - i0 as f32)
+}
+
+/// Permutes the axes of a tensor, e.g. `axes: vec![2, 0, 1]` converts an `HWC` image to `CHW`.
+///
+/// Axis `i` of the output is axis `axes[i]` of the input, like ONNX's `Transpose` operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transpose {
+	/// The permutation of the input's axes.
+	pub axes: Vec<usize>
+}
+
+impl Preprocessor for Transpose {
+	fn process(&self, input: DynValue) -> Result<DynValue> {
+		let array = input.try_extract_tensor::<f32>()?;
+		let mut seen = vec![false; array.ndim()];
+		let is_permutation = self.axes.len() == array.ndim() && self.axes.iter().all(|&a| a < seen.len() && !std::mem::replace(&mut seen[a], true));
+		if !is_permutation {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("`Transpose` axes {:?} are not a permutation of the axes of a tensor with shape {:?}", self.axes, array.shape())
+			));
+		}
+
+		let array = array.permuted_axes(IxDyn(&self.axes)).as_standard_layout().into_owned();
+		Ok(Tensor::from_array(array)?.into_dyn())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ndarray::{Array, arr3};
+
+	use super::*;
+
+	fn f32_tensor(array: ArrayD<f32>) -> DynValue {
+		Tensor::from_array(array).expect("").into_dyn()
+	}
+
+	#[test]
+	fn test_normalize() -> Result<()> {
+		let input = f32_tensor(arr3(&[[[1.0_f32, 3.0]], [[10.0, 20.0]]]).into_dyn());
+		let output = Normalize { mean: vec![1.0, 10.0], std: vec![2.0, 5.0] }.process(input)?;
+		assert_eq!(output.try_extract_tensor::<f32>()?.as_slice(), Some(&[0.0, 1.0, 0.0, 2.0][..]));
+
+		let input = f32_tensor(Array::zeros((1, 2, 2)).into_dyn());
+		assert!(Normalize { mean: vec![0.0; 3], std: vec![1.0; 3] }.process(input).is_err());
+		Ok(())
+	}
+
+	#[test]
+	fn test_resize() -> Result<()> {
+		let input = f32_tensor(arr3(&[[[0.0_f32, 1.0], [2.0, 3.0]]]).into_dyn());
+		let output = Resize { height: 4, width: 4, mode: InterpolationMode::Nearest }.process(input)?;
+		assert_eq!(output.shape()?, [1, 4, 4]);
+		assert_eq!(&output.try_extract_tensor::<f32>()?.as_slice().expect("")[..4], [0.0, 0.0, 1.0, 1.0]);
+
+		let input = f32_tensor(arr3(&[[[0.0_f32, 4.0]]]).into_dyn());
+		let output = Resize { height: 1, width: 4, mode: InterpolationMode::Bilinear }.process(input)?;
+		assert_eq!(output.try_extract_tensor::<f32>()?.as_slice(), Some(&[0.0, 1.0, 3.0, 4.0][..]));
+		Ok(())
+	}
+
+	#[test]
+	fn test_pipeline() -> Result<()> {
+		let pipeline = Pipeline::new(vec![Box::new(Transpose { axes: vec![2, 0, 1] })]).with_step(Normalize { mean: vec![1.0], std: vec![1.0] });
+		assert_eq!(pipeline.len(), 2);
+
+		let input = f32_tensor(Array::from_shape_vec((1, 2, 3), vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]).expect("").into_dyn());
+		let output = pipeline.process(input)?;
+		assert_eq!(output.shape()?, [3, 1, 2]);
+		assert_eq!(output.try_extract_tensor::<f32>()?.as_slice(), Some(&[0.0, 3.0, 1.0, 4.0, 2.0, 5.0][..]));
+
+		let input = f32_tensor(Array::zeros((1, 2, 3)).into_dyn());
+		assert!(Transpose { axes: vec![0, 0, 1] }.process(input).is_err());
+		Ok(())
+	}
+}
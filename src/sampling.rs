@@ -0,0 +1,169 @@
+//! Utilities for sampling tokens from the logits produced by language models.
+//!
+//! ```
+//! # use ndarray::Array1;
+//! # use ort::sampling::{temperature_scale, top_k_sample, top_p_sample};
+//! let mut rng = rand::rngs::mock::StepRng::new(0, 1);
+//! let mut logits = Array1::from_vec(vec![0.1_f32, 2.5, -1.0, 0.7]);
+//!
+//! temperature_scale(&mut logits, 0.8);
+//! let token = top_k_sample(logits.view(), 2, &mut rng);
+//! assert!(token == 1 || token == 3);
+//!
+//! let token = top_p_sample(logits.view(), 0.9, &mut rng);
+//! assert!(token < 4);
+//! ```
+
+use std::cmp::Ordering;
+
+use ndarray::{Array1, ArrayView1};
+use rand::Rng;
+
+/// Scales `logits` in place by `1 / temperature`.
+///
+/// Temperatures below `1.0` sharpen the distribution (making likely tokens even more likely), while temperatures above
+/// `1.0` flatten it, producing more diverse output.
+///
+/// # Panics
+/// Panics if `temperature` is not a positive, finite number.
+pub fn temperature_scale(logits: &mut Array1<f32>, temperature: f32) {
+	assert!(temperature > 0.0 && temperature.is_finite(), "temperature must be a positive, finite number; got {temperature}");
+	logits.mapv_inplace(|l| l / temperature);
+}
+
+/// Samples a token index from the `k` tokens with the highest logits.
+///
+/// The logits of the top `k` tokens are converted to probabilities via softmax before sampling, so more likely tokens
+/// are proportionally more likely to be chosen. `k` is clamped to `1..=logits.len()`; a `k` of `1` is equivalent to
+/// greedy decoding.
+///
+/// # Panics
+/// Panics if `logits` is empty.
+pub fn top_k_sample(logits: ArrayView1<f32>, k: usize, rng: &mut impl Rng) -> usize {
+	let mut candidates = sorted_candidates(logits);
+	candidates.truncate(k.clamp(1, candidates.len()));
+	let weights = softmax(&candidates);
+	sample(&candidates, &weights, rng)
+}
+
+/// Samples a token index using nucleus (top-p) sampling.
+///
+/// Only the smallest set of most likely tokens whose cumulative probability reaches `p` is considered; all other
+/// tokens are discarded before sampling. The most likely token is always included, so a `p` of `0.0` is equivalent to
+/// greedy decoding, and a `p` of `1.0` samples from the entire distribution.
+///
+/// # Panics
+/// Panics if `logits` is empty.
+pub fn top_p_sample(logits: ArrayView1<f32>, p: f32, rng: &mut impl Rng) -> usize {
+	let mut candidates = sorted_candidates(logits);
+	let mut weights = softmax(&candidates);
+
+	let total: f32 = weights.iter().sum();
+	let threshold = p.clamp(0.0, 1.0) * total;
+	let mut cumulative = 0.0;
+	let mut keep = weights.len();
+	for (i, w) in weights.iter().enumerate() {
+		cumulative += w;
+		if cumulative >= threshold {
+			keep = i + 1;
+			break;
+		}
+	}
+
+	candidates.truncate(keep);
+	weights.truncate(keep);
+	sample(&candidates, &weights, rng)
+}
+
+/// Returns `(index, logit)` pairs sorted by descending logit. `NaN` logits are treated as negative infinity so they are
+/// never sampled unless every logit is `NaN`.
+fn sorted_candidates(logits: ArrayView1<f32>) -> Vec<(usize, f32)> {
+	assert!(!logits.is_empty(), "cannot sample from empty logits");
+	let mut candidates: Vec<(usize, f32)> = logits
+		.iter()
+		.map(|&l| if l.is_nan() { f32::NEG_INFINITY } else { l })
+		.enumerate()
+		.collect();
+	candidates.sort_unstable_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+	candidates
+}
+
+/// Computes unnormalized softmax weights for sorted candidates. The maximum logit is subtracted before exponentiation
+/// to avoid overflow.
+fn softmax(candidates: &[(usize, f32)]) -> Vec<f32> {
+	let max = candidates[0].1;
+	if max == f32::NEG_INFINITY {
+		// every logit is -inf (or NaN); fall back to a uniform distribution
+		return vec![1.0; candidates.len()];
+	}
+	candidates.iter().map(|(_, l)| (l - max).exp()).collect()
+}
+
+fn sample(candidates: &[(usize, f32)], weights: &[f32], rng: &mut impl Rng) -> usize {
+	let total: f32 = weights.iter().sum();
+	let mut target = rng.gen::<f32>() * total;
+	for ((idx, _), w) in candidates.iter().zip(weights) {
+		if target < *w {
+			return *idx;
+		}
+		target -= w;
+	}
+	// floating point error may leave a tiny remainder; the last candidate absorbs it
+	candidates[candidates.len() - 1].0
+}
+
+#[cfg(test)]
+mod tests {
+	use ndarray::{Array1, arr1};
+	use rand::rngs::mock::StepRng;
+
+	use super::*;
+
+	#[test]
+	fn test_temperature_scale() {
+		let mut logits = arr1(&[1.0_f32, -2.0, 4.0]);
+		temperature_scale(&mut logits, 2.0);
+		assert_eq!(logits, arr1(&[0.5, -1.0, 2.0]));
+	}
+
+	#[test]
+	fn test_greedy_equivalence() {
+		let mut rng = StepRng::new(u64::MAX / 2, 0x1234_5678);
+		let logits = arr1(&[0.3_f32, f32::NAN, 7.5, 7.4, -1e30]);
+		for _ in 0..16 {
+			assert_eq!(top_k_sample(logits.view(), 1, &mut rng), 2);
+			assert_eq!(top_p_sample(logits.view(), 0.0, &mut rng), 2);
+		}
+	}
+
+	#[test]
+	fn test_top_k_restricts_candidates() {
+		let mut rng = StepRng::new(0, u64::MAX / 7);
+		let logits = arr1(&[10.0_f32, 9.0, -3.0, 9.5, 1000.0]);
+		for _ in 0..64 {
+			let token = top_k_sample(logits.view(), 3, &mut rng);
+			assert!([0, 3, 4].contains(&token));
+		}
+	}
+
+	#[test]
+	fn test_top_p_restricts_candidates() {
+		let mut rng = StepRng::new(0, u64::MAX / 7);
+		// softmax ≈ [0.665, 0.245, 0.090, ~0]
+		let logits = arr1(&[2.0_f32, 1.0, 0.0, -20.0]);
+		for _ in 0..64 {
+			let token = top_p_sample(logits.view(), 0.8, &mut rng);
+			assert!(token == 0 || token == 1);
+		}
+	}
+
+	#[test]
+	fn test_large_logits_are_stable() {
+		let mut rng = StepRng::new(0, u64::MAX / 3);
+		let logits = Array1::from_vec(vec![1e30_f32, 1e30, f32::MIN]);
+		for _ in 0..16 {
+			assert!(top_k_sample(logits.view(), 3, &mut rng) < 2);
+			assert!(top_p_sample(logits.view(), 1.0, &mut rng) < 2);
+		}
+	}
+}
@@ -0,0 +1,251 @@
+use std::{
+	future::Future,
+	pin::Pin,
+	sync::{
+		Arc, Mutex,
+		mpsc::{self, Receiver, RecvTimeoutError, Sender}
+	},
+	task::{Context, Poll, Waker},
+	thread::JoinHandle,
+	time::{Duration, Instant}
+};
+
+use crate::{
+	error::{Error, ErrorCode, Result},
+	session::{Session, SessionInputValue, SessionInputs},
+	tensor::TensorElementType,
+	value::{DynValue, ValueType}
+};
+
+/// Collects individual requests arriving within a short time window into a single batch, runs the batch once, and
+/// scatters the outputs back to each caller.
+///
+/// Each request passes one value per model input (in the order of [`Session::inputs`]), with the batch dimension
+/// first. Requests whose inputs have the same element types and the same non-batch dimensions are concatenated along
+/// the batch dimension & run together; requests which are incompatible with the rest of the batch (or whose outputs
+/// can't be split along the batch dimension) fall back to being run separately. Requests are only batched if every
+/// input of the model has a dynamic batch dimension.
+///
+/// A batch is run once it reaches `max_batch_size` rows, or `max_wait` after its first request arrived, whichever
+/// comes first. A request which would push the batch over `max_batch_size` rows starts the next batch instead. Batches
+/// are run on a dedicated thread, which stops when the `DynamicBatcher` is dropped.
+///
+/// ```no_run
+/// # use std::{sync::Arc, time::Duration};
+/// # use ort::{session::{DynamicBatcher, Session}, value::Tensor};
+/// # async fn handle_request(batcher: &DynamicBatcher) -> ort::Result<()> {
+/// let input = Tensor::from_array(ndarray::Array4::<f32>::zeros((1, 64, 64, 3)))?;
+/// let outputs = batcher.run(vec![input.into_dyn()])?.await?;
+/// # 	Ok(())
+/// # }
+/// # fn main() -> ort::Result<()> {
+/// let session = Arc::new(Session::builder()?.commit_from_file("tests/data/upsample.onnx")?);
+/// let batcher = DynamicBatcher::new(session, 16, Duration::from_millis(5))?;
+/// # 	Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DynamicBatcher {
+	sender: Option<Mutex<Sender<BatchRequest>>>,
+	worker: Option<JoinHandle<()>>
+}
+
+impl DynamicBatcher {
+	/// Creates a new batcher for `session`, which runs batches of up to `max_batch_size` rows, waiting at most
+	/// `max_wait` for a batch to fill up.
+	pub fn new(session: Arc<Session>, max_batch_size: usize, max_wait: Duration) -> Result<Self> {
+		if max_batch_size == 0 {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "`max_batch_size` must be at least 1"));
+		}
+
+		let (sender, receiver) = mpsc::channel();
+		let worker = std::thread::Builder::new()
+			.name("ort-dynamic-batcher".to_string())
+			.spawn(move || batch_worker(&session, &receiver, max_batch_size, max_wait))
+			.map_err(Error::wrap)?;
+		Ok(Self {
+			sender: Some(Mutex::new(sender)),
+			worker: Some(worker)
+		})
+	}
+
+	/// Submits a request to be run as part of the next batch, returning a future which resolves to the request's
+	/// outputs, in the order of [`Session::outputs`].
+	pub fn run(&self, inputs: Vec<DynValue>) -> Result<BatchFut> {
+		let slot = Arc::new(BatchSlot::default());
+		let request = BatchRequest { inputs, slot: Arc::clone(&slot) };
+		self.sender
+			.as_ref()
+			.unwrap_or_else(|| unreachable!())
+			.lock()
+			.expect("poisoned lock")
+			.send(request)
+			.map_err(|_| Error::new("The dynamic batcher's worker thread has stopped"))?;
+		Ok(BatchFut { slot })
+	}
+}
+
+impl Drop for DynamicBatcher {
+	fn drop(&mut self) {
+		// closing the channel stops the worker once it has finished the pending requests
+		drop(self.sender.take());
+		if let Some(worker) = self.worker.take() {
+			let _ = worker.join();
+		}
+	}
+}
+
+/// A future resolving to the outputs of a request submitted to a [`DynamicBatcher`].
+#[derive(Debug)]
+pub struct BatchFut {
+	slot: Arc<BatchSlot>
+}
+
+impl Future for BatchFut {
+	type Output = Result<Vec<DynValue>>;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+		let mut state = self.slot.state.lock().expect("poisoned lock");
+		match state.0.take() {
+			Some(outputs) => Poll::Ready(outputs),
+			None => {
+				state.1 = Some(cx.waker().clone());
+				Poll::Pending
+			}
+		}
+	}
+}
+
+#[derive(Debug, Default)]
+struct BatchSlot {
+	state: Mutex<(Option<Result<Vec<DynValue>>>, Option<Waker>)>
+}
+
+impl BatchSlot {
+	fn complete(&self, outputs: Result<Vec<DynValue>>) {
+		let mut state = self.state.lock().expect("poisoned lock");
+		state.0 = Some(outputs);
+		if let Some(waker) = state.1.take() {
+			waker.wake();
+		}
+	}
+}
+
+#[derive(Debug)]
+struct BatchRequest {
+	inputs: Vec<DynValue>,
+	slot: Arc<BatchSlot>
+}
+
+impl BatchRequest {
+	/// The number of rows in this request, i.e. the size of its first input's batch dimension.
+	fn rows(&self) -> usize {
+		match self.inputs.first().map(DynValue::dtype) {
+			Some(ValueType::Tensor { dimensions, .. }) if !dimensions.is_empty() => dimensions[0].max(0) as usize,
+			_ => 1
+		}
+	}
+
+	/// Returns the element type & non-batch dimensions of each input, or `None` if this request can't be batched.
+	fn batch_key(&self) -> Option<Vec<(TensorElementType, &[i64])>> {
+		self.inputs
+			.iter()
+			.map(|input| match input.dtype() {
+				ValueType::Tensor { ty, dimensions, .. }
+					if *ty != TensorElementType::String && !dimensions.is_empty() && input.memory_info().is_cpu_accessible() =>
+				{
+					Some((*ty, &dimensions[1..]))
+				}
+				_ => None
+			})
+			.collect()
+	}
+}
+
+fn batch_worker(session: &Session, receiver: &Receiver<BatchRequest>, max_batch_size: usize, max_wait: Duration) {
+	// models with a fixed batch dimension would reject a concatenated batch
+	let batchable = session.inputs.iter().all(|input| match &input.input_type {
+		ValueType::Tensor { dimensions, .. } => dimensions.first() == Some(&-1),
+		_ => false
+	});
+	// a request which didn't fit into the previous batch, which starts the next one
+	let mut carried: Option<BatchRequest> = None;
+	while let Some(first) = carried.take().or_else(|| receiver.recv().ok()) {
+		let deadline = Instant::now() + max_wait;
+		let mut rows = first.rows();
+		let mut requests = vec![first];
+		while rows < max_batch_size {
+			let timeout = deadline.saturating_duration_since(Instant::now());
+			match receiver.recv_timeout(timeout) {
+				Ok(request) if rows + request.rows() > max_batch_size => {
+					carried = Some(request);
+					break;
+				}
+				Ok(request) => {
+					rows += request.rows();
+					requests.push(request);
+				}
+				Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => break
+			}
+		}
+
+		// group requests with compatible shapes; unbatchable requests each get their own group
+		let mut groups: Vec<Vec<BatchRequest>> = Vec::new();
+		for request in requests {
+			let key = request.batch_key();
+			match groups.iter_mut().find(|group| key.is_some() && group[0].batch_key() == key) {
+				Some(group) => group.push(request),
+				None => groups.push(vec![request])
+			}
+		}
+
+		for group in groups {
+			if group.len() == 1 || !batchable {
+				run_separately(session, group);
+			} else if let Err(group) = run_batched(session, group) {
+				run_separately(session, group);
+			}
+		}
+	}
+}
+
+fn run_single(session: &Session, inputs: &[DynValue]) -> Result<Vec<DynValue>> {
+	let inputs: Vec<SessionInputValue<'_>> = inputs.iter().map(|input| SessionInputValue::View(input.view())).collect();
+	let inputs: SessionInputs<'_, '_> = SessionInputs::ValueSlice(&inputs);
+	let outputs = session.run(inputs)?;
+	Ok(outputs.into_iter().map(|(_, value)| value).collect())
+}
+
+fn run_separately(session: &Session, requests: Vec<BatchRequest>) {
+	for request in requests {
+		request.slot.complete(run_single(session, &request.inputs));
+	}
+}
+
+/// Runs a group of compatible requests as a single batch. If the batch fails or its outputs can't be split along the
+/// batch dimension, the requests are returned to be run separately.
+fn run_batched(session: &Session, requests: Vec<BatchRequest>) -> Result<(), Vec<BatchRequest>> {
+	let rows: Vec<usize> = requests.iter().map(BatchRequest::rows).collect();
+
+	let outputs = (0..requests[0].inputs.len())
+		.map(|i| DynValue::concatenate_refs(&requests.iter().map(|request| &request.inputs[i]).collect::<Vec<_>>(), 0))
+		.collect::<Result<Vec<_>>>()
+		.and_then(|inputs| run_single(session, &inputs));
+	let Ok(outputs) = outputs else {
+		return Err(requests);
+	};
+
+	let mut scattered: Vec<Vec<DynValue>> = requests.iter().map(|_| Vec::with_capacity(outputs.len())).collect();
+	for output in &outputs {
+		let Ok(parts) = output.split(0, &rows) else {
+			return Err(requests);
+		};
+		for (request_outputs, part) in scattered.iter_mut().zip(parts) {
+			request_outputs.push(part);
+		}
+	}
+	for (request, outputs) in requests.into_iter().zip(scattered) {
+		request.slot.complete(Ok(outputs));
+	}
+	Ok(())
+}
@@ -1,67 +1,174 @@
 #[cfg(feature = "fetch-models")]
-use std::fmt::Write;
+use std::{fmt::Write, path::PathBuf};
 use std::{any::Any, marker::PhantomData, path::Path, ptr::NonNull, sync::Arc};
 
-use super::SessionBuilder;
+use super::{SessionBuilder, SessionOrigin};
 use crate::{
 	AsPointer,
-	environment::get_environment,
 	error::{Error, ErrorCode, Result},
 	execution_providers::apply_execution_providers,
 	memory::Allocator,
 	ortsys,
-	session::{InMemorySession, Input, Output, Session, SharedSessionInner, dangerous}
+	session::{InMemorySession, Input, Output, Session, SharedSessionInner, dangerous},
+	value::ValueType
 };
 
 impl SessionBuilder {
 	/// Downloads a pre-trained ONNX model from the given URL and builds the session.
+	///
+	/// Downloaded models are cached in the `ort` cache directory (or the directory specified by the `ORT_MODEL_CACHE_DIR`
+	/// environment variable), keyed by the URL. If the server provided an `ETag` or `Last-Modified` header when the model
+	/// was downloaded, subsequent calls revalidate the cached model with a conditional request, and only download it
+	/// again if it has changed. If the server cannot be reached, the cached model is used as-is.
+	///
+	/// To ignore the cache & always download the model, see [`SessionBuilder::commit_from_url_with_refresh`]. To verify
+	/// the integrity of the model, see [`SessionBuilder::commit_from_url_with_sha256`].
 	#[cfg(feature = "fetch-models")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "fetch-models")))]
 	pub fn commit_from_url(self, model_url: impl AsRef<str>) -> Result<Session> {
-		let mut download_dir = ort_sys::internal::dirs::cache_dir()
-			.expect("could not determine cache directory")
-			.join("models");
+		self.commit_from_url_with_refresh(model_url, false)
+	}
+
+	/// Downloads a pre-trained ONNX model from the given URL and builds the session. If `force_refresh` is `true`, the
+	/// model is always downloaded again, replacing any cached copy; otherwise, this behaves exactly like
+	/// [`SessionBuilder::commit_from_url`].
+	#[cfg(feature = "fetch-models")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "fetch-models")))]
+	pub fn commit_from_url_with_refresh(self, model_url: impl AsRef<str>, force_refresh: bool) -> Result<Session> {
+		let model_filepath = Self::fetch_model(model_url.as_ref(), force_refresh)?;
+		self.commit_from_file(model_filepath)
+	}
+
+	/// Downloads a pre-trained ONNX model from the given URL, verifies that its SHA-256 hash matches `sha256` (given as
+	/// a hex string), and builds the session.
+	///
+	/// The model is cached like with [`SessionBuilder::commit_from_url`]. If the cached model does not match the
+	/// expected hash, it is downloaded again; if the downloaded model still does not match, it is removed from the cache
+	/// and an error is returned.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// # let expected_sha256 = "";
+	/// let session = Session::builder()?.commit_from_url_with_sha256("https://example.com/model.onnx", expected_sha256)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "fetch-models")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "fetch-models")))]
+	pub fn commit_from_url_with_sha256(self, model_url: impl AsRef<str>, sha256: impl AsRef<str>) -> Result<Session> {
+		let url = model_url.as_ref();
+		let expected = sha256.as_ref().to_ascii_lowercase();
+
+		let mut model_filepath = Self::fetch_model(url, false)?;
+		if file_sha256(&model_filepath)? != expected {
+			tracing::warn!(model_filepath = format!("{}", model_filepath.display()).as_str(), "Cached model does not match expected hash, downloading again");
+			model_filepath = Self::fetch_model(url, true)?;
+			let actual = file_sha256(&model_filepath)?;
+			if actual != expected {
+				let _ = std::fs::remove_file(&model_filepath);
+				let _ = std::fs::remove_file(model_filepath.with_extension("validator"));
+				return Err(Error::new(format!("Downloaded model from {url:?} has SHA-256 hash {actual}, expected {expected}")));
+			}
+		}
+
+		self.commit_from_file(model_filepath)
+	}
+
+	/// Downloads the model at `url` into the cache (if necessary), returning the path to the cached model.
+	#[cfg(feature = "fetch-models")]
+	fn fetch_model(url: &str, force_refresh: bool) -> Result<PathBuf> {
+		let mut download_dir = match std::env::var_os("ORT_MODEL_CACHE_DIR") {
+			Some(dir) => PathBuf::from(dir),
+			None => ort_sys::internal::dirs::cache_dir()
+				.expect("could not determine cache directory")
+				.join("models")
+		};
 		if std::fs::create_dir_all(&download_dir).is_err() {
 			download_dir = std::env::current_dir().expect("Failed to obtain current working directory");
 		}
 
-		let url = model_url.as_ref();
-		let model_filename = <sha2::Sha256 as sha2::Digest>::digest(url).into_iter().fold(String::new(), |mut s, b| {
-			let _ = write!(&mut s, "{:02x}", b);
-			s
-		});
-		let model_filepath = download_dir.join(model_filename);
-		let downloaded_path = if model_filepath.exists() {
-			tracing::info!(model_filepath = format!("{}", model_filepath.display()).as_str(), "Model already exists, skipping download");
-			model_filepath
+		let model_filename = to_hex(&<sha2::Sha256 as sha2::Digest>::digest(url));
+		let model_filepath = download_dir.join(&model_filename);
+		// Stores the `ETag` & `Last-Modified` headers of the cached model, one per line.
+		let validator_filepath = download_dir.join(format!("{model_filename}.validator"));
+
+		let validator = if !force_refresh && model_filepath.exists() {
+			match std::fs::read_to_string(&validator_filepath) {
+				Ok(validator) => Some(validator),
+				Err(_) => {
+					tracing::info!(model_filepath = format!("{}", model_filepath.display()).as_str(), "Model already exists, skipping download");
+					return Ok(model_filepath);
+				}
+			}
 		} else {
-			tracing::info!(model_filepath = format!("{}", model_filepath.display()).as_str(), url = format!("{url:?}").as_str(), "Downloading model");
+			None
+		};
 
-			let resp = ureq::get(url).call().map_err(|e| Error::new(format!("Error downloading to file: {e}")))?;
+		let mut request = ureq::get(url);
+		if let Some(validator) = &validator {
+			let mut lines = validator.lines();
+			if let Some(etag) = lines.next().filter(|s| !s.is_empty()) {
+				request = request.set("If-None-Match", etag);
+			}
+			if let Some(last_modified) = lines.next().filter(|s| !s.is_empty()) {
+				request = request.set("If-Modified-Since", last_modified);
+			}
+		}
 
-			let len = resp
-				.header("Content-Length")
-				.and_then(|s| s.parse::<usize>().ok())
-				.expect("Missing Content-Length header");
-			tracing::info!(len, "Downloading {} bytes", len);
+		let resp = match request.call() {
+			Ok(resp) => resp,
+			Err(e) if validator.is_some() => {
+				tracing::warn!(model_filepath = format!("{}", model_filepath.display()).as_str(), "Failed to revalidate cached model, using cached copy: {e}");
+				return Ok(model_filepath);
+			}
+			Err(e) => return Err(Error::new(format!("Error downloading to file: {e}")))
+		};
+		if resp.status() == 304 {
+			tracing::info!(model_filepath = format!("{}", model_filepath.display()).as_str(), "Cached model is up to date, skipping download");
+			return Ok(model_filepath);
+		}
 
-			let mut reader = resp.into_reader();
+		tracing::info!(model_filepath = format!("{}", model_filepath.display()).as_str(), url = format!("{url:?}").as_str(), "Downloading model");
 
-			let f = std::fs::File::create(&model_filepath).expect("Failed to create model file");
-			let mut writer = std::io::BufWriter::new(f);
+		let etag = resp.header("ETag").unwrap_or_default().to_owned();
+		let last_modified = resp.header("Last-Modified").unwrap_or_default().to_owned();
+		let len = resp
+			.header("Content-Length")
+			.and_then(|s| s.parse::<usize>().ok())
+			.expect("Missing Content-Length header");
+		tracing::info!(len, "Downloading {} bytes", len);
 
-			let bytes_io_count = std::io::copy(&mut reader, &mut writer).map_err(Error::wrap)?;
-			if bytes_io_count == len as u64 {
-				model_filepath
-			} else {
-				return Err(Error::new(format!("Failed to download entire model; file only has {bytes_io_count} bytes, expected {len}")));
-			}
-		};
+		let mut reader = resp.into_reader();
+
+		// Download to a temporary file first so an interrupted download can't leave a truncated model in the cache.
+		let partial_filepath = download_dir.join(format!("{model_filename}.part"));
+		let f = std::fs::File::create(&partial_filepath).expect("Failed to create model file");
+		let mut writer = std::io::BufWriter::new(f);
+
+		let bytes_io_count = std::io::copy(&mut reader, &mut writer).map_err(Error::wrap)?;
+		std::io::Write::flush(&mut writer).map_err(Error::wrap)?;
+		drop(writer);
+		if bytes_io_count != len as u64 {
+			let _ = std::fs::remove_file(&partial_filepath);
+			return Err(Error::new(format!("Failed to download entire model; file only has {bytes_io_count} bytes, expected {len}")));
+		}
+		std::fs::rename(&partial_filepath, &model_filepath).map_err(Error::wrap)?;
 
-		self.commit_from_file(downloaded_path)
+		if etag.is_empty() && last_modified.is_empty() {
+			let _ = std::fs::remove_file(&validator_filepath);
+		} else if let Err(e) = std::fs::write(&validator_filepath, format!("{etag}\n{last_modified}")) {
+			tracing::warn!("Failed to save cache validator for downloaded model: {e}");
+		}
+
+		Ok(model_filepath)
 	}
 
 	/// Loads an ONNX model from a file and builds the session.
+	///
+	/// If the model stores its weights in external data files, they are resolved relative to the model file's
+	/// directory, regardless of the process's current working directory, unless a different directory was configured
+	/// with [`SessionBuilder::with_external_data_dir`].
 	pub fn commit_from_file<P>(mut self, model_filepath_ref: P) -> Result<Session>
 	where
 		P: AsRef<Path>
@@ -70,11 +177,37 @@ impl SessionBuilder {
 		if !model_filepath.exists() {
 			return Err(Error::new_with_code(ErrorCode::NoSuchFile, format!("File at `{}` does not exist", model_filepath.display())));
 		}
+		// capture the options as-is, before they are modified by committing, so the session can be recreated in `try_clone`
+		let origin = if self.retain_origin {
+			Some(SessionOrigin::new(&self, model_filepath.to_path_buf())?)
+		} else {
+			None
+		};
+
+		// ONNX Runtime only respects a custom external data directory for models loaded from memory, and the model must be
+		// parsed to validate initializer overrides.
+		if !self.intermediate_outputs.is_empty()
+			|| !self.initializer_overrides.is_empty()
+			|| self.inspect_graph_nodes
+			|| self.retain_model_proto
+			|| self.custom_external_data_dir
+		{
+			let model_bytes = std::fs::read(model_filepath).map_err(Error::wrap)?;
+			// Since the model is now loaded from memory, ONNX Runtime needs to be told where to find external data.
+			if !self.custom_external_data_dir {
+				if let Some(model_dir) = model_filepath.parent().and_then(Path::to_str) {
+					self.add_config_entry("session.model_external_initializers_file_folder_path", model_dir)?;
+				}
+			}
+			let mut session = self.commit_from_memory(&model_bytes)?;
+			session.origin = origin;
+			return Ok(session);
+		}
 
 		let model_path = crate::util::path_to_os_char(model_filepath);
 
-		let env = get_environment()?;
-		apply_execution_providers(&mut self, env.execution_providers.iter().cloned())?;
+		let env = self.environment()?;
+		apply_execution_providers(&mut self, env.execution_providers.iter().cloned(), false)?;
 
 		if env.has_global_threadpool && !self.no_global_thread_pool {
 			ortsys![unsafe DisablePerSessionThreads(self.ptr_mut())?];
@@ -115,6 +248,9 @@ impl SessionBuilder {
 		if let Some(thread_manager) = self.thread_manager.take() {
 			extras.push(Box::new(thread_manager) as Box<dyn Any>);
 		}
+		for resource in self.execution_provider_resources.drain(..) {
+			extras.push(Box::new(resource) as Box<dyn Any>);
+		}
 
 		Ok(Session {
 			inner: Arc::new(SharedSessionInner {
@@ -124,7 +260,10 @@ impl SessionBuilder {
 				_environment: env
 			}),
 			inputs,
-			outputs
+			outputs,
+			graph_nodes: None,
+			model_proto: None,
+			origin
 		})
 	}
 
@@ -146,10 +285,23 @@ impl SessionBuilder {
 
 	/// Load an ONNX graph from memory and commit the session.
 	pub fn commit_from_memory(mut self, model_bytes: &[u8]) -> Result<Session> {
+		let patched_model;
+		let model_bytes = if self.intermediate_outputs.is_empty() {
+			model_bytes
+		} else {
+			patched_model = crate::model_proto::add_graph_outputs(model_bytes, &self.intermediate_outputs)?;
+			&patched_model
+		};
+		if !self.initializer_overrides.is_empty() {
+			self.validate_initializer_overrides(model_bytes)?;
+		}
+		let graph_nodes = if self.inspect_graph_nodes { Some(crate::model_proto::graph_nodes(model_bytes)?) } else { None };
+		let model_proto = if self.retain_model_proto { Some(model_bytes.to_vec()) } else { None };
+
 		let mut session_ptr: *mut ort_sys::OrtSession = std::ptr::null_mut();
 
-		let env = get_environment()?;
-		apply_execution_providers(&mut self, env.execution_providers.iter().cloned())?;
+		let env = self.environment()?;
+		apply_execution_providers(&mut self, env.execution_providers.iter().cloned(), false)?;
 
 		if env.has_global_threadpool && !self.no_global_thread_pool {
 			ortsys![unsafe DisablePerSessionThreads(self.ptr_mut())?];
@@ -197,6 +349,13 @@ impl SessionBuilder {
 		if let Some(thread_manager) = self.thread_manager.take() {
 			extras.push(Box::new(thread_manager) as Box<dyn Any>);
 		}
+		for resource in self.execution_provider_resources.drain(..) {
+			extras.push(Box::new(resource) as Box<dyn Any>);
+		}
+		// ONNX Runtime does not copy overridden initializers, so they must outlive the session.
+		for (_, value) in self.initializer_overrides.drain(..) {
+			extras.push(Box::new(value) as Box<dyn Any>);
+		}
 
 		let session = Session {
 			inner: Arc::new(SharedSessionInner {
@@ -206,8 +365,54 @@ impl SessionBuilder {
 				_environment: env
 			}),
 			inputs,
-			outputs
+			outputs,
+			graph_nodes,
+			model_proto,
+			origin: None
 		};
 		Ok(session)
 	}
 }
+
+impl SessionBuilder {
+	fn validate_initializer_overrides(&self, model_bytes: &[u8]) -> Result<()> {
+		let initializers = crate::model_proto::graph_initializers(model_bytes)?;
+		for (name, value) in &self.initializer_overrides {
+			let Some(initializer) = initializers.iter().find(|i| &i.name == name) else {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Model has no initializer named `{name}` to override")));
+			};
+			let ValueType::Tensor { ty, dimensions, .. } = value.dtype() else {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Override for initializer `{name}` must be a tensor")));
+			};
+			if ort_sys::ONNXTensorElementDataType::from(*ty) as u64 != initializer.data_type {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Override for initializer `{name}` has element type {ty}, which does not match the type declared in the model")
+				));
+			}
+			if *dimensions != initializer.dims {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Override for initializer `{name}` has shape {dimensions:?}, but the model declares it with shape {:?}", initializer.dims)
+				));
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(feature = "fetch-models")]
+fn to_hex(bytes: &[u8]) -> String {
+	bytes.iter().fold(String::new(), |mut s, b| {
+		let _ = write!(&mut s, "{:02x}", b);
+		s
+	})
+}
+
+#[cfg(feature = "fetch-models")]
+fn file_sha256(path: &Path) -> Result<String> {
+	let mut hasher = <sha2::Sha256 as sha2::Digest>::new();
+	let mut file = std::fs::File::open(path).map_err(Error::wrap)?;
+	std::io::copy(&mut file, &mut hasher).map_err(Error::wrap)?;
+	Ok(to_hex(&sha2::Digest::finalize(hasher)))
+}
@@ -6,13 +6,20 @@ use crate::Result;
 impl SessionBuilder {
 	/// Enable/disable the usage of prepacking.
 	///
+	/// When enabled, some kernels rearrange their constant weights into a layout optimized for the kernel when the
+	/// session is created, trading extra memory for faster inference. Disabling prepacking (`with_prepacking(false)`)
+	/// keeps weights exactly as they are stored in the model, which saves memory & keeps initializers loaded from
+	/// memory-mapped or shared buffers untouched.
+	///
 	/// This option is **enabled** by default.
+	#[doc(alias = "with_disable_prepacking")]
 	pub fn with_prepacking(mut self, enable: bool) -> Result<Self> {
 		self.add_config_entry("session.disable_prepacking", if enable { "0" } else { "1" })?;
 		Ok(self)
 	}
 
-	/// Use allocators from the registered environment.
+	/// Use allocators from the registered environment, such as a shared CPU arena configured via
+	/// [`EnvironmentBuilder::with_memory_arena`](crate::environment::EnvironmentBuilder::with_memory_arena).
 	///
 	/// This option is **disabled** by default.
 	pub fn with_env_allocators(mut self) -> Result<Self> {
@@ -68,12 +75,50 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
-	/// Accepts a comma-separated list of optimizers to disable.
-	pub fn with_disabled_optimizers(mut self, optimizers: &str) -> Result<Self> {
-		self.add_config_entry("optimization.disable_specified_optimizers", optimizers)?;
+	/// Disables the graph optimizers (transformers & rewrite rules) with the given names, e.g. `"ConstantFolding"` or
+	/// `"GeluFusion"`.
+	///
+	/// This is mostly useful for debugging: if a model's outputs differ between optimization levels, disabling
+	/// optimizers one by one can narrow down which one is responsible.
+	///
+	/// ONNX Runtime silently ignores names it doesn't recognize. `ort` emits a warning for names which are not among the
+	/// optimizers it knows about, but since ONNX Runtime may add new optimizers, an unrecognized name is not treated as
+	/// an error.
+	///
+	/// ```
+	/// # use ort::session::{builder::GraphOptimizationLevel, Session};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_optimization_level(GraphOptimizationLevel::Level3)?
+	/// 	.with_disabled_optimizers(&["ConstantFolding", "MatMulAddFusion"])?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_disabled_optimizers(mut self, optimizers: &[&str]) -> Result<Self> {
+		for optimizer in optimizers {
+			if !KNOWN_OPTIMIZERS.contains(optimizer) {
+				tracing::warn!("Unknown optimizer `{optimizer}` passed to `SessionBuilder::with_disabled_optimizers`; it may not be disabled");
+			}
+		}
+		self.add_config_entry("optimization.disable_specified_optimizers", &optimizers.join(","))?;
 		Ok(self)
 	}
 
+	/// Disables constant folding, which precomputes nodes whose inputs are all constant at session creation time.
+	///
+	/// Constant folding runs even at [`GraphOptimizationLevel::Level1`](super::GraphOptimizationLevel::Level1), and can
+	/// hide bugs in custom operators or model export tools by evaluating the affected nodes ahead of time; disabling it
+	/// makes sure every node actually runs during inference.
+	///
+	/// This is shorthand for `with_disabled_optimizers(&["ConstantFolding"])`. Like
+	/// [`SessionBuilder::with_disabled_optimizers`], this replaces any previously disabled optimizers, so to disable
+	/// others in addition to constant folding, include `"ConstantFolding"` in a single call to `with_disabled_optimizers`
+	/// instead.
+	pub fn with_constant_folding_disabled(self) -> Result<Self> {
+		self.with_disabled_optimizers(&["ConstantFolding"])
+	}
+
 	/// Enable using device allocator for allocating initialized tensor memory.
 	///
 	/// This option is **disabled** by default.
@@ -98,3 +143,60 @@ impl SessionBuilder {
 		Ok(self)
 	}
 }
+
+/// Names of the graph transformers & rewrite rules in ONNX Runtime's optimizer pipeline, used to warn about typos in
+/// [`SessionBuilder::with_disabled_optimizers`].
+const KNOWN_OPTIMIZERS: &[&str] = &[
+	// transformers
+	"AttentionFusion",
+	"BiasDropoutFusion",
+	"BiasGeluFusion",
+	"BiasSoftmaxFusion",
+	"CommonSubexpressionElimination",
+	"ConstantFolding",
+	"ConstantSharing",
+	"ConvActivationFusion",
+	"DoubleQDQPairsRemover",
+	"DynamicQuantizeMatMulFusion",
+	"EmbedLayerNormFusion",
+	"EnsureUniqueDQForNodeUnit",
+	"FastGeluFusion",
+	"FreeDimensionOverrideTransformer",
+	"GeluApproximation",
+	"GeluFusion",
+	"GemmActivationFusion",
+	"LayerNormFusion",
+	"MatMulActivationFusion",
+	"MatMulAddFusion",
+	"MatMulIntegerToFloatFusion",
+	"MatMulScaleFusion",
+	"NchwcTransformer",
+	"NhwcTransformer",
+	"QDQFinalCleanupTransformer",
+	"QDQPropagationTransformer",
+	"QDQS8ToU8Transformer",
+	"QDQSelectorActionTransformer",
+	"QuickGeluFusion",
+	"ReshapeFusion",
+	"SimplifiedLayerNormFusion",
+	"SkipLayerNormFusion",
+	"TransposeOptimizer",
+	// rewrite rules
+	"CastElimination",
+	"ConvAddFusion",
+	"ConvBNFusion",
+	"ConvMulFusion",
+	"DivMulFusion",
+	"EliminateDropout",
+	"EliminateIdentity",
+	"EliminateSlice",
+	"ExpandElimination",
+	"FuseReluClip",
+	"GemmSumFusion",
+	"GemmTransposeFusion",
+	"MatmulBNFusion",
+	"NoopElimination",
+	"NotWhereFusion",
+	"PadFusion",
+	"UnsqueezeElimination"
+];
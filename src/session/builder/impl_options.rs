@@ -12,8 +12,8 @@ use super::SessionBuilder;
 use crate::{
 	AsPointer,
 	environment::{self, ThreadManager},
-	error::Result,
-	execution_providers::{ExecutionProviderDispatch, apply_execution_providers},
+	error::{Error, ErrorCode, Result, status_to_result},
+	execution_providers::{ArenaExtendStrategy, ExecutionProviderDispatch, apply_execution_providers},
 	memory::MemoryInfo,
 	operator::OperatorDomain,
 	ortsys,
@@ -35,7 +35,32 @@ impl SessionBuilder {
 	///   `CUDAExecutionProvider`) **is discouraged** unless you allow the user to configure the execution providers by
 	///   providing a `Vec` of [`ExecutionProviderDispatch`]es.
 	pub fn with_execution_providers(mut self, execution_providers: impl IntoIterator<Item = ExecutionProviderDispatch>) -> Result<Self> {
-		apply_execution_providers(&mut self, execution_providers.into_iter())?;
+		apply_execution_providers(&mut self, execution_providers.into_iter(), false)?;
+		Ok(self)
+	}
+
+	/// Registers a list of execution providers for this session like [`SessionBuilder::with_execution_providers`], but
+	/// returns an error if any execution provider other than [`CPUExecutionProvider`] fails to register, instead of
+	/// silently falling back to the next one.
+	///
+	/// This only concerns *registration* of the EP, e.g. when CUDA isn't installed, the EP's Cargo feature isn't
+	/// enabled, or ONNX Runtime wasn't built with support for it. Once an EP is registered, nodes it doesn't support are
+	/// still assigned to the next EP in the list, or the CPU EP, as usual. This makes it possible to tell "CUDA isn't
+	/// working" apart from "this operator isn't implemented on CUDA".
+	///
+	/// ```no_run
+	/// # use ort::{execution_providers::{CPUExecutionProvider, CUDAExecutionProvider}, session::Session};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_execution_providers_strict([CUDAExecutionProvider::default().build(), CPUExecutionProvider::default().build()])?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`CPUExecutionProvider`]: crate::execution_providers::CPUExecutionProvider
+	pub fn with_execution_providers_strict(mut self, execution_providers: impl IntoIterator<Item = ExecutionProviderDispatch>) -> Result<Self> {
+		apply_execution_providers(&mut self, execution_providers.into_iter(), true)?;
 		Ok(self)
 	}
 
@@ -51,10 +76,38 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Confines this session's computation to the CPUs of the given NUMA node, to avoid costly cross-socket memory
+	/// accesses on multi-socket servers.
+	///
+	/// This configures the session to use one intra-op thread per CPU of the node, pins each thread to its CPU, and
+	/// allocates initializers (i.e. model weights) directly instead of through the memory arena. Since memory is
+	/// typically placed on the NUMA node of the thread that first writes to it, **the session should also be created
+	/// (committed) from a thread running on the same node** - e.g. one pinned with `numactl --cpunodebind` - so that
+	/// its weights are allocated in node-local memory.
+	///
+	/// This option has no effect if the environment was configured with a global thread pool. It is only supported on
+	/// Linux, where the CPUs of each node are read from `/sys/devices/system/node`.
+	pub fn with_numa_aware_allocation(self, numa_node: u32) -> Result<Self> {
+		let cpus = numa_node_cpus(numa_node)?;
+		if cpus.is_empty() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("NUMA node {numa_node} has no CPUs")));
+		}
+
+		let mut builder = self.with_intra_threads(cpus.len())?;
+		// ONNX Runtime uses the calling thread as one of the intra-op threads, so affinities are only specified for the
+		// `n - 1` threads it creates. Processor IDs are 1-based.
+		let affinities = cpus.iter().skip(1).map(|cpu| (cpu + 1).to_string()).collect::<Vec<_>>().join(";");
+		if !affinities.is_empty() {
+			builder.add_config_entry("session.intra_op_thread_affinities", &affinities)?;
+		}
+		builder.add_config_entry("session.use_device_allocator_for_initializers", "1")?;
+		Ok(builder)
+	}
+
 	/// Configure the session to use a number of threads to parallelize the execution of the graph. If nodes can be run
 	/// in parallel, this sets the maximum number of threads to use to run them in parallel.
 	///
-	/// This has no effect when the session execution mode is set to `Sequential`.
+	/// This has no effect when the session execution mode is set to [`ExecutionMode::Sequential`].
 	///
 	/// For configuring the number of threads used to parallelize the execution within nodes, see
 	/// [`SessionBuilder::with_intra_threads()`].
@@ -63,21 +116,39 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
-	/// Enable/disable the parallel execution mode for this session. By default, this is disabled.
+	/// Sets the [`ExecutionMode`] of this session. By default, nodes are executed sequentially.
 	///
-	/// Parallel execution can improve performance for models with many branches, at the cost of higher memory usage.
-	/// You can configure the amount of threads used to parallelize the execution of the graph via
-	/// [`SessionBuilder::with_inter_threads()`].
-	pub fn with_parallel_execution(mut self, parallel_execution: bool) -> Result<Self> {
-		let execution_mode = if parallel_execution {
-			ort_sys::ExecutionMode::ORT_PARALLEL
-		} else {
-			ort_sys::ExecutionMode::ORT_SEQUENTIAL
-		};
-		ortsys![unsafe SetSessionExecutionMode(self.ptr_mut(), execution_mode)?];
+	/// [`ExecutionMode::Parallel`] allows independent branches of the graph to be executed concurrently, which can
+	/// significantly improve performance for models with many parallel subgraphs (e.g. multi-branch encoders). Note
+	/// that parallel execution may increase memory usage, since the intermediate tensors of multiple branches need to be
+	/// kept alive at the same time.
+	///
+	/// The number of threads used to execute branches in parallel is configured via
+	/// [`SessionBuilder::with_inter_threads()`], which has no effect in [`ExecutionMode::Sequential`].
+	///
+	/// ```
+	/// # use ort::session::{Session, builder::ExecutionMode};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_execution_mode(ExecutionMode::Parallel)?
+	/// 	.with_inter_threads(4)?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_execution_mode(mut self, mode: ExecutionMode) -> Result<Self> {
+		ortsys![unsafe SetSessionExecutionMode(self.ptr_mut(), mode.into())?];
 		Ok(self)
 	}
 
+	/// Enable/disable the parallel execution mode for this session. By default, this is disabled.
+	///
+	/// This is equivalent to [`SessionBuilder::with_execution_mode`] with either [`ExecutionMode::Parallel`] or
+	/// [`ExecutionMode::Sequential`].
+	pub fn with_parallel_execution(self, parallel_execution: bool) -> Result<Self> {
+		self.with_execution_mode(if parallel_execution { ExecutionMode::Parallel } else { ExecutionMode::Sequential })
+	}
+
 	/// Set the session's optimization level. See [`GraphOptimizationLevel`] for more information on the different
 	/// optimization levels.
 	pub fn with_optimization_level(mut self, opt_level: GraphOptimizationLevel) -> Result<Self> {
@@ -124,13 +195,45 @@ impl SessionBuilder {
 	}
 
 	/// Registers a custom operator library at the given library path.
+	///
+	/// The library must export a `RegisterCustomOps` function, which ONNX Runtime calls to register the library's
+	/// operators with this session. ONNX Runtime keeps the library loaded for as long as any session created from this
+	/// builder is alive, so the library does not need to be kept loaded separately.
+	///
+	/// Returns an error mentioning `lib_path` if the library cannot be loaded or does not export `RegisterCustomOps`.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_operator_library("libcustom_ops.so")?
+	/// 	.commit_from_file("model_with_custom_ops.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[doc(alias = "RegisterCustomOpsLibrary")]
 	pub fn with_operator_library(mut self, lib_path: impl AsRef<Path>) -> Result<Self> {
+		let lib_path = lib_path.as_ref();
 		let path_cstr = path_to_os_char(lib_path);
-		ortsys![unsafe RegisterCustomOpsLibrary_V2(self.ptr_mut(), path_cstr.as_ptr())?];
+		status_to_result(ortsys![unsafe RegisterCustomOpsLibrary_V2(self.ptr_mut(), path_cstr.as_ptr())])
+			.map_err(|e| Error::new_with_code(e.code(), format!("Failed to load operator library `{}`: {}", lib_path.display(), e.message())))?;
 		Ok(self)
 	}
 
-	/// Enables [`onnxruntime-extensions`](https://github.com/microsoft/onnxruntime-extensions) custom operators.
+	/// Registers a custom operator library at the given library path. This is an alias of
+	/// [`SessionBuilder::with_operator_library`].
+	pub fn with_custom_op_library(self, lib_path: impl AsRef<Path>) -> Result<Self> {
+		self.with_operator_library(lib_path)
+	}
+
+	/// Enables [`onnxruntime-extensions`](https://github.com/microsoft/onnxruntime-extensions) custom operators, like
+	/// the tokenizer operators used to run text models end-to-end.
+	///
+	/// This only works if ONNX Runtime was built with the extensions included (`--use_extensions`), which is not the
+	/// case for the prebuilt binaries; otherwise, this returns an error. In that case, build or download the standalone
+	/// extensions library (`libortextensions.so`, `ortextensions.dll`, etc.) and load it with
+	/// [`SessionBuilder::with_operator_library`] instead.
+	#[doc(alias = "with_ort_extensions")]
 	pub fn with_extensions(mut self) -> Result<Self> {
 		ortsys![unsafe EnableOrtCustomOps(self.ptr_mut())?];
 		Ok(self)
@@ -147,6 +250,26 @@ impl SessionBuilder {
 	///
 	/// The default (non-deterministic) kernels will typically use faster algorithms that may introduce slight variance.
 	/// Enabling deterministic compute will output reproducible results, but may come at a performance penalty.
+	///
+	/// This is equivalent to setting the `session.use_deterministic_compute` config entry. It is currently honored by
+	/// the CPU, CUDA (and by extension TensorRT's CUDA fallback), and ROCm execution providers; other execution
+	/// providers may ignore it and use nondeterministic kernels regardless.
+	///
+	/// Deterministic kernels alone do not guarantee bit-identical results across runs - the order in which threads
+	/// reduce partial results can also introduce variance. For fully reproducible outputs, combine this with a single
+	/// intra-op thread and sequential execution:
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_deterministic_compute(true)?
+	/// 	.with_intra_threads(1)?
+	/// 	.with_parallel_execution(false)?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	pub fn with_deterministic_compute(mut self, enable: bool) -> Result<Self> {
 		ortsys![unsafe SetDeterministicCompute(self.ptr_mut(), enable)?];
 		Ok(self)
@@ -160,6 +283,35 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Overrides the value of the initializer (i.e. weight) named `name` with `value`, without modifying the model.
+	///
+	/// This can be used to swap out a set of weights, like an embedding table or bias, for experimentation or
+	/// A/B testing without re-exporting the model. `value` must have the same element type & shape as the initializer
+	/// declared in the graph; this is checked when the session is committed, which will fail if the model has no
+	/// initializer named `name` or if the types or shapes do not match.
+	///
+	/// Since the declared initializers must be read from the model, this is only supported for `.onnx` models (not
+	/// `.ort` models).
+	///
+	/// ```no_run
+	/// # use ort::{session::Session, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let bias = Tensor::from_array(([768], vec![0.0_f32; 768]))?;
+	/// let session = Session::builder()?
+	/// 	.with_initializer_override("encoder.bias", bias)?
+	/// 	.commit_from_file("model.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_initializer_override(mut self, name: impl Into<String>, value: impl Into<DynValue>) -> Result<Self> {
+		let name = name.into();
+		let c_name = CString::new(name.as_str())?;
+		let value = Rc::new(value.into());
+		ortsys![unsafe AddInitializer(self.ptr_mut(), c_name.as_ptr(), value.ptr())?];
+		self.initializer_overrides.push((name, value));
+		Ok(self)
+	}
+
 	pub fn with_external_initializer_file(mut self, file_name: impl AsRef<Path>, buffer: Cow<'static, [u8]>) -> Result<Self> {
 		// We need to hold onto `buffer` until the session is actually committed. This means `buffer` must outlive 'self (if
 		// SessionBuilder were to have a lifetime). Adding a lifetime to SessionBuilder would be breaking, so right now we
@@ -172,6 +324,48 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Sets the directory in which ONNX Runtime looks for the external data files (i.e. weights stored outside of the
+	/// `.onnx` file) referenced by the model.
+	///
+	/// External data file paths stored in the model are relative; they are resolved against, in order of precedence:
+	/// 1. the directory configured with this method, if any;
+	/// 2. when loading with [`SessionBuilder::commit_from_file`], the directory containing the model file;
+	/// 3. when loading from memory, the process's current working directory.
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_external_data_dir("/srv/models/weights")?
+	/// 	.commit_from_file("/srv/models/graphs/model.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_external_data_dir(mut self, path: impl AsRef<Path>) -> Result<Self> {
+		let path = path.as_ref();
+		let path = path
+			.to_str()
+			.ok_or_else(|| Error::new_with_code(ErrorCode::InvalidArgument, format!("External data directory `{}` is not valid UTF-8", path.display())))?;
+		self.add_config_entry("session.model_external_initializers_file_folder_path", path)?;
+		self.custom_external_data_dir = true;
+		Ok(self)
+	}
+
+	/// Sets an identifier for this session which is attached to all log messages ONNX Runtime emits for it, making it
+	/// easier to tell apart the logs of multiple sessions running concurrently.
+	///
+	/// When logs are forwarded to [`tracing`] (see
+	/// [`EnvironmentBuilder::with_tracing`](crate::environment::EnvironmentBuilder::with_tracing)), the identifier is
+	/// recorded as the `id` field of the `ort` span each message is emitted in.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_log_id("bert-encoder")?.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[doc(alias("with_session_log_id", "SetSessionLogId"))]
 	pub fn with_log_id(mut self, id: impl AsRef<str>) -> Result<Self> {
 		let id = CString::new(id.as_ref())?;
 		ortsys![unsafe SetSessionLogId(self.ptr_mut(), id.as_ptr())?];
@@ -190,11 +384,166 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Shares prepacked constant weights with other sessions using the same [`PrepackedWeights`] container.
+	///
+	/// Some kernels (like the CPU execution provider's `MatMul` & `Conv`) *prepack* their constant weights into a
+	/// layout better suited for computation. When multiple sessions load the same model, each would normally keep its
+	/// own copy of these packed weights; with a shared container, the packed weights are only stored once.
+	///
+	/// ```
+	/// # use ort::session::{Session, builder::PrepackedWeights};
+	/// # fn main() -> ort::Result<()> {
+	/// let weights = PrepackedWeights::new();
+	///
+	/// let session_a = Session::builder()?
+	/// 	.with_prepacked_weights(&weights)?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// let session_b = Session::builder()?
+	/// 	.with_intra_threads(1)?
+	/// 	.with_prepacked_weights(&weights)?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	pub fn with_prepacked_weights(mut self, weights: &PrepackedWeights) -> Result<Self> {
 		self.prepacked_weights = Some(weights.clone());
 		Ok(self)
 	}
 
+	/// Exposes the values of internal nodes as additional session outputs, which can be used to capture intermediate
+	/// activations.
+	///
+	/// `names` are the names of values produced by nodes inside the graph (i.e. a node's output name, not the node's
+	/// name). They are appended to the model's outputs in the given order, so they can be retrieved from
+	/// [`SessionOutputs`](crate::session::SessionOutputs) by name, just like regular outputs.
+	///
+	/// This works by editing the model's graph before the session is created, so it is only supported for `.onnx`
+	/// models (not `.ort` models). Note that graph optimizations may fuse away intermediate values, in which case
+	/// session creation will fail; lower the [optimization level](SessionBuilder::with_optimization_level) if so.
+	///
+	/// ```no_run
+	/// # use ort::session::{Session, builder::GraphOptimizationLevel};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_optimization_level(GraphOptimizationLevel::Disable)?
+	/// 	.with_intermediate_outputs(&["/encoder/layer.0/attention/Softmax_output_0"])?
+	/// 	.commit_from_file("model.onnx")?;
+	///
+	/// let outputs = session.run(ort::inputs![ndarray::Array2::<i64>::zeros((1, 8))]?)?;
+	/// let attention = outputs["/encoder/layer.0/attention/Softmax_output_0"].try_extract_tensor::<f32>()?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_intermediate_outputs(mut self, names: &[&str]) -> Result<Self> {
+		self.intermediate_outputs.extend(names.iter().map(|n| n.to_string()));
+		Ok(self)
+	}
+
+	/// Enables inspection of the model's graph, so that its nodes can be enumerated with [`Session::graph_nodes`].
+	///
+	/// When loading a model from a file, enabling this requires reading the model into memory, so it is disabled by
+	/// default. It is only supported for `.onnx` models.
+	///
+	/// [`Session::graph_nodes`]: crate::session::Session::graph_nodes
+	pub fn with_graph_nodes(mut self, enable: bool) -> Result<Self> {
+		self.inspect_graph_nodes = enable;
+		Ok(self)
+	}
+
+	/// Retains a copy of the serialized model, so that it can be retrieved with [`Session::model_proto_bytes`] or dumped
+	/// with [`Session::model_proto_text`] for debugging.
+	///
+	/// This keeps the entire model in memory for the lifetime of the session (in addition to ONNX Runtime's own copy),
+	/// so it is disabled by default. It is only supported for `.onnx` models.
+	///
+	/// [`Session::model_proto_bytes`]: crate::session::Session::model_proto_bytes
+	/// [`Session::model_proto_text`]: crate::session::Session::model_proto_text
+	pub fn with_model_proto(mut self, enable: bool) -> Result<Self> {
+		self.retain_model_proto = enable;
+		Ok(self)
+	}
+
+	/// Allows sessions committed with [`SessionBuilder::commit_from_file`] to be re-created with
+	/// [`Session::try_clone`].
+	///
+	/// This keeps a copy of the session options alive for the lifetime of the session, so it is disabled by default.
+	/// Committing will fail if the builder was configured with options that cannot be shared between threads, like
+	/// [`SessionBuilder::with_allocator`], initializers, or a custom thread manager.
+	///
+	/// [`Session::try_clone`]: crate::session::Session::try_clone
+	pub fn with_cloning(mut self, enable: bool) -> Result<Self> {
+		self.retain_origin = enable;
+		Ok(self)
+	}
+
+	/// Seeds the random number generator used by operators like `Dropout`, `RandomNormal`, `RandomUniform`,
+	/// `Multinomial` & `Bernoulli`, for reproducible runs of graphs containing them.
+	///
+	/// ONNX Runtime's inference API has no per-session seed; the only seed control it exposes is the process-wide seed
+	/// of its training API. This therefore requires the `training` feature (and a build of ONNX Runtime with training
+	/// support), and affects *all* sessions in the process. Without the `training` feature, or if the linked build of ONNX
+	/// Runtime does not support training, this returns an error. Seeds must be at most [`i64::MAX`].
+	/// Nodes which specify their own `seed` attribute are not affected.
+	///
+	/// Seeding is only honored by the following execution providers:
+	/// - [`CPUExecutionProvider`](crate::execution_providers::CPUExecutionProvider)
+	/// - [`CUDAExecutionProvider`](crate::execution_providers::CUDAExecutionProvider)
+	///
+	/// Other execution providers have no seed control, so seeding is a no-op for nodes assigned to them. A warning is
+	/// logged for each such execution provider registered with this builder, and they can be queried with
+	/// [`SessionBuilder::unseeded_execution_providers`].
+	///
+	/// ```no_run
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_seed(42)?.commit_from_file("eval_model.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_seed(mut self, seed: u64) -> Result<Self> {
+		self.seed = Some(seed);
+
+		#[cfg(not(feature = "training"))]
+		return Err(Error::new_with_code(
+			ErrorCode::NotImplemented,
+			"ONNX Runtime only supports seeding its random number generator via the training API; enable the `training` feature to use `SessionBuilder::with_seed`"
+		));
+
+		#[cfg(feature = "training")]
+		{
+			// ONNX Runtime takes the seed as an `int64_t`
+			let Ok(signed_seed) = i64::try_from(seed) else {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Seed {seed} is out of range; seeds must be at most {}", i64::MAX)));
+			};
+			// `trainsys!` panics if the training API is unavailable, e.g. when dynamically loading a build of ONNX Runtime
+			// without training support
+			crate::training::training_api()?;
+			crate::training::trainsys![unsafe SetSeed(signed_seed)?];
+			for ep in self.unseeded_execution_providers() {
+				tracing::warn!("`{ep}` has no seed control; `SessionBuilder::with_seed` has no effect on nodes assigned to it");
+			}
+			Ok(self)
+		}
+	}
+
+	/// Returns the names of execution providers registered with this builder which do not support seeding via
+	/// [`SessionBuilder::with_seed`].
+	#[must_use]
+	pub fn unseeded_execution_providers(&self) -> Vec<&'static str> {
+		self.registered_execution_providers
+			.iter()
+			.copied()
+			.filter(|ep| !SEEDABLE_EXECUTION_PROVIDERS.contains(ep))
+			.collect()
+	}
+
+	pub(crate) fn record_registered_execution_provider(&mut self, ep: &'static str) {
+		self.registered_execution_providers.push(ep);
+		if self.seed.is_some() && !SEEDABLE_EXECUTION_PROVIDERS.contains(&ep) {
+			tracing::warn!("`{ep}` has no seed control; `SessionBuilder::with_seed` has no effect on nodes assigned to it");
+		}
+	}
+
 	/// Configures this environment to use its own thread pool instead of defaulting to the
 	/// [`Environment`](crate::environment::Environment)'s global thread pool if one was defined.
 	pub fn with_independent_thread_pool(mut self) -> Result<Self> {
@@ -202,6 +551,24 @@ impl SessionBuilder {
 		Ok(self)
 	}
 
+	/// Configures this session to use the [`Environment`](crate::environment::Environment)'s global thread pool instead
+	/// of creating its own, undoing [`SessionBuilder::with_independent_thread_pool`].
+	///
+	/// Sessions already use the global thread pool by default if one is configured, but this method additionally
+	/// returns an error if the current environment was not created with
+	/// [`EnvironmentBuilder::with_global_thread_pool`](crate::environment::EnvironmentBuilder::with_global_thread_pool),
+	/// making sure the session won't silently create its own threads.
+	pub fn disable_per_session_threads(mut self) -> Result<Self> {
+		if !self.environment()?.has_global_threadpool {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				"Cannot disable per-session threads: the environment has no global thread pool (see `EnvironmentBuilder::with_global_thread_pool`)"
+			));
+		}
+		self.no_global_thread_pool = false;
+		Ok(self)
+	}
+
 	pub fn with_thread_manager<T: ThreadManager + Any + 'static>(mut self, manager: T) -> Result<Self> {
 		let manager = Rc::new(manager);
 		ortsys![unsafe SessionOptionsSetCustomThreadCreationOptions(self.ptr_mut(), (&*manager as *const T) as *mut std::ffi::c_void)?];
@@ -303,6 +670,107 @@ impl From<GraphOptimizationLevel> for ort_sys::GraphOptimizationLevel {
 	}
 }
 
+/// Execution providers whose random operators use the seed set by [`SessionBuilder::with_seed`].
+const SEEDABLE_EXECUTION_PROVIDERS: [&str; 2] = ["CPUExecutionProvider", "CUDAExecutionProvider"];
+
+/// Controls how the nodes of a session's graph are executed; see [`SessionBuilder::with_execution_mode`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionMode {
+	/// Nodes are executed one after another. This is the default.
+	#[default]
+	Sequential,
+	/// Independent nodes may be executed concurrently on the session's inter-op thread pool.
+	Parallel
+}
+
+impl From<ExecutionMode> for ort_sys::ExecutionMode {
+	fn from(val: ExecutionMode) -> Self {
+		match val {
+			ExecutionMode::Sequential => ort_sys::ExecutionMode::ORT_SEQUENTIAL,
+			ExecutionMode::Parallel => ort_sys::ExecutionMode::ORT_PARALLEL
+		}
+	}
+}
+
+#[cfg(target_os = "linux")]
+fn numa_node_cpus(numa_node: u32) -> Result<Vec<usize>> {
+	let path = format!("/sys/devices/system/node/node{numa_node}/cpulist");
+	let cpu_list = std::fs::read_to_string(&path)
+		.map_err(|e| Error::new_with_code(ErrorCode::NoSuchFile, format!("Failed to read CPUs of NUMA node {numa_node} from `{path}`: {e}")))?;
+	parse_cpu_list(cpu_list.trim()).ok_or_else(|| Error::new(format!("Failed to parse CPU list of NUMA node {numa_node}: `{cpu_list}`")))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn numa_node_cpus(_numa_node: u32) -> Result<Vec<usize>> {
+	Err(Error::new_with_code(ErrorCode::NotImplemented, "NUMA-aware allocation is only supported on Linux"))
+}
+
+/// Parses a Linux CPU list, like `0-3,8-11,16`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_cpu_list(cpu_list: &str) -> Option<Vec<usize>> {
+	let mut cpus = Vec::new();
+	for range in cpu_list.split(',').filter(|r| !r.is_empty()) {
+		match range.split_once('-') {
+			Some((start, end)) => cpus.extend(start.parse::<usize>().ok()?..=end.parse::<usize>().ok()?),
+			None => cpus.push(range.parse().ok()?)
+		}
+	}
+	Some(cpus)
+}
+
+/// Configuration for a memory arena; see
+/// [`EnvironmentBuilder::with_memory_arena`](crate::environment::EnvironmentBuilder::with_memory_arena) for the shared
+/// CPU arena, and [`CUDAExecutionProvider::with_arena_config`](crate::execution_providers::CUDAExecutionProvider::with_arena_config)
+/// for the CUDA device arena.
+#[derive(Debug, Default, Clone)]
+pub struct ArenaConfig {
+	/// How the arena grows when it runs out of memory.
+	pub strategy: ArenaExtendStrategy,
+	/// The size of the first allocation made by the arena, in bytes. Defaults to 1 MiB.
+	pub initial_chunk_size: Option<usize>,
+	/// The maximum amount of memory the arena may allocate, in bytes. Defaults to unlimited.
+	pub max_mem_limit: Option<usize>
+}
+
+impl ArenaConfig {
+	pub(crate) fn create(&self) -> Result<ArenaCfgHandle> {
+		let mut keys = vec![CString::new("arena_extend_strategy")?];
+		let mut values = vec![match self.strategy {
+			ArenaExtendStrategy::NextPowerOfTwo => 0,
+			ArenaExtendStrategy::SameAsRequested => 1
+		}];
+		if let Some(initial_chunk_size) = self.initial_chunk_size {
+			keys.push(CString::new("initial_chunk_size_bytes")?);
+			values.push(initial_chunk_size);
+		}
+		if let Some(max_mem_limit) = self.max_mem_limit {
+			keys.push(CString::new("max_mem")?);
+			values.push(max_mem_limit);
+		}
+		let key_ptrs: Vec<*const c_char> = keys.iter().map(|k| k.as_ptr()).collect();
+
+		let mut arena_cfg: *mut ort_sys::OrtArenaCfg = ptr::null_mut();
+		ortsys![unsafe CreateArenaCfgV2(key_ptrs.as_ptr(), values.as_ptr(), values.len(), &mut arena_cfg)?; nonNull(arena_cfg)];
+		Ok(ArenaCfgHandle(arena_cfg))
+	}
+}
+
+/// An owned [`ort_sys::OrtArenaCfg`].
+#[derive(Debug)]
+pub(crate) struct ArenaCfgHandle(*mut ort_sys::OrtArenaCfg);
+
+impl ArenaCfgHandle {
+	pub(crate) fn ptr(&self) -> *mut ort_sys::OrtArenaCfg {
+		self.0
+	}
+}
+
+impl Drop for ArenaCfgHandle {
+	fn drop(&mut self) {
+		ortsys![unsafe ReleaseArenaCfg(self.0)];
+	}
+}
+
 #[derive(Debug)]
 struct PrepackedWeightsInner(*mut ort_sys::OrtPrepackedWeightsContainer);
 
@@ -312,12 +780,21 @@ impl Drop for PrepackedWeightsInner {
 	}
 }
 
+// The container is internally synchronized by ONNX Runtime, since it is designed to be shared by multiple sessions.
+unsafe impl Send for PrepackedWeightsInner {}
+unsafe impl Sync for PrepackedWeightsInner {}
+
+/// A container for prepacked weights which can be shared between multiple sessions to reduce memory usage.
+///
+/// Cloning a `PrepackedWeights` is cheap and refers to the same underlying container. The container is kept alive as
+/// long as any session created with it is alive. See [`SessionBuilder::with_prepacked_weights`].
 #[derive(Debug, Clone)]
 pub struct PrepackedWeights {
 	inner: Arc<PrepackedWeightsInner>
 }
 
 impl PrepackedWeights {
+	/// Creates a new, empty prepacked weights container.
 	#[allow(clippy::new_without_default)]
 	pub fn new() -> Self {
 		let mut ptr: *mut ort_sys::OrtPrepackedWeightsContainer = ptr::null_mut();
@@ -339,3 +816,16 @@ impl AsPointer for PrepackedWeights {
 		self.inner.0
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::parse_cpu_list;
+
+	#[test]
+	fn test_parse_cpu_list() {
+		assert_eq!(parse_cpu_list("0-3,8-9,16"), Some(vec![0, 1, 2, 3, 8, 9, 16]));
+		assert_eq!(parse_cpu_list("5"), Some(vec![5]));
+		assert_eq!(parse_cpu_list(""), Some(vec![]));
+		assert_eq!(parse_cpu_list("0-x"), None);
+	}
+}
@@ -2,6 +2,7 @@ use std::{
 	any::Any,
 	borrow::Cow,
 	ffi::CString,
+	path::PathBuf,
 	ptr::{self, NonNull},
 	rc::Rc,
 	sync::Arc
@@ -9,7 +10,8 @@ use std::{
 
 use crate::{
 	AsPointer,
-	error::{Result, assert_non_null_pointer, status_to_result},
+	environment::{self, Environment},
+	error::{Error, ErrorCode, Result, assert_non_null_pointer, status_to_result},
 	memory::MemoryInfo,
 	operator::OperatorDomain,
 	ortsys,
@@ -20,7 +22,7 @@ mod impl_commit;
 mod impl_config_keys;
 mod impl_options;
 
-pub use self::impl_options::{GraphOptimizationLevel, PrepackedWeights};
+pub use self::impl_options::{ArenaConfig, ExecutionMode, GraphOptimizationLevel, PrepackedWeights};
 
 /// Creates a session using the builder pattern.
 ///
@@ -46,9 +48,19 @@ pub struct SessionBuilder {
 	operator_domains: Vec<Arc<OperatorDomain>>,
 	external_initializers: Vec<Rc<DynValue>>,
 	external_initializer_buffers: Vec<Cow<'static, [u8]>>,
+	initializer_overrides: Vec<(String, Rc<DynValue>)>,
 	prepacked_weights: Option<PrepackedWeights>,
+	intermediate_outputs: Vec<String>,
+	inspect_graph_nodes: bool,
+	retain_model_proto: bool,
+	custom_external_data_dir: bool,
 	thread_manager: Option<Rc<dyn Any>>,
-	no_global_thread_pool: bool
+	execution_provider_resources: Vec<Rc<dyn Any>>,
+	no_global_thread_pool: bool,
+	seed: Option<u64>,
+	registered_execution_providers: Vec<&'static str>,
+	environment: Option<Arc<Environment>>,
+	retain_origin: bool
 }
 
 impl Clone for SessionBuilder {
@@ -62,9 +74,19 @@ impl Clone for SessionBuilder {
 			operator_domains: self.operator_domains.clone(),
 			external_initializers: self.external_initializers.clone(),
 			external_initializer_buffers: self.external_initializer_buffers.clone(),
+			initializer_overrides: self.initializer_overrides.clone(),
 			prepacked_weights: self.prepacked_weights.clone(),
+			intermediate_outputs: self.intermediate_outputs.clone(),
+			inspect_graph_nodes: self.inspect_graph_nodes,
+			retain_model_proto: self.retain_model_proto,
+			custom_external_data_dir: self.custom_external_data_dir,
 			thread_manager: self.thread_manager.clone(),
-			no_global_thread_pool: self.no_global_thread_pool
+			execution_provider_resources: self.execution_provider_resources.clone(),
+			no_global_thread_pool: self.no_global_thread_pool,
+			seed: self.seed,
+			registered_execution_providers: self.registered_execution_providers.clone(),
+			environment: self.environment.clone(),
+			retain_origin: self.retain_origin
 		}
 	}
 }
@@ -98,20 +120,82 @@ impl SessionBuilder {
 			operator_domains: Vec::new(),
 			external_initializers: Vec::new(),
 			external_initializer_buffers: Vec::new(),
+			initializer_overrides: Vec::new(),
 			prepacked_weights: None,
+			intermediate_outputs: Vec::new(),
+			inspect_graph_nodes: false,
+			retain_model_proto: false,
+			custom_external_data_dir: false,
 			thread_manager: None,
-			no_global_thread_pool: false
+			execution_provider_resources: Vec::new(),
+			no_global_thread_pool: false,
+			seed: None,
+			registered_execution_providers: Vec::new(),
+			environment: None,
+			retain_origin: false
 		})
 	}
 
-	pub(crate) fn add_config_entry(&mut self, key: &str, value: &str) -> Result<()> {
+	/// Creates a new session builder which creates its session under `env` instead of the global environment. See
+	/// [`EnvironmentBuilder::build_local`](crate::environment::EnvironmentBuilder::build_local).
+	pub fn new_with_env(env: &Arc<Environment>) -> Result<Self> {
+		let mut builder = Self::new()?;
+		builder.environment = Some(Arc::clone(env));
+		Ok(builder)
+	}
+
+	/// Returns the environment sessions created with this builder will use.
+	pub(crate) fn environment(&self) -> Result<Arc<Environment>> {
+		match &self.environment {
+			Some(env) => Ok(Arc::clone(env)),
+			None => environment::get_environment()
+		}
+	}
+
+	/// Keeps `resource` alive until sessions committed from this builder are dropped, for execution providers which
+	/// hand ONNX Runtime pointers it will hold on to past registration.
+	pub(crate) fn retain_execution_provider_resource(&mut self, resource: Rc<dyn Any>) {
+		self.execution_provider_resources.push(resource);
+	}
+
+	/// Adds a raw configuration entry to the session options in place, via ONNX Runtime's `AddSessionConfigEntry`.
+	///
+	/// This is a low-level escape hatch for options which have no typed wrapper, like
+	/// `"session.intra_op_thread_affinities"` or `"session.disable_prepacking"`; prefer
+	/// [`SessionBuilder::with_config_entry`] when building a session by value.
+	///
+	/// # Stability
+	/// Config keys are defined by ONNX Runtime, not `ort`, and are **not** covered by `ort`'s semver guarantees. Keys may
+	/// be renamed, change meaning, or be removed between ONNX Runtime versions, and unknown keys or invalid values are
+	/// often silently ignored rather than reported. Some keys are also set internally by `ort` (e.g. by
+	/// [`SessionBuilder::with_external_data_dir`]), and setting them here may conflict with those options. Always check
+	/// the keys you use against the `onnxruntime_session_options_config_keys.h` of the ONNX Runtime version you link to.
+	#[doc(hidden)]
+	pub fn add_config_entry(&mut self, key: &str, value: &str) -> Result<&mut Self> {
 		let key = CString::new(key)?;
 		let value = CString::new(value)?;
 		ortsys![unsafe AddSessionConfigEntry(self.ptr_mut(), key.as_ptr(), value.as_ptr())?];
-		Ok(())
+		Ok(self)
 	}
 
 	/// Adds a custom configuration entry to the session.
+	///
+	/// This can be used to set options which `ort` does not yet provide a typed wrapper for. See
+	/// [`onnxruntime_session_options_config_keys.h`](https://github.com/microsoft/onnxruntime/blob/main/include/onnxruntime/core/session/onnxruntime_session_options_config_keys.h)
+	/// for a list of supported keys & values. Unknown keys are silently ignored by ONNX Runtime.
+	///
+	/// Note that config keys are defined by ONNX Runtime and are not covered by `ort`'s semver guarantees; they may
+	/// change between ONNX Runtime versions.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?
+	/// 	.with_config_entry("session.disable_prepacking", "1")?
+	/// 	.commit_from_file("tests/data/upsample.onnx")?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	pub fn with_config_entry(mut self, key: impl AsRef<str>, value: impl AsRef<str>) -> Result<Self> {
 		self.add_config_entry(key.as_ref(), value.as_ref())?;
 		Ok(self)
@@ -125,3 +209,101 @@ impl AsPointer for SessionBuilder {
 		self.session_options_ptr.as_ptr()
 	}
 }
+
+/// The model file & thread-safe options a [`Session`](crate::session::Session) was committed with, used to re-create it
+/// in [`Session::try_clone`](crate::session::Session::try_clone).
+///
+/// Unlike a [`SessionBuilder`], this holds no reference-counted (`Rc`) state, so it can be shared by threads along
+/// with the session.
+pub(crate) struct SessionOrigin {
+	session_options_ptr: NonNull<ort_sys::OrtSessionOptions>,
+	operator_domains: Vec<Arc<OperatorDomain>>,
+	prepacked_weights: Option<PrepackedWeights>,
+	intermediate_outputs: Vec<String>,
+	inspect_graph_nodes: bool,
+	retain_model_proto: bool,
+	custom_external_data_dir: bool,
+	no_global_thread_pool: bool,
+	seed: Option<u64>,
+	registered_execution_providers: Vec<&'static str>,
+	environment: Option<Arc<Environment>>,
+	pub(crate) path: PathBuf
+}
+
+impl SessionOrigin {
+	/// Captures the options of `builder`, which is about to commit the model at `path`.
+	pub(crate) fn new(builder: &SessionBuilder, path: PathBuf) -> Result<Self> {
+		if builder.memory_info.is_some()
+			|| !builder.external_initializers.is_empty()
+			|| !builder.external_initializer_buffers.is_empty()
+			|| !builder.initializer_overrides.is_empty()
+			|| builder.thread_manager.is_some()
+			|| !builder.execution_provider_resources.is_empty()
+		{
+			return Err(Error::new_with_code(
+				ErrorCode::NotImplemented,
+				"Sessions with a custom memory info, initializers, thread manager, or execution provider resources cannot be cloned"
+			));
+		}
+
+		let mut session_options_ptr = ptr::null_mut();
+		ortsys![unsafe CloneSessionOptions(builder.ptr(), &mut session_options_ptr)?; nonNull(session_options_ptr)];
+		Ok(Self {
+			session_options_ptr: unsafe { NonNull::new_unchecked(session_options_ptr) },
+			operator_domains: builder.operator_domains.clone(),
+			prepacked_weights: builder.prepacked_weights.clone(),
+			intermediate_outputs: builder.intermediate_outputs.clone(),
+			inspect_graph_nodes: builder.inspect_graph_nodes,
+			retain_model_proto: builder.retain_model_proto,
+			custom_external_data_dir: builder.custom_external_data_dir,
+			no_global_thread_pool: builder.no_global_thread_pool,
+			seed: builder.seed,
+			registered_execution_providers: builder.registered_execution_providers.clone(),
+			environment: builder.environment.clone(),
+			path
+		})
+	}
+
+	/// Creates a new builder with the captured options, which will itself retain its origin.
+	pub(crate) fn to_builder(&self) -> Result<SessionBuilder> {
+		let mut session_options_ptr = ptr::null_mut();
+		ortsys![unsafe CloneSessionOptions(self.session_options_ptr.as_ptr(), &mut session_options_ptr)?; nonNull(session_options_ptr)];
+		Ok(SessionBuilder {
+			session_options_ptr: unsafe { NonNull::new_unchecked(session_options_ptr) },
+			memory_info: None,
+			operator_domains: self.operator_domains.clone(),
+			external_initializers: Vec::new(),
+			external_initializer_buffers: Vec::new(),
+			initializer_overrides: Vec::new(),
+			prepacked_weights: self.prepacked_weights.clone(),
+			intermediate_outputs: self.intermediate_outputs.clone(),
+			inspect_graph_nodes: self.inspect_graph_nodes,
+			retain_model_proto: self.retain_model_proto,
+			custom_external_data_dir: self.custom_external_data_dir,
+			thread_manager: None,
+			execution_provider_resources: Vec::new(),
+			no_global_thread_pool: self.no_global_thread_pool,
+			seed: self.seed,
+			registered_execution_providers: self.registered_execution_providers.clone(),
+			environment: self.environment.clone(),
+			retain_origin: true
+		})
+	}
+}
+
+impl std::fmt::Debug for SessionOrigin {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SessionOrigin").field("path", &self.path).finish_non_exhaustive()
+	}
+}
+
+impl Drop for SessionOrigin {
+	fn drop(&mut self) {
+		ortsys![unsafe ReleaseSessionOptions(self.session_options_ptr.as_ptr())];
+	}
+}
+
+// The captured `OrtSessionOptions` are only ever read (cloned) by `to_builder`, and all other shared state is
+// reference counted atomically.
+unsafe impl Send for SessionOrigin {}
+unsafe impl Sync for SessionOrigin {}
@@ -14,38 +14,44 @@ use std::{
 	any::Any,
 	ffi::{CStr, CString},
 	marker::PhantomData,
-	ops::Deref,
+	ops::{ControlFlow, Deref},
 	os::raw::c_char,
 	ptr::NonNull,
-	sync::Arc
+	sync::Arc,
+	time::{Duration, Instant}
 };
 
 use crate::{
 	AsPointer, char_p_to_string,
 	environment::Environment,
-	error::{Error, ErrorCode, Result, assert_non_null_pointer, status_to_result},
+	error::{Error, ErrorCode, ErrorKind, Result, assert_non_null_pointer, status_to_result},
 	extern_system_fn,
 	io_binding::IoBinding,
 	memory::Allocator,
 	metadata::ModelMetadata,
 	ortsys,
-	value::{Value, ValueType}
+	tensor::TensorElementType,
+	value::{DynTensor, DynValue, Tensor, Value, ValueType, ValueTypeMarker, calculate_tensor_size}
 };
 
 mod r#async;
+mod batcher;
 pub mod builder;
 pub mod input;
 pub mod output;
+mod pipeline;
 pub mod run_options;
 pub use self::{
 	r#async::InferenceFut,
+	batcher::{BatchFut, DynamicBatcher},
 	input::{SessionInputValue, SessionInputs},
-	output::SessionOutputs,
+	output::{BenchmarkStats, RunTimings, SessionOutputs},
+	pipeline::SessionPipeline,
 	run_options::{HasSelectedOutputs, NoSelectedOutputs, RunOptions, SelectedOutputMarker}
 };
 use self::{
 	r#async::{AsyncInferenceContext, InferenceFutInner, RunOptionsRef},
-	builder::SessionBuilder
+	builder::{SessionBuilder, SessionOrigin}
 };
 
 /// Holds onto an [`ort_sys::OrtSession`] pointer and its associated allocator.
@@ -91,13 +97,46 @@ impl Drop for SharedSessionInner {
 /// # 	Ok(())
 /// # }
 /// ```
+///
+/// # Sharing between threads
+/// `Session` is `Send + Sync`, and [`Session::run`] only takes `&self`, so a single session can be shared between
+/// many threads (e.g. via an `Arc<Session>`) and run concurrently, rather than loading a separate copy of the model
+/// for each thread.
+///
+/// Note that sharing a session between threads is known to cause segfaults with some execution providers other than
+/// the CPU execution provider, like CUDA & DirectML.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use ort::session::Session;
+/// # fn main() -> ort::Result<()> {
+/// let session = Arc::new(Session::builder()?.commit_from_file("tests/data/upsample.onnx")?);
+/// let handles: Vec<_> = (0..4)
+/// 	.map(|_| {
+/// 		let session = Arc::clone(&session);
+/// 		std::thread::spawn(move || -> ort::Result<()> {
+/// 			let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+/// 			let _outputs = session.run(ort::inputs![input]?)?;
+/// 			Ok(())
+/// 		})
+/// 	})
+/// 	.collect();
+/// for handle in handles {
+/// 	handle.join().expect("thread panicked")?;
+/// }
+/// # 	Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Session {
 	pub(crate) inner: Arc<SharedSessionInner>,
 	/// Information about the graph's inputs.
 	pub inputs: Vec<Input>,
 	/// Information about the graph's outputs.
-	pub outputs: Vec<Output>
+	pub outputs: Vec<Output>,
+	pub(crate) graph_nodes: Option<Vec<GraphNode>>,
+	pub(crate) model_proto: Option<Vec<u8>>,
+	pub(crate) origin: Option<SessionOrigin>
 }
 
 /// A [`Session`] where the graph data is stored in memory.
@@ -134,12 +173,72 @@ pub struct Output {
 	pub output_type: ValueType
 }
 
+/// Information about a node in a [`Session`]'s graph; see [`Session::graph_nodes`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+	/// Name of the node. Node names are optional in ONNX, so this may be empty.
+	pub name: String,
+	/// The operator this node executes, e.g. `Conv` or `MatMul`.
+	pub op_type: String,
+	/// The domain of the operator; empty for the default ONNX domain.
+	pub domain: String,
+	/// Names of the values consumed by this node. Omitted optional inputs are represented by an empty string.
+	pub inputs: Vec<String>,
+	/// Names of the values produced by this node.
+	pub outputs: Vec<String>,
+	/// Names of the attributes set on this node.
+	pub attributes: Vec<String>
+}
+
 impl Session {
 	/// Creates a new [`SessionBuilder`].
 	pub fn builder() -> Result<SessionBuilder> {
 		SessionBuilder::new()
 	}
 
+	/// Creates a new [`SessionBuilder`] which creates its session under `env` instead of the global environment.
+	///
+	/// This is mainly useful for library crates, which shouldn't configure the global environment; see
+	/// [`EnvironmentBuilder::build_local`](crate::environment::EnvironmentBuilder::build_local).
+	pub fn builder_with_env(env: &Arc<Environment>) -> Result<SessionBuilder> {
+		SessionBuilder::new_with_env(env)
+	}
+
+	/// Creates a new, independent session from the same model file & with the same options (execution providers,
+	/// threading options, etc.) as this one.
+	///
+	/// ONNX Runtime has no way for sessions to share a loaded model, so this loads & optimizes the model again, just
+	/// like calling [`SessionBuilder::commit_from_file`] with the original builder would. To avoid keeping several
+	/// copies of prepacked weights in memory, configure the original session with
+	/// [`SessionBuilder::with_prepacked_weights`](crate::session::builder::SessionBuilder::with_prepacked_weights); the
+	/// container is shared by all clones.
+	///
+	/// Note that since [`Session::run`] can be called concurrently, a single session shared between threads via an
+	/// [`Arc`] is often enough; see [the type-level docs](Session#sharing-between-threads).
+	///
+	/// Only sessions created with [`SessionBuilder::commit_from_file`] with
+	/// [`SessionBuilder::with_cloning`](crate::session::builder::SessionBuilder::with_cloning) enabled can be cloned;
+	/// for other sessions, this returns an error.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_cloning(true)?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let workers: Vec<Session> = (0..4).map(|_| session.try_clone()).collect::<ort::Result<_>>()?;
+	/// assert_eq!(workers[0].inputs[0].name, session.inputs[0].name);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn try_clone(&self) -> Result<Session> {
+		let Some(origin) = &self.origin else {
+			return Err(Error::new_with_code(
+				ErrorCode::NotImplemented,
+				"Only sessions created with `SessionBuilder::commit_from_file` and `SessionBuilder::with_cloning` can be cloned"
+			));
+		};
+		origin.to_builder()?.commit_from_file(&origin.path)
+	}
+
 	/// Returns this session's [`Allocator`].
 	#[must_use]
 	pub fn allocator(&self) -> &Allocator {
@@ -158,6 +257,125 @@ impl Session {
 		Arc::clone(&self.inner)
 	}
 
+	/// Returns the names of the graph's inputs, in the order they are defined in the model.
+	///
+	/// These are the names to use as keys in [`ort::inputs!`](crate::inputs). See [`Session::inputs`] for more
+	/// information about each input, like its type & shape.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// for name in session.input_names() {
+	/// 	println!("input: {name}");
+	/// }
+	/// assert_eq!(session.input_names().len(), session.inputs.len());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[must_use]
+	pub fn input_names(&self) -> Vec<&str> {
+		self.inputs.iter().map(|input| input.name.as_str()).collect()
+	}
+
+	/// Returns the names of the graph's outputs, in the order they are defined in the model.
+	///
+	/// See [`Session::outputs`] for more information about each output, like its type & shape.
+	#[must_use]
+	pub fn output_names(&self) -> Vec<&str> {
+		self.outputs.iter().map(|output| output.name.as_str()).collect()
+	}
+
+	/// Returns an iterator over the nodes of the model's main graph, in the order they appear in the model file.
+	///
+	/// The graph is only inspected if the session was created with
+	/// [`SessionBuilder::with_graph_nodes`](crate::session::builder::SessionBuilder::with_graph_nodes); otherwise,
+	/// this returns an error. Note that this describes the graph as it was loaded, *before* any optimizations were
+	/// applied by ONNX Runtime.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_graph_nodes(true)?.commit_from_file("tests/data/upsample.onnx")?;
+	/// for node in session.graph_nodes()? {
+	/// 	println!("{} ({}): {:?} -> {:?}", node.name, node.op_type, node.inputs, node.outputs);
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn graph_nodes(&self) -> Result<impl Iterator<Item = &GraphNode>> {
+		self.graph_nodes
+			.as_ref()
+			.map(|nodes| nodes.iter())
+			.ok_or_else(|| Error::new("Graph nodes are only available if the session was created with `SessionBuilder::with_graph_nodes(true)`"))
+	}
+
+	/// Returns the serialized `ModelProto` this session was created from.
+	///
+	/// ONNX Runtime does not retain the model after loading it, so this is only available if the session was created
+	/// with [`SessionBuilder::with_model_proto`](crate::session::builder::SessionBuilder::with_model_proto); otherwise,
+	/// this returns an error. The returned model is exactly what was passed to ONNX Runtime, including any outputs
+	/// added by [`SessionBuilder::with_intermediate_outputs`](crate::session::builder::SessionBuilder::with_intermediate_outputs).
+	/// Weights stored in external data files are *not* inlined.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_model_proto(true)?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let model = session.model_proto_bytes()?;
+	/// assert_eq!(model, std::fs::read("tests/data/upsample.onnx").unwrap());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn model_proto_bytes(&self) -> Result<Vec<u8>> {
+		self.model_proto
+			.clone()
+			.ok_or_else(|| Error::new("The model is only available if the session was created with `SessionBuilder::with_model_proto(true)`"))
+	}
+
+	/// Returns a human-readable dump of the model's inputs, outputs, and the nodes of its main graph, for debugging.
+	///
+	/// Like [`Session::model_proto_bytes`], this requires the session to be created with
+	/// [`SessionBuilder::with_model_proto`](crate::session::builder::SessionBuilder::with_model_proto).
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.with_model_proto(true)?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let text = session.model_proto_text()?;
+	/// assert!(text.contains(session.inputs[0].name.as_str()));
+	/// println!("{text}");
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[doc(alias = "to_text")]
+	pub fn model_proto_text(&self) -> Result<String> {
+		use std::fmt::Write;
+
+		let model = self
+			.model_proto
+			.as_deref()
+			.ok_or_else(|| Error::new("The model is only available if the session was created with `SessionBuilder::with_model_proto(true)`"))?;
+		let nodes = crate::model_proto::graph_nodes(model)?;
+
+		let mut text = String::new();
+		// writing to a `String` never fails
+		let _ = writeln!(text, "inputs:");
+		for input in &self.inputs {
+			let _ = writeln!(text, "  {}: {}", input.name, input.input_type);
+		}
+		let _ = writeln!(text, "outputs:");
+		for output in &self.outputs {
+			let _ = writeln!(text, "  {}: {}", output.name, output.output_type);
+		}
+		let _ = writeln!(text, "nodes:");
+		for node in &nodes {
+			let op_type = if node.domain.is_empty() { node.op_type.clone() } else { format!("{}.{}", node.domain, node.op_type) };
+			let _ = writeln!(text, "  {} ({op_type}): [{}] -> [{}]", node.name, node.inputs.join(", "), node.outputs.join(", "));
+		}
+		Ok(text)
+	}
+
 	/// Returns a list of initializers which are overridable (i.e. also graph inputs).
 	#[must_use]
 	pub fn overridable_initializers(&self) -> Vec<OverridableInitializer> {
@@ -256,22 +474,384 @@ impl Session {
 		}
 	}
 
+	/// Run input data through the ONNX graph, computing only the outputs named in `output_names`.
+	///
+	/// Since ONNX Runtime only executes the parts of the graph required to compute the requested outputs, this can skip
+	/// a significant amount of work, e.g. running only the encoder of an encoder-decoder model when only its embedding
+	/// is needed. The returned [`SessionOutputs`] only contain the requested outputs, in the order they were requested.
+	///
+	/// Returns an error if any of the names is not an output of the model. For more control over outputs, like
+	/// preallocating output values, see [`OutputSelector`](crate::session::run_options::OutputSelector).
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let output_name = session.outputs[0].name.clone();
+	///
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let outputs = session.run_selecting(ort::inputs![input]?, &[output_name.as_str()])?;
+	/// assert_eq!(outputs.len(), 1);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn run_selecting<'s, 'i, 'v: 'i, const N: usize>(
+		&'s self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>,
+		output_names: &[&str]
+	) -> Result<SessionOutputs<'s, 's>> {
+		let output_names = output_names
+			.iter()
+			.map(|name| {
+				self.outputs
+					.iter()
+					.find(|o| o.name == *name)
+					.map(|o| o.name.as_str())
+					.ok_or_else(|| run_options::unknown_output(name, &self.outputs))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		let output_tensors = std::iter::repeat_with(|| None).take(output_names.len()).collect();
+
+		match input_values.into() {
+			SessionInputs::ValueSlice(input_values) => {
+				self.run_inner_with_outputs::<NoSelectedOutputs>(&self.input_names(), input_values.iter(), None, output_names, output_tensors, None)
+			}
+			SessionInputs::ValueArray(input_values) => {
+				self.run_inner_with_outputs::<NoSelectedOutputs>(&self.input_names(), input_values.iter(), None, output_names, output_tensors, None)
+			}
+			SessionInputs::ValueMap(input_values) => self.run_inner_with_outputs::<NoSelectedOutputs>(
+				&input_values.iter().map(|(k, _)| k.as_ref()).collect::<Vec<_>>(),
+				input_values.iter().map(|(_, v)| v),
+				None,
+				output_names,
+				output_tensors,
+				None
+			)
+		}
+	}
+
+	/// Run input data through the ONNX graph like [`Session::run`], additionally returning a [`RunTimings`] breakdown of
+	/// how long each phase of the run took.
+	///
+	/// This is useful to determine whether time is spent in ONNX Runtime itself, or in preparing inputs & wrapping
+	/// outputs on the Rust side. Note that the input binding phase only covers the conversion of `input_values` into
+	/// [`SessionInputs`] and the marshalling of names & pointers for the FFI call; values created *before* calling this
+	/// method (e.g. by [`ort::inputs!`](crate::inputs)) are not included, so the construction of those should be timed
+	/// separately.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let (outputs, timings) = session.run_timed(ort::inputs![input]?)?;
+	/// println!("{timings:?}");
+	/// assert!(timings.execution <= timings.total());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn run_timed<'s, 'i, 'v: 'i, const N: usize>(
+		&'s self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>
+	) -> Result<(SessionOutputs<'s, 's>, RunTimings)> {
+		let start = Instant::now();
+		let input_values = input_values.into();
+		let output_names = self.output_names();
+		let output_tensors = std::iter::repeat_with(|| None).take(output_names.len()).collect();
+		let mut timings = RunTimings {
+			input_binding: start.elapsed(),
+			..RunTimings::default()
+		};
+
+		let outputs = match input_values {
+			SessionInputs::ValueSlice(input_values) => self.run_inner_with_outputs::<NoSelectedOutputs>(
+				&self.input_names(),
+				input_values.iter(),
+				None,
+				output_names,
+				output_tensors,
+				Some(&mut timings)
+			),
+			SessionInputs::ValueArray(input_values) => self.run_inner_with_outputs::<NoSelectedOutputs>(
+				&self.input_names(),
+				input_values.iter(),
+				None,
+				output_names,
+				output_tensors,
+				Some(&mut timings)
+			),
+			SessionInputs::ValueMap(input_values) => self.run_inner_with_outputs::<NoSelectedOutputs>(
+				&input_values.iter().map(|(k, _)| k.as_ref()).collect::<Vec<_>>(),
+				input_values.iter().map(|(_, v)| v),
+				None,
+				output_names,
+				output_tensors,
+				Some(&mut timings)
+			)
+		}?;
+		Ok((outputs, timings))
+	}
+
+	/// Runs the session `n` times with the given inputs, returning statistics about the latency of each run.
+	///
+	/// One untimed run is performed first to warm up the session (see [`Session::warmup`]). Each run is timed
+	/// end-to-end, including binding inputs & extracting outputs. This can be used in CI to check that inference latency
+	/// hasn't regressed:
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let stats = session.benchmark(20, ort::inputs![input]?)?;
+	/// println!("p95 latency: {}us", stats.p95_ns / 1000);
+	/// assert!(stats.median_ns <= stats.p95_ns);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn benchmark<'s, 'i, 'v: 'i, const N: usize>(&'s self, n: usize, input_values: impl Into<SessionInputs<'i, 'v, N>>) -> Result<BenchmarkStats> {
+		if n == 0 {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot benchmark with 0 iterations"));
+		}
+
+		let input_values = input_values.into();
+		let (input_names, input_values): (Vec<&str>, Vec<&SessionInputValue<'v>>) = match &input_values {
+			SessionInputs::ValueSlice(input_values) => (self.input_names(), input_values.iter().collect()),
+			SessionInputs::ValueArray(input_values) => (self.input_names(), input_values.iter().collect()),
+			SessionInputs::ValueMap(input_values) => input_values.iter().map(|(k, v)| (k.as_ref(), v)).unzip()
+		};
+		let run = || {
+			let output_names = self.output_names();
+			let output_tensors = std::iter::repeat_with(|| None).take(output_names.len()).collect();
+			self.run_inner_with_outputs::<NoSelectedOutputs>(&input_names, input_values.iter().copied(), None, output_names, output_tensors, None)
+		};
+
+		run()?;
+		let mut latencies = Vec::with_capacity(n);
+		for _ in 0..n {
+			let start = Instant::now();
+			run()?;
+			latencies.push(start.elapsed().as_nanos() as u64);
+		}
+		Ok(BenchmarkStats::from_latencies(latencies))
+	}
+
+	/// Repeatedly runs the graph in an autoregressive loop, streaming each step's result to `stream`.
+	///
+	/// The model must have the same number of inputs and outputs. After each step, every output is fed back into the
+	/// input at the same position for the next step, and the first output - i.e. the newly generated token - is passed
+	/// to `stream`. Generation continues until `stream` returns [`ControlFlow::Break`], so the closure is responsible
+	/// for stopping on an end-of-sequence token or after a maximum number of steps.
+	///
+	/// ```no_run
+	/// # use std::ops::ControlFlow;
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("decoder_step.onnx")?;
+	/// let initial_state = ndarray::Array2::<f32>::zeros((1, 512));
+	///
+	/// let mut generated = Vec::new();
+	/// session.run_streaming(ort::inputs![ndarray::arr1(&[0_i64]), initial_state]?, |token| {
+	/// 	let token = token.try_extract_scalar::<i64>().unwrap_or(2);
+	/// 	generated.push(token);
+	/// 	if token == 2 || generated.len() >= 64 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+	/// })?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn run_streaming<'i, 'v: 'i, const N: usize>(
+		&self,
+		initial_inputs: impl Into<SessionInputs<'i, 'v, N>>,
+		mut stream: impl FnMut(&DynValue) -> ControlFlow<()>
+	) -> Result<()> {
+		if self.inputs.len() != self.outputs.len() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Streaming requires a model whose outputs can be fed back as inputs, but this model has {} inputs and {} outputs.",
+					self.inputs.len(),
+					self.outputs.len()
+				)
+			));
+		}
+
+		let mut outputs = self.run(initial_inputs)?;
+		while stream(&outputs[0]).is_continue() {
+			let next_inputs: Vec<SessionInputValue<'_>> = outputs.into_iter().map(|(_, value)| SessionInputValue::from(value)).collect();
+			outputs = self.run(next_inputs.as_slice())?;
+		}
+		Ok(())
+	}
+
+	/// Run input data through the ONNX graph, writing the results into caller-provided output buffers.
+	///
+	/// `outputs` must contain exactly one value for each of the session's [`Session::outputs`], in the same order. ONNX
+	/// Runtime will write directly into these values instead of allocating new ones, so for models with fixed output
+	/// shapes, the same buffers can be reused across calls to avoid per-run allocations.
+	///
+	/// Returns an error if the number of buffers doesn't match the number of outputs, or if a buffer's type or shape
+	/// is incompatible with the output it is bound to.
+	///
+	/// ```
+	/// # use ort::{session::Session, memory::Allocator, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let mut outputs = [Tensor::<f32>::new(&Allocator::default(), [1, 128, 128, 3])?];
+	/// for _ in 0..4 {
+	/// 	let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// 	session.run_into(ort::inputs![input]?, &mut outputs)?;
+	/// 	let upscaled = outputs[0].extract_tensor();
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn run_into<'i, 'v: 'i, T: ValueTypeMarker + ?Sized, const N: usize>(
+		&self,
+		input_values: impl Into<SessionInputs<'i, 'v, N>>,
+		outputs: &mut [Value<T>]
+	) -> Result<()> {
+		match input_values.into() {
+			SessionInputs::ValueSlice(input_values) => {
+				self.run_into_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values.iter(), outputs)
+			}
+			SessionInputs::ValueArray(input_values) => {
+				self.run_into_inner(&self.inputs.iter().map(|input| input.name.as_str()).collect::<Vec<_>>(), input_values.iter(), outputs)
+			}
+			SessionInputs::ValueMap(input_values) => {
+				self.run_into_inner(&input_values.iter().map(|(k, _)| k.as_ref()).collect::<Vec<_>>(), input_values.iter().map(|(_, v)| v), outputs)
+			}
+		}
+	}
+
+	fn run_into_inner<'i, 'v: 'i, T: ValueTypeMarker + ?Sized>(
+		&self,
+		input_names: &[&str],
+		input_values: impl Iterator<Item = &'i SessionInputValue<'v>>,
+		outputs: &mut [Value<T>]
+	) -> Result<()> {
+		if outputs.len() != self.outputs.len() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("{} output buffers were provided, but the model has {} outputs.", outputs.len(), self.outputs.len())
+			));
+		}
+		for (output, buffer) in self.outputs.iter().zip(outputs.iter()) {
+			validate_output_buffer(output, buffer.dtype())?;
+		}
+
+		let input_ort_values: Vec<*const ort_sys::OrtValue> = input_values.map(|input_array_ort| input_array_ort.ptr()).collect();
+		if input_ort_values.len() > input_names.len() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("{} inputs were provided, but the model only accepts {}.", input_ort_values.len(), input_names.len())
+			));
+		}
+
+		let input_names: Vec<CString> = input_names
+			.iter()
+			.map(|n| CString::new(n.as_bytes()).unwrap_or_else(|_| unreachable!()))
+			.collect();
+		let input_names_ptr: Vec<*const c_char> = input_names.iter().map(|n| n.as_ptr()).collect();
+		let output_names: Vec<CString> = self
+			.outputs
+			.iter()
+			.map(|o| CString::new(o.name.as_bytes()).unwrap_or_else(|_| unreachable!()))
+			.collect();
+		let output_names_ptr: Vec<*const c_char> = output_names.iter().map(|n| n.as_ptr()).collect();
+		let mut output_tensor_ptrs: Vec<*mut ort_sys::OrtValue> = outputs.iter_mut().map(|v| v.ptr_mut()).collect();
+
+		ortsys![
+			unsafe Run(
+				self.inner.session_ptr.as_ptr(),
+				std::ptr::null(),
+				input_names_ptr.as_ptr(),
+				input_ort_values.as_ptr(),
+				input_ort_values.len(),
+				output_names_ptr.as_ptr(),
+				output_names_ptr.len(),
+				output_tensor_ptrs.as_mut_ptr()
+			)?
+		];
+
+		Ok(())
+	}
+
+	/// Runs the session `n` times with dummy inputs, so that lazily initialized state - like TensorRT engines or CUDA
+	/// kernel selection - is primed before the first real request. Returns the total time spent running them.
+	///
+	/// Inputs are zero-filled tensors shaped according to [`Session::inputs`], with any dynamic dimensions set to `1`.
+	/// Since some execution providers build separate kernels for different input shapes, models with dynamic
+	/// dimensions should additionally be warmed up with representative inputs.
+	///
+	/// Returns an error if any of the model's inputs is not a numeric tensor.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let elapsed = session.warmup(3)?;
+	/// println!("warm-up took {elapsed:?}");
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn warmup(&self, n: usize) -> Result<Duration> {
+		let allocator = Allocator::default();
+		let input_names = self.input_names();
+		let input_values = self
+			.inputs
+			.iter()
+			.map(|input| match &input.input_type {
+				ValueType::Tensor { ty, dimensions, .. } => {
+					let shape: Vec<i64> = dimensions.iter().map(|&d| if d < 0 { 1 } else { d }).collect();
+					zeroed_tensor(&allocator, *ty, shape).map(SessionInputValue::from)
+				}
+				other => Err(Error::new_with_code(
+					ErrorCode::NotImplemented,
+					format!("Cannot create a dummy value for input `{}` of type {other}", input.name)
+				))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let start = Instant::now();
+		for _ in 0..n {
+			self.run_inner::<NoSelectedOutputs>(&input_names, input_values.iter(), None)?;
+		}
+		Ok(start.elapsed())
+	}
+
 	fn run_inner<'i, 'r, 's: 'r, 'v: 'i, O: SelectedOutputMarker>(
 		&'s self,
 		input_names: &[&str],
 		input_values: impl Iterator<Item = &'i SessionInputValue<'v>>,
 		run_options: Option<&'r RunOptions<O>>
 	) -> Result<SessionOutputs<'r, 's>> {
+		let (output_names, output_tensors) = match run_options {
+			Some(r) => r.outputs.resolve_outputs(&self.outputs)?,
+			None => (self.outputs.iter().map(|o| o.name.as_str()).collect(), std::iter::repeat_with(|| None).take(self.outputs.len()).collect())
+		};
+		self.run_inner_with_outputs(input_names, input_values, run_options, output_names, output_tensors, None)
+	}
+
+	/// Runs the graph, computing only the outputs named in `output_names`. `output_tensors` holds a preallocated value
+	/// for each output, or `None` to let ONNX Runtime allocate it.
+	///
+	/// If `timings` is provided, the time spent in each phase of the run is added to it.
+	fn run_inner_with_outputs<'i, 'r, 's: 'r, 'v: 'i, O: SelectedOutputMarker>(
+		&'s self,
+		input_names: &[&str],
+		input_values: impl Iterator<Item = &'i SessionInputValue<'v>>,
+		run_options: Option<&'r RunOptions<O>>,
+		output_names: Vec<&'r str>,
+		mut output_tensors: Vec<Option<DynValue>>,
+		mut timings: Option<&mut RunTimings>
+	) -> Result<SessionOutputs<'r, 's>> {
+		let mut phase_start = Instant::now();
 		let input_names_ptr: Vec<*const c_char> = input_names
 			.iter()
 			.map(|n| CString::new(n.as_bytes()).unwrap_or_else(|_| unreachable!()))
 			.map(|n| n.into_raw().cast_const())
 			.collect();
 
-		let (output_names, mut output_tensors) = match run_options {
-			Some(r) => r.outputs.resolve_outputs(&self.outputs),
-			None => (self.outputs.iter().map(|o| o.name.as_str()).collect(), std::iter::repeat_with(|| None).take(self.outputs.len()).collect())
-		};
 		let output_names_ptr: Vec<*const c_char> = output_names
 			.iter()
 			.map(|n| CString::new(*n).unwrap_or_else(|_| unreachable!()))
@@ -285,8 +865,9 @@ impl Session {
 			})
 			.collect();
 
+		let input_values: Vec<&SessionInputValue<'v>> = input_values.collect();
 		// The C API expects pointers for the arrays (pointers to C-arrays)
-		let input_ort_values: Vec<*const ort_sys::OrtValue> = input_values.map(|input_array_ort| input_array_ort.ptr()).collect();
+		let input_ort_values: Vec<*const ort_sys::OrtValue> = input_values.iter().map(|input_array_ort| input_array_ort.ptr()).collect();
 		if input_ort_values.len() > input_names.len() {
 			// If we provide more inputs than the model expects with `ort::inputs![a, b, c]`, then we get an `input_names` shorter
 			// than `inputs`. ONNX Runtime will attempt to look up the name of all inputs before doing any checks, thus going out of
@@ -300,7 +881,11 @@ impl Session {
 
 		let run_options_ptr = if let Some(run_options) = &run_options { run_options.ptr() } else { std::ptr::null() };
 
-		ortsys![
+		if let Some(timings) = timings.as_deref_mut() {
+			timings.input_binding += phase_start.elapsed();
+			phase_start = Instant::now();
+		}
+		status_to_result(ortsys![
 			unsafe Run(
 				self.inner.session_ptr.as_ptr(),
 				run_options_ptr,
@@ -310,8 +895,16 @@ impl Session {
 				output_names_ptr.as_ptr(),
 				output_names_ptr.len(),
 				output_tensor_ptrs.as_mut_ptr()
-			)?
-		];
+			)
+		])
+		.map_err(|e| match self.diagnose_inputs(&input_names[..input_values.len()], &input_values) {
+			Some((kind, diagnosis)) => Error::new_with_kind(e.code(), kind, format!("{diagnosis}: {}", e.message())),
+			None => e
+		})?;
+		if let Some(timings) = timings.as_deref_mut() {
+			timings.execution += phase_start.elapsed();
+			phase_start = Instant::now();
+		}
 
 		let outputs: Vec<Value> = output_tensors
 			.into_iter()
@@ -332,7 +925,54 @@ impl Session {
 			drop(unsafe { CString::from_raw(p.cast_mut().cast()) });
 		}
 
-		Ok(SessionOutputs::new(output_names, outputs))
+		let outputs = SessionOutputs::new(output_names, outputs);
+		if let Some(timings) = timings {
+			timings.output_extraction += phase_start.elapsed();
+		}
+		Ok(outputs)
+	}
+
+	/// Compares the given inputs against the model's signature, returning a structured description of the first
+	/// incompatibility found, if any. This is used to give more actionable errors when a run fails; the description is
+	/// prepended to ONNX Runtime's own error message.
+	fn diagnose_inputs(&self, input_names: &[&str], input_values: &[&SessionInputValue<'_>]) -> Option<(ErrorKind, String)> {
+		if let Some(missing) = self.inputs.iter().find(|input| !input_names.contains(&input.name.as_str())) {
+			return Some((ErrorKind::MissingInput(missing.name.clone()), format!("Missing required input `{}`", missing.name)));
+		}
+
+		for (name, value) in input_names.iter().zip(input_values) {
+			let Some(input) = self.inputs.iter().find(|input| input.name == *name) else {
+				continue;
+			};
+			match (&input.input_type, value.dtype()) {
+				(ValueType::Tensor { ty: expected_ty, .. }, ValueType::Tensor { ty, .. }) if expected_ty != ty => {}
+				(ValueType::Tensor { dimensions: expected, .. }, ValueType::Tensor { dimensions: got, .. }) => {
+					if expected.len() != got.len() || expected.iter().zip(got).any(|(expected, got)| *expected != -1 && expected != got) {
+						return Some((
+							ErrorKind::ShapeMismatch {
+								input: input.name.clone(),
+								expected: expected.clone(),
+								got: got.clone()
+							},
+							format!("Input `{}` has shape {got:?}, but the model expects shape {expected:?}", input.name)
+						));
+					}
+					continue;
+				}
+				(expected, got) if std::mem::discriminant(expected) == std::mem::discriminant(got) => continue,
+				_ => {}
+			}
+			return Some((
+				ErrorKind::TypeMismatch {
+					input: input.name.clone(),
+					expected: input.input_type.clone(),
+					got: value.dtype().clone()
+				},
+				format!("Input `{}` has type {}, but the model expects type {}", input.name, value.dtype(), input.input_type)
+			));
+		}
+
+		None
 	}
 
 	/// Asynchronously run input data through the ONNX graph, performing inference.
@@ -502,6 +1142,62 @@ impl Session {
 	}
 }
 
+/// Checks that a caller-provided buffer passed to [`Session::run_into`] is compatible with the model output it will be
+/// bound to.
+fn validate_output_buffer(output: &Output, buffer_type: &ValueType) -> Result<()> {
+	let compatible = match (&output.output_type, buffer_type) {
+		(ValueType::Tensor { ty: expected_ty, dimensions: expected_dims, .. }, ValueType::Tensor { ty, dimensions, .. }) => {
+			expected_ty == ty
+				&& expected_dims.len() == dimensions.len()
+				&& expected_dims.iter().zip(dimensions).all(|(expected, actual)| *expected == -1 || expected == actual)
+		}
+		(expected, actual) => expected == actual
+	};
+	if compatible {
+		Ok(())
+	} else {
+		Err(Error::new_with_code(
+			ErrorCode::InvalidArgument,
+			format!("Output buffer for `{}` has type {buffer_type}, which is incompatible with the model's output type {}", output.name, output.output_type)
+		))
+	}
+}
+
+fn zeroed_tensor(allocator: &Allocator, ty: TensorElementType, shape: Vec<i64>) -> Result<DynValue> {
+	macro_rules! zeroed {
+		($t:ty) => {{
+			let mut tensor = Tensor::<$t>::new(allocator, shape)?;
+			tensor.extract_raw_tensor_mut().1.fill(<$t>::default());
+			tensor.into_dyn()
+		}};
+	}
+
+	Ok(match ty {
+		TensorElementType::Float32 => zeroed!(f32),
+		TensorElementType::Float64 => zeroed!(f64),
+		#[cfg(feature = "half")]
+		TensorElementType::Float16 => zeroed!(half::f16),
+		#[cfg(feature = "half")]
+		TensorElementType::Bfloat16 => zeroed!(half::bf16),
+		TensorElementType::Float8E4M3 => zeroed!(crate::tensor::f8e4m3),
+		TensorElementType::Float8E5M2 => zeroed!(crate::tensor::f8e5m2),
+		TensorElementType::Int8 => zeroed!(i8),
+		TensorElementType::Int16 => zeroed!(i16),
+		TensorElementType::Int32 => zeroed!(i32),
+		TensorElementType::Int64 => zeroed!(i64),
+		TensorElementType::Uint8 => zeroed!(u8),
+		TensorElementType::Uint16 => zeroed!(u16),
+		TensorElementType::Uint32 => zeroed!(u32),
+		TensorElementType::Uint64 => zeroed!(u64),
+		TensorElementType::Bool => zeroed!(bool),
+		TensorElementType::Int4 | TensorElementType::Uint4 => {
+			let len = ty.data_size(calculate_tensor_size(&shape)).unwrap_or_default();
+			DynTensor::from_bytes(ty, shape, &vec![0; len])?.into_dyn()
+		}
+		TensorElementType::String => return Err(Error::new_with_code(ErrorCode::NotImplemented, "Cannot create a dummy string tensor"))
+	})
+}
+
 /// Workload type, used to signal to execution providers whether to prioritize performance or efficiency.
 ///
 /// See [`Session::set_workload_type`].
@@ -514,10 +1210,14 @@ pub enum WorkloadType {
 	Efficient
 }
 
+// SAFETY: `OrtSession` is not tied to the thread it was created on; see
 // https://github.com/microsoft/onnxruntime/issues/114
 unsafe impl Send for Session {}
 // Allowing `Sync` segfaults with CUDA, DirectML, and seemingly any EP other than the CPU EP. I'm not certain if it's a
 // temporary bug in ONNX Runtime or a wontfix. Maybe this impl should be removed just to be safe?
+// Note that ONNX Runtime itself guarantees that `Run` may be called concurrently on the same session, and all per-run
+// state (input & output names, values, and `RunOptions`) is created by or passed to each individual call to
+// `Session::run` & co., so concurrent runs never share mutable state on our side.
 unsafe impl Sync for Session {}
 
 impl AsPointer for Session {
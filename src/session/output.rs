@@ -3,7 +3,8 @@ use std::{
 	iter::FusedIterator,
 	mem::ManuallyDrop,
 	ops::{Index, IndexMut},
-	ptr
+	ptr,
+	time::Duration
 };
 
 use crate::{
@@ -154,6 +155,29 @@ impl<'r, 's> SessionOutputs<'r, 's> {
 		}
 	}
 
+	/// Consumes the outputs, returning each remaining output's name & value in the order they were returned by the
+	/// model.
+	///
+	/// This is equivalent to collecting the outputs' [`IntoIterator`] implementation, with the names converted to owned
+	/// `String`s so they can outlive the session's output names.
+	///
+	/// ```
+	/// # use ort::session::Session;
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// let input = ndarray::Array4::<f32>::zeros((1, 64, 64, 3));
+	/// let outputs = session.run(ort::inputs![input]?)?;
+	///
+	/// for (name, value) in outputs.into_values() {
+	/// 	println!("{name}: {:?}", value.dtype());
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn into_values(self) -> Vec<(String, DynValue)> {
+		self.into_iter().map(|(name, value)| (name.to_string(), value)).collect()
+	}
+
 	pub fn iter_mut(&mut self) -> IterMut<'_, 'r> {
 		IterMut {
 			key_iter: self.keys.iter(),
@@ -293,7 +317,9 @@ impl<'x> Iterator for Values<'x, '_> {
 		loop {
 			match self.key_iter.next() {
 				None => return None,
-				Some(&"") => continue,
+				Some(&"") => {
+					self.value_iter.next();
+				}
 				Some(_) => {
 					self.effective_len -= 1;
 					return self.value_iter.next().map(DynValue::view);
@@ -323,7 +349,9 @@ impl<'x> Iterator for ValuesMut<'x, '_> {
 		loop {
 			match self.key_iter.next() {
 				None => return None,
-				Some(&"") => continue,
+				Some(&"") => {
+					self.value_iter.next();
+				}
 				Some(_) => {
 					self.effective_len -= 1;
 					return self.value_iter.next().map(DynValue::view_mut);
@@ -353,7 +381,9 @@ impl<'x, 'k> Iterator for Iter<'x, 'k> {
 		loop {
 			match self.key_iter.next() {
 				None => return None,
-				Some(&"") => continue,
+				Some(&"") => {
+					self.value_iter.next();
+				}
 				Some(key) => {
 					self.effective_len -= 1;
 					return self.value_iter.next().map(|v| (*key, v.view()));
@@ -383,7 +413,9 @@ impl<'x, 'k> Iterator for IterMut<'x, 'k> {
 		loop {
 			match self.key_iter.next() {
 				None => return None,
-				Some(&"") => continue,
+				Some(&"") => {
+					self.value_iter.next();
+				}
 				Some(key) => {
 					self.effective_len -= 1;
 					return self.value_iter.next().map(|v| (*key, v.view_mut()));
@@ -414,7 +446,9 @@ impl<'r> Iterator for IntoIter<'r, '_> {
 		loop {
 			match self.keys.next() {
 				None => return None,
-				Some("") => continue,
+				Some("") => {
+					self.values.next();
+				}
 				Some(key) => {
 					self.effective_len -= 1;
 					return self.values.next().map(|v| (key, v));
@@ -435,3 +469,91 @@ impl Drop for IntoIter<'_, '_> {
 		}
 	}
 }
+
+/// A breakdown of the time spent in each phase of a [`Session::run_timed`](crate::session::Session::run_timed) call.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RunTimings {
+	/// Time spent converting the inputs and preparing the input & output names and pointers for ONNX Runtime.
+	pub input_binding: Duration,
+	/// Time spent inside ONNX Runtime's `Run` call, i.e. actually executing the graph.
+	pub execution: Duration,
+	/// Time spent wrapping the values returned by ONNX Runtime into [`SessionOutputs`].
+	pub output_extraction: Duration
+}
+
+impl RunTimings {
+	/// Returns the total time spent across all phases.
+	#[must_use]
+	pub fn total(&self) -> Duration {
+		self.input_binding + self.execution + self.output_extraction
+	}
+}
+
+/// Latency statistics collected by [`Session::benchmark`](crate::session::Session::benchmark), in nanoseconds.
+///
+/// Percentiles (including the median) use the nearest-rank method, so they are always one of the measured latencies.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BenchmarkStats {
+	pub mean_ns: u64,
+	pub median_ns: u64,
+	pub p95_ns: u64,
+	pub p99_ns: u64,
+	pub min_ns: u64,
+	pub max_ns: u64
+}
+
+impl BenchmarkStats {
+	/// Computes statistics from a non-empty list of latencies.
+	pub(crate) fn from_latencies(mut latencies: Vec<u64>) -> Self {
+		latencies.sort_unstable();
+		let percentile = |p: usize| latencies[((p * latencies.len() + 99) / 100).saturating_sub(1)];
+		BenchmarkStats {
+			mean_ns: (latencies.iter().map(|x| u128::from(*x)).sum::<u128>() / latencies.len() as u128) as u64,
+			median_ns: percentile(50),
+			p95_ns: percentile(95),
+			p99_ns: percentile(99),
+			min_ns: latencies[0],
+			max_ns: latencies[latencies.len() - 1]
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{BenchmarkStats, SessionOutputs};
+	use crate::value::Tensor;
+
+	#[test]
+	fn test_outputs_iteration() -> crate::Result<()> {
+		let values = (0..3).map(|i| Tensor::from_array(([1_usize], vec![i as f32])).map(|t| t.into_dyn())).collect::<crate::Result<Vec<_>>>()?;
+		let mut outputs = SessionOutputs::new(vec!["a", "b", "c"], values);
+		assert!(outputs.remove("b").is_some());
+
+		let names: Vec<&str> = outputs.keys().collect();
+		assert_eq!(names, ["a", "c"]);
+		let first: Vec<f32> = outputs.values().map(|v| v.try_extract_raw_tensor::<f32>().unwrap().1[0]).collect();
+		assert_eq!(first, [0.0, 2.0]);
+
+		let values = outputs.into_values();
+		assert_eq!(values.len(), 2);
+		assert_eq!(values[1].0, "c");
+		assert_eq!(values[1].1.try_extract_raw_tensor::<f32>()?.1, [2.0]);
+		Ok(())
+	}
+
+	#[test]
+	fn test_benchmark_stats() {
+		let stats = BenchmarkStats::from_latencies((1..=100).rev().collect());
+		assert_eq!(stats, BenchmarkStats {
+			mean_ns: 50,
+			median_ns: 50,
+			p95_ns: 95,
+			p99_ns: 99,
+			min_ns: 1,
+			max_ns: 100
+		});
+
+		let stats = BenchmarkStats::from_latencies(vec![7]);
+		assert_eq!((stats.median_ns, stats.p99_ns, stats.min_ns, stats.max_ns), (7, 7, 7, 7));
+	}
+}
@@ -0,0 +1,145 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::{
+	error::{Error, ErrorCode, Result},
+	session::{Session, SessionInputValue, SessionInputs},
+	value::DynValue
+};
+
+/// Runs several [`Session`]s in sequence, feeding outputs of earlier stages into inputs of later ones (e.g.
+/// preprocessing → encoder → decoder).
+///
+/// Stages run in the order they were added. Each input of a stage is either [linked](SessionPipeline::with_link) to an
+/// output of an earlier stage, or provided by the caller of [`SessionPipeline::run`] by name. Linked values are handed to
+/// the next session as-is, without being copied or moved between devices, so outputs which an execution provider leaves
+/// in device memory stay there between stages.
+///
+/// ```no_run
+/// # use ort::session::{Session, SessionPipeline};
+/// # fn main() -> ort::Result<()> {
+/// let pipeline = SessionPipeline::new()
+/// 	.with_stage("encoder", Session::builder()?.commit_from_file("encoder.onnx")?)?
+/// 	.with_stage("decoder", Session::builder()?.commit_from_file("decoder.onnx")?)?
+/// 	.with_link("encoder", "last_hidden_state", "decoder", "encoder_hidden_states")?;
+///
+/// let input_ids = ort::value::Tensor::from_array(([1, 4], vec![101_i64, 7592, 2088, 102]))?;
+/// let outputs = pipeline.run([("input_ids", input_ids.into_dyn())])?;
+/// let logits = &outputs["logits"];
+/// # 	Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct SessionPipeline {
+	stages: Vec<PipelineStage>
+}
+
+#[derive(Debug)]
+struct PipelineStage {
+	name: String,
+	session: Session,
+	/// Maps input names of this stage to the index of the stage & the name of the output which feeds them.
+	links: HashMap<String, (usize, String)>
+}
+
+impl SessionPipeline {
+	/// Creates an empty pipeline.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Appends a stage which runs `session`. Stage names are used to refer to the stage in
+	/// [`SessionPipeline::with_link`] and [`SessionPipeline::stage`].
+	///
+	/// Returns an error if the pipeline already has a stage named `name`.
+	pub fn with_stage(mut self, name: impl Into<String>, session: Session) -> Result<Self> {
+		let name = name.into();
+		if self.stages.iter().any(|stage| stage.name == name) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Pipeline already has a stage named `{name}`")));
+		}
+		self.stages.push(PipelineStage {
+			name,
+			session,
+			links: HashMap::new()
+		});
+		Ok(self)
+	}
+
+	/// Feeds the output named `output` of stage `from` into the input named `input` of stage `to`.
+	///
+	/// Returns an error if either stage, the output, or the input does not exist, or if `from` does not run before `to`.
+	pub fn with_link(mut self, from: impl AsRef<str>, output: impl Into<String>, to: impl AsRef<str>, input: impl Into<String>) -> Result<Self> {
+		let (from, to, output, input) = (from.as_ref(), to.as_ref(), output.into(), input.into());
+		let from_idx = self.stage_index(from)?;
+		let to_idx = self.stage_index(to)?;
+		if from_idx >= to_idx {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Pipeline stage `{from}` must run before stage `{to}` to feed its inputs")));
+		}
+		if !self.stages[from_idx].session.outputs.iter().any(|o| o.name == output) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Pipeline stage `{from}` has no output named `{output}`")));
+		}
+		if !self.stages[to_idx].session.inputs.iter().any(|i| i.name == input) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Pipeline stage `{to}` has no input named `{input}`")));
+		}
+
+		self.stages[to_idx].links.insert(input, (from_idx, output));
+		Ok(self)
+	}
+
+	/// Returns the session of the stage named `name`, if it exists.
+	pub fn stage(&self, name: &str) -> Option<&Session> {
+		self.stages.iter().find(|stage| stage.name == name).map(|stage| &stage.session)
+	}
+
+	fn stage_index(&self, name: &str) -> Result<usize> {
+		self.stages
+			.iter()
+			.position(|stage| stage.name == name)
+			.ok_or_else(|| Error::new_with_code(ErrorCode::InvalidArgument, format!("No pipeline stage named `{name}`")))
+	}
+
+	/// Runs every stage in order, returning the outputs of the last stage by name.
+	///
+	/// `inputs` provides values for the inputs of any stage which are not linked to an earlier stage's output, matched
+	/// by input name; if several stages have an unlinked input of the same name, they all receive the same value.
+	/// Outputs of intermediate stages are dropped as soon as the pipeline finishes, unless they are returned by the last
+	/// stage.
+	pub fn run<K: Into<String>>(&self, inputs: impl IntoIterator<Item = (K, DynValue)>) -> Result<HashMap<String, DynValue>> {
+		let Some(last) = self.stages.len().checked_sub(1) else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot run a pipeline with no stages"));
+		};
+
+		let inputs: HashMap<String, DynValue> = inputs.into_iter().map(|(k, v)| (k.into(), v)).collect();
+		// outputs of earlier stages which feed later stages, keyed by stage index & output name
+		let mut intermediates: HashMap<(usize, String), DynValue> = HashMap::new();
+		for (i, stage) in self.stages.iter().enumerate() {
+			let outputs: Vec<(String, DynValue)> = {
+				let mut stage_inputs: Vec<(Cow<'_, str>, SessionInputValue<'_>)> = Vec::with_capacity(stage.session.inputs.len());
+				for input in &stage.session.inputs {
+					let value = match stage.links.get(&input.name) {
+						Some((from, output)) => intermediates.get(&(*from, output.clone())).ok_or_else(|| {
+							Error::new(format!("Pipeline stage `{}` did not produce output `{output}` for stage `{}`", self.stages[*from].name, stage.name))
+						})?,
+						// leave missing inputs for the session to report
+						None => match inputs.get(&input.name) {
+							Some(value) => value,
+							None => continue
+						}
+					};
+					stage_inputs.push((Cow::Borrowed(input.name.as_str()), SessionInputValue::View(value.view())));
+				}
+				let stage_inputs: SessionInputs<'_, '_> = SessionInputs::ValueMap(stage_inputs);
+				stage.session.run(stage_inputs)?.into_values()
+			};
+
+			for (name, value) in outputs {
+				// only keep outputs which are needed later
+				let needed = i == last || self.stages[i + 1..].iter().any(|later| later.links.values().any(|(from, output)| *from == i && *output == name));
+				if needed {
+					intermediates.insert((i, name), value);
+				}
+			}
+		}
+
+		Ok(intermediates.into_iter().filter(|((stage, _), _)| *stage == last).map(|((_, name), value)| (name, value)).collect())
+	}
+}
@@ -9,7 +9,7 @@ use std::{
 use crate::{
 	AsPointer,
 	adapter::{Adapter, AdapterInner},
-	error::Result,
+	error::{Error, ErrorCode, Result},
 	ortsys,
 	session::Output,
 	value::{DynValue, Value, ValueTypeMarker}
@@ -73,6 +73,22 @@ impl OutputSelector {
 	}
 
 	/// Mark the output specified by the `name` for inclusion.
+	///
+	/// Combined with [`OutputSelector::no_default`], this can be used to only compute the outputs you need, e.g. only
+	/// the `probs` output of a multi-head model; ONNX Runtime may then skip the nodes that only contribute to the other
+	/// outputs. Running a session with an output name which does not exist in the model returns an error.
+	///
+	/// ```no_run
+	/// # use ort::session::{Session, run_options::{OutputSelector, RunOptions}};
+	/// # fn main() -> ort::Result<()> {
+	/// # let session = Session::builder()?.commit_from_file("model.onnx")?;
+	/// # let input = ndarray::Array2::<i64>::zeros((1, 16));
+	/// let options = RunOptions::new()?.with_outputs(OutputSelector::no_default().with("probs"));
+	/// let outputs = session.run_with_options(ort::inputs![input]?, &options)?;
+	/// assert_eq!(outputs.len(), 1);
+	/// # 	Ok(())
+	/// # }
+	/// ```
 	pub fn with(mut self, name: impl Into<String>) -> Self {
 		self.allowlist.push(name.into());
 		self
@@ -117,16 +133,31 @@ impl OutputSelector {
 		self
 	}
 
-	pub(crate) fn resolve_outputs<'a, 's: 'a>(&'a self, outputs: &'s [Output]) -> (Vec<&'a str>, Vec<Option<DynValue>>) {
-		if self.use_defaults { outputs.iter() } else { [].iter() }
+	pub(crate) fn resolve_outputs<'a, 's: 'a>(&'a self, outputs: &'s [Output]) -> Result<(Vec<&'a str>, Vec<Option<DynValue>>)> {
+		if let Some(name) = self.allowlist.iter().find(|n| !outputs.iter().any(|o| o.name == **n)) {
+			return Err(unknown_output(name, outputs));
+		}
+
+		Ok(if self.use_defaults { outputs.iter() } else { [].iter() }
 			.map(|o| &o.name)
 			.filter(|n| !self.default_blocklist.contains(n))
 			.chain(self.allowlist.iter())
 			.map(|n| (n.as_str(), self.preallocated_outputs.get(n).map(DynValue::clone_of)))
-			.unzip()
+			.unzip())
 	}
 }
 
+/// Creates the error returned when an output that is not in `outputs` is requested.
+pub(crate) fn unknown_output(name: &str, outputs: &[Output]) -> Error {
+	Error::new_with_code(
+		ErrorCode::InvalidArgument,
+		format!(
+			"Requested output `{name}` does not exist; available outputs are: {}",
+			outputs.iter().map(|o| format!("`{}`", o.name)).collect::<Vec<_>>().join(", ")
+		)
+	)
+}
+
 /// Types that specify whether a [`RunOptions`] was configured with an [`OutputSelector`].
 pub trait SelectedOutputMarker {}
 /// Marks that a [`RunOptions`] was not configured with an [`OutputSelector`].
@@ -0,0 +1,271 @@
+use std::fmt::Debug;
+
+use super::PrimitiveTensorElementType;
+use crate::{
+	error::{Error, ErrorCode, Result},
+	value::{Tensor, TensorRef}
+};
+
+/// A growable buffer which appends elements along one axis of a tensor, for building up a tensor over many steps (for
+/// example, the token sequence in a text generation loop) without reallocating the whole tensor on every step as
+/// `ndarray::concatenate` would.
+///
+/// Elements are stored with the growing axis outermost, so appending is amortized `O(1)` per element like
+/// [`Vec::extend_from_slice`]. When all dimensions before the growing axis are `1` (e.g. a `[1, sequence_length]`
+/// token tensor), this is also the tensor's natural layout, and [`TensorBuilder::view`] can create a tensor from the
+/// buffer without copying it.
+///
+/// ```
+/// # use ort::tensor::TensorBuilder;
+/// # fn main() -> ort::Result<()> {
+/// // a `[1, sequence_length]` tensor of token IDs
+/// let mut tokens = TensorBuilder::<i64>::new([1, 0], 1)?;
+/// tokens.extend_from_slice(&[464, 5044, 286])?;
+/// for token in [1204, 318] {
+/// 	{
+/// 		let input = tokens.view()?;
+/// 		assert_eq!(input.extract_raw_tensor().0, [1, tokens.len() as i64]);
+/// 		// ...run the model with `input`...
+/// 	}
+/// 	tokens.push(&[token])?;
+/// }
+/// assert_eq!(tokens.into_tensor()?.extract_raw_tensor().1, [464, 5044, 286, 1204, 318]);
+/// # 	Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TensorBuilder<T: PrimitiveTensorElementType + Debug + Clone + 'static> {
+	data: Vec<T>,
+	shape: Vec<i64>,
+	axis: usize,
+	/// The number of elements in one slice along `axis`.
+	slice_len: usize
+}
+
+impl<T: PrimitiveTensorElementType + Debug + Clone + 'static> TensorBuilder<T> {
+	/// Creates an empty builder for a tensor of the given `shape`, which grows along `axis`.
+	///
+	/// The dimension of `shape` at `axis` is ignored (and conventionally `0`), since the builder starts out empty; all
+	/// other dimensions must be at least `1`.
+	pub fn new(shape: impl AsRef<[i64]>, axis: usize) -> Result<Self> {
+		Self::with_capacity(shape, axis, 0)
+	}
+
+	/// Creates an empty builder like [`TensorBuilder::new`], with space for `capacity` slices along `axis` before
+	/// reallocating.
+	pub fn with_capacity(shape: impl AsRef<[i64]>, axis: usize, capacity: usize) -> Result<Self> {
+		let mut shape = shape.as_ref().to_vec();
+		if axis >= shape.len() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Axis {axis} is out of bounds for shape {shape:?}")));
+		}
+		shape[axis] = 0;
+		if let Some((i, dim)) = shape.iter().enumerate().find(|(i, dim)| *i != axis && **dim < 1) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Invalid dimension {dim} at {i}; all dimensions besides the growing axis must be >= 1")));
+		}
+
+		let slice_len = shape.iter().enumerate().filter(|(i, _)| *i != axis).map(|(_, dim)| *dim as usize).product();
+		Ok(Self {
+			data: Vec::with_capacity(capacity * slice_len),
+			shape,
+			axis,
+			slice_len
+		})
+	}
+
+	/// Returns the current length of the tensor along the growing axis.
+	pub fn len(&self) -> usize {
+		self.shape[self.axis] as usize
+	}
+
+	/// Returns `true` if nothing has been appended to the builder yet.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Returns the current shape of the tensor.
+	pub fn shape(&self) -> &[i64] {
+		&self.shape
+	}
+
+	/// Reserves space for at least `additional` more slices along the growing axis.
+	pub fn reserve(&mut self, additional: usize) {
+		self.data.reserve(additional * self.slice_len);
+	}
+
+	/// Appends a single slice along the growing axis. `slice` holds the elements of a tensor with the builder's shape
+	/// *without* the growing axis, in row-major order.
+	///
+	/// ```
+	/// # use ort::tensor::TensorBuilder;
+	/// # fn main() -> ort::Result<()> {
+	/// // grow a `[2, n]` tensor column by column
+	/// let mut builder = TensorBuilder::<i64>::new([2, 0], 1)?;
+	/// builder.push(&[1, 2])?;
+	/// builder.push(&[3, 4])?;
+	/// assert_eq!(builder.to_tensor()?.extract_raw_tensor().1, [1, 3, 2, 4]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn push(&mut self, slice: &[T]) -> Result<()> {
+		if slice.len() != self.slice_len {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Expected a slice of {} elements to append to tensor of shape {:?}; got {}", self.slice_len, self.shape, slice.len())
+			));
+		}
+		self.data.extend_from_slice(slice);
+		self.shape[self.axis] += 1;
+		Ok(())
+	}
+
+	/// Appends any number of consecutive slices along the growing axis; see [`TensorBuilder::push`]. The length of
+	/// `data` must be a multiple of the number of elements in one slice.
+	pub fn extend_from_slice(&mut self, data: &[T]) -> Result<()> {
+		if data.len() % self.slice_len != 0 {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Expected a multiple of {} elements to append to tensor of shape {:?}; got {}", self.slice_len, self.shape, data.len())
+			));
+		}
+		self.data.extend_from_slice(data);
+		self.shape[self.axis] += (data.len() / self.slice_len) as i64;
+		Ok(())
+	}
+
+	/// Appends an array along the growing axis. The array must have the same shape as the builder, except along the
+	/// growing axis, where it may have any length.
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn append<D: ndarray::Dimension>(&mut self, array: ndarray::ArrayView<'_, T, D>) -> Result<()> {
+		let array = array.into_dyn();
+		let matches = array.ndim() == self.shape.len()
+			&& array
+				.shape()
+				.iter()
+				.zip(&self.shape)
+				.enumerate()
+				.all(|(i, (a, b))| i == self.axis || *a as i64 == *b);
+		if !matches {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot append array of shape {:?} to tensor of shape {:?} along axis {}", array.shape(), self.shape, self.axis)
+			));
+		}
+
+		let rows = array.shape()[self.axis];
+		// move the growing axis to the front to match our storage order
+		let mut order: Vec<usize> = (0..array.ndim()).collect();
+		order.remove(self.axis);
+		order.insert(0, self.axis);
+		self.data.extend(array.permuted_axes(order).iter().cloned());
+		self.shape[self.axis] += rows as i64;
+		Ok(())
+	}
+
+	/// Returns `true` if the buffer is laid out in row-major order, i.e. all dimensions before the growing axis are `1`.
+	fn is_contiguous(&self) -> bool {
+		self.shape[..self.axis].iter().all(|dim| *dim == 1)
+	}
+
+	/// Rearranges the buffer into row-major order.
+	fn to_row_major(&self) -> Vec<T> {
+		if self.is_contiguous() {
+			return self.data.clone();
+		}
+
+		let outer: usize = self.shape[..self.axis].iter().map(|dim| *dim as usize).product();
+		let inner = self.slice_len / outer;
+		let mut data = Vec::with_capacity(self.data.len());
+		for o in 0..outer {
+			for row in 0..self.len() {
+				let start = (row * outer + o) * inner;
+				data.extend_from_slice(&self.data[start..start + inner]);
+			}
+		}
+		data
+	}
+
+	/// Creates a tensor which borrows the builder's buffer, without copying it.
+	///
+	/// This is only possible when all dimensions before the growing axis are `1`, otherwise an error is returned; use
+	/// [`TensorBuilder::to_tensor`] instead in that case. The builder must not be empty. Like any tensor created with
+	/// [`Tensor::from_shape_slice`], the returned view cannot be upgraded to an owned [`Tensor`].
+	pub fn view(&self) -> Result<TensorRef<'_, T>> {
+		if !self.is_contiguous() {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot view tensor of shape {:?} growing along axis {} without copying", self.shape, self.axis)
+			));
+		}
+		Tensor::from_shape_slice(self.shape.as_slice(), &self.data)
+	}
+
+	/// Copies the builder's contents into a new tensor. The builder must not be empty.
+	pub fn to_tensor(&self) -> Result<Tensor<T>> {
+		Tensor::from_array((self.shape.clone(), self.to_row_major()))
+	}
+
+	/// Converts the builder into a tensor, reusing its buffer if possible. The builder must not be empty.
+	pub fn into_tensor(self) -> Result<Tensor<T>> {
+		if self.is_contiguous() {
+			Tensor::from_array((self.shape, self.data))
+		} else {
+			self.to_tensor()
+		}
+	}
+
+	/// Converts the builder into an [`ndarray::ArrayD`].
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn into_array(self) -> ndarray::ArrayD<T> {
+		let shape: Vec<usize> = self.shape.iter().map(|dim| *dim as usize).collect();
+		let data = if self.is_contiguous() { self.data } else { self.to_row_major() };
+		ndarray::ArrayD::from_shape_vec(shape, data).expect("buffer should match shape")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::TensorBuilder;
+
+	#[test]
+	fn test_token_stream() -> crate::Result<()> {
+		let mut tokens = TensorBuilder::<i64>::with_capacity([1, 0], 1, 8)?;
+		assert!(tokens.is_empty());
+		for token in 0..5 {
+			tokens.push(&[token])?;
+			let view = tokens.view()?;
+			assert_eq!(view.extract_raw_tensor().0, [1, token + 1]);
+		}
+		assert!(tokens.push(&[1, 2]).is_err());
+		assert_eq!(tokens.into_tensor()?.extract_raw_tensor().1, [0, 1, 2, 3, 4]);
+		Ok(())
+	}
+
+	#[test]
+	fn test_non_leading_axis() -> crate::Result<()> {
+		// [2, n, 2]
+		let mut builder = TensorBuilder::<i32>::new([2, 0, 2], 1)?;
+		builder.push(&[1, 2, 3, 4])?;
+		builder.extend_from_slice(&[5, 6, 7, 8, 9, 10, 11, 12])?;
+		assert_eq!(builder.shape(), [2, 3, 2]);
+		assert!(builder.view().is_err());
+
+		let tensor = builder.to_tensor()?;
+		assert_eq!(tensor.extract_raw_tensor().1, [1, 2, 5, 6, 9, 10, 3, 4, 7, 8, 11, 12]);
+		#[cfg(feature = "ndarray")]
+		assert_eq!(builder.into_array(), tensor.extract_tensor().to_owned());
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "ndarray")]
+	fn test_append_array() -> crate::Result<()> {
+		let mut builder = TensorBuilder::<f32>::new([2, 0], 1)?;
+		builder.append(ndarray::array![[1.0, 2.0], [3.0, 4.0]].view())?;
+		builder.append(ndarray::array![[5.0], [6.0]].view())?;
+		assert!(builder.append(ndarray::array![[5.0]].view()).is_err());
+		assert_eq!(builder.into_array(), ndarray::array![[1.0, 2.0, 5.0], [3.0, 4.0, 6.0]].into_dyn());
+		Ok(())
+	}
+}
@@ -0,0 +1,271 @@
+//! 8-bit floating point types, as introduced in ONNX opset 19 for FP8 quantization.
+
+#![allow(non_camel_case_types)]
+
+use std::fmt;
+
+/// Converts the bits of a finite 8-bit float with `mantissa_bits` mantissa bits & the given exponent `bias` to `f32`.
+fn f8_to_f32(bits: u8, mantissa_bits: u32, bias: i32) -> f32 {
+	let sign = if bits & 0x80 != 0 { -1.0 } else { 1.0 };
+	let exponent = i32::from((bits & 0x7f) >> mantissa_bits);
+	let mantissa = f32::from(bits & ((1 << mantissa_bits) - 1)) / (1 << mantissa_bits) as f32;
+	if exponent == 0 {
+		// subnormal
+		sign * mantissa * 2.0_f32.powi(1 - bias)
+	} else {
+		sign * (1.0 + mantissa) * 2.0_f32.powi(exponent - bias)
+	}
+}
+
+/// Rounds a finite `x` to the nearest 8-bit float with `mantissa_bits` mantissa bits & the given exponent `bias`,
+/// rounding ties to even. Values too large to be represented saturate to `max`, the bits of the largest finite value.
+fn f32_to_f8(x: f32, mantissa_bits: u32, bias: i32, max: u8) -> u8 {
+	let bits = x.to_bits();
+	let sign = ((bits >> 24) & 0x80) as u8;
+	let exponent = ((bits >> 23) & 0xff) as i32 - 127;
+	if exponent == -127 {
+		// zero, or an `f32` subnormal, which is far too small to be represented
+		return sign;
+	}
+
+	// `x` is `significand * 2^(exponent - 23)`
+	let significand = (bits & 0x7f_ffff) | 0x80_0000;
+	let min_exponent = 1 - bias;
+	// drop the low bits which don't fit in the mantissa, plus however many more are needed to denormalize `x` if it is
+	// smaller than the smallest normal value
+	let shift = (23 - mantissa_bits as i32 + (min_exponent - exponent).max(0)) as u32;
+	if shift > 24 {
+		// less than half of the smallest subnormal value
+		return sign;
+	}
+	let mut mantissa = significand >> shift;
+	let remainder = significand & ((1 << shift) - 1);
+	let half = 1 << (shift - 1);
+	if remainder > half || (remainder == half && mantissa & 1 == 1) {
+		mantissa += 1;
+	}
+
+	// `mantissa` includes the implicit leading bit for normal values, which is accounted for by the `- 1` here; for
+	// subnormal values, the exponent field is `0`. Overflow from rounding carries into the exponent.
+	let encoded = (((exponent.max(min_exponent) + bias - 1) as u32) << mantissa_bits) + mantissa;
+	sign | encoded.min(u32::from(max)) as u8
+}
+
+/// An 8-bit floating point number with 4 exponent bits and 3 mantissa bits, corresponding to ONNX's `FLOAT8E4M3FN`
+/// element type ([`TensorElementType::Float8E4M3`](crate::tensor::TensorElementType::Float8E4M3)).
+///
+/// This format has no infinities; its largest finite value is `448`. Values are stored as their raw bits, so tensors of
+/// `f8e4m3` can be passed to & extracted from ONNX Runtime without conversion; use [`f8e4m3::to_f32`] &
+/// [`f8e4m3::from_f32`] to convert to & from `f32`.
+///
+/// ```
+/// # use ort::tensor::f8e4m3;
+/// assert_eq!(f8e4m3::from_f32(1.0).to_bits(), 0x38);
+/// assert_eq!(f8e4m3::from_f32(0.3).to_f32(), 0.3125);
+/// // out of range values saturate
+/// assert_eq!(f8e4m3::from_f32(1000.0), f8e4m3::MAX);
+/// ```
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct f8e4m3(u8);
+
+impl f8e4m3 {
+	/// The largest finite value, `448`.
+	pub const MAX: Self = Self(0x7e);
+	/// The smallest finite value, `-448`.
+	pub const MIN: Self = Self(0xfe);
+	/// Not a number.
+	pub const NAN: Self = Self(0x7f);
+
+	/// Creates a value from its raw bits.
+	pub const fn from_bits(bits: u8) -> Self {
+		Self(bits)
+	}
+
+	/// Returns the raw bits of this value.
+	pub const fn to_bits(self) -> u8 {
+		self.0
+	}
+
+	/// Returns `true` if this value is NaN.
+	pub const fn is_nan(self) -> bool {
+		self.0 & 0x7f == 0x7f
+	}
+
+	/// Converts an `f32` to the nearest `f8e4m3`, rounding ties to even. Values outside of the representable range
+	/// (including infinities) saturate to [`f8e4m3::MAX`] or [`f8e4m3::MIN`], matching ONNX's `Cast` operator with
+	/// `saturate=1`.
+	pub fn from_f32(x: f32) -> Self {
+		if x.is_nan() {
+			Self::NAN
+		} else if x.is_infinite() {
+			if x > 0.0 { Self::MAX } else { Self::MIN }
+		} else {
+			Self(f32_to_f8(x, 3, 7, Self::MAX.0))
+		}
+	}
+
+	/// Converts this value to `f32`. This conversion is exact.
+	pub fn to_f32(self) -> f32 {
+		if self.is_nan() { f32::NAN } else { f8_to_f32(self.0, 3, 7) }
+	}
+}
+
+impl From<f8e4m3> for f32 {
+	fn from(value: f8e4m3) -> Self {
+		value.to_f32()
+	}
+}
+
+impl fmt::Debug for f8e4m3 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(&self.to_f32(), f)
+	}
+}
+
+impl fmt::Display for f8e4m3 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(&self.to_f32(), f)
+	}
+}
+
+/// An 8-bit floating point number with 5 exponent bits and 2 mantissa bits, corresponding to ONNX's `FLOAT8E5M2`
+/// element type ([`TensorElementType::Float8E5M2`](crate::tensor::TensorElementType::Float8E5M2)).
+///
+/// Like IEEE floats, this format has infinities; its largest finite value is `57344`. Values are stored as their raw
+/// bits, so tensors of `f8e5m2` can be passed to & extracted from ONNX Runtime without conversion; use
+/// [`f8e5m2::to_f32`] & [`f8e5m2::from_f32`] to convert to & from `f32`.
+///
+/// ```
+/// # use ort::tensor::f8e5m2;
+/// assert_eq!(f8e5m2::from_f32(1.0).to_bits(), 0x3c);
+/// assert_eq!(f8e5m2::from_f32(0.3).to_f32(), 0.3125);
+/// assert_eq!(f8e5m2::from_f32(f32::INFINITY), f8e5m2::INFINITY);
+/// ```
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct f8e5m2(u8);
+
+impl f8e5m2 {
+	/// The largest finite value, `57344`.
+	pub const MAX: Self = Self(0x7b);
+	/// The smallest finite value, `-57344`.
+	pub const MIN: Self = Self(0xfb);
+	/// Positive infinity.
+	pub const INFINITY: Self = Self(0x7c);
+	/// Negative infinity.
+	pub const NEG_INFINITY: Self = Self(0xfc);
+	/// Not a number.
+	pub const NAN: Self = Self(0x7f);
+
+	/// Creates a value from its raw bits.
+	pub const fn from_bits(bits: u8) -> Self {
+		Self(bits)
+	}
+
+	/// Returns the raw bits of this value.
+	pub const fn to_bits(self) -> u8 {
+		self.0
+	}
+
+	/// Returns `true` if this value is NaN.
+	pub const fn is_nan(self) -> bool {
+		self.0 & 0x7f > 0x7c
+	}
+
+	/// Returns `true` if this value is positive or negative infinity.
+	pub const fn is_infinite(self) -> bool {
+		self.0 & 0x7f == 0x7c
+	}
+
+	/// Converts an `f32` to the nearest `f8e5m2`, rounding ties to even. Finite values outside of the representable
+	/// range saturate to [`f8e5m2::MAX`] or [`f8e5m2::MIN`], matching ONNX's `Cast` operator with `saturate=1`;
+	/// infinities are preserved.
+	pub fn from_f32(x: f32) -> Self {
+		if x.is_nan() {
+			Self::NAN
+		} else if x.is_infinite() {
+			if x > 0.0 { Self::INFINITY } else { Self::NEG_INFINITY }
+		} else {
+			Self(f32_to_f8(x, 2, 15, Self::MAX.0))
+		}
+	}
+
+	/// Converts this value to `f32`. This conversion is exact.
+	pub fn to_f32(self) -> f32 {
+		if self.is_nan() {
+			f32::NAN
+		} else if self.is_infinite() {
+			if self.0 & 0x80 != 0 { f32::NEG_INFINITY } else { f32::INFINITY }
+		} else {
+			f8_to_f32(self.0, 2, 15)
+		}
+	}
+}
+
+impl From<f8e5m2> for f32 {
+	fn from(value: f8e5m2) -> Self {
+		value.to_f32()
+	}
+}
+
+impl fmt::Debug for f8e5m2 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(&self.to_f32(), f)
+	}
+}
+
+impl fmt::Display for f8e5m2 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(&self.to_f32(), f)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{f8e4m3, f8e5m2};
+	use crate::{tensor::TensorElementType, value::Tensor};
+
+	#[test]
+	fn test_f8e4m3() {
+		for bits in 0..=u8::MAX {
+			let x = f8e4m3::from_bits(bits);
+			if !x.is_nan() {
+				assert_eq!(f8e4m3::from_f32(x.to_f32()), x, "{bits:#04x} did not round-trip");
+			}
+		}
+		assert_eq!(f8e4m3::MAX.to_f32(), 448.0);
+		// smallest subnormal
+		assert_eq!(f8e4m3::from_bits(0x01).to_f32(), 2.0_f32.powi(-9));
+		assert_eq!(f8e4m3::from_f32(2.0_f32.powi(-10)).to_bits(), 0x00);
+		assert_eq!(f8e4m3::from_f32(-f32::INFINITY), f8e4m3::MIN);
+		// 1.0625 lies halfway between 1.0 & 1.125; ties round to even
+		assert_eq!(f8e4m3::from_f32(1.0625).to_f32(), 1.0);
+		assert_eq!(f8e4m3::from_f32(1.1875).to_f32(), 1.25);
+		assert!(f8e4m3::from_f32(f32::NAN).to_f32().is_nan());
+	}
+
+	#[test]
+	fn test_f8e5m2() {
+		for bits in 0..=u8::MAX {
+			let x = f8e5m2::from_bits(bits);
+			if !x.is_nan() {
+				assert_eq!(f8e5m2::from_f32(x.to_f32()), x, "{bits:#04x} did not round-trip");
+			}
+		}
+		assert_eq!(f8e5m2::MAX.to_f32(), 57344.0);
+		assert_eq!(f8e5m2::from_bits(0x01).to_f32(), 2.0_f32.powi(-16));
+		assert_eq!(f8e5m2::from_f32(1e6), f8e5m2::MAX);
+		assert_eq!(f8e5m2::NEG_INFINITY.to_f32(), f32::NEG_INFINITY);
+	}
+
+	#[test]
+	fn test_f8_tensor() -> crate::Result<()> {
+		let data: Vec<f8e4m3> = [0.5, -2.0, 448.0].into_iter().map(f8e4m3::from_f32).collect();
+		let tensor = Tensor::from_array(([3_usize], data.clone()))?;
+		assert_eq!(tensor.dtype().tensor_type(), Some(TensorElementType::Float8E4M3));
+		assert_eq!(tensor.try_extract_raw_tensor::<f8e4m3>()?.1, data);
+		assert!(tensor.try_extract_raw_tensor::<f8e5m2>().is_err());
+		Ok(())
+	}
+}
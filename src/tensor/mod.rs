@@ -1,11 +1,19 @@
 //! Traits related to [`Tensor`](crate::value::Tensor)s.
 
+mod builder;
+mod float8;
 #[cfg(feature = "ndarray")]
 mod ndarray;
+mod quantize;
 mod types;
 
 #[cfg(feature = "ndarray")]
 pub use self::ndarray::ArrayExtensions;
-pub use self::types::{IntoTensorElementType, PrimitiveTensorElementType, TensorElementType, Utf8Data};
+pub use self::{
+	builder::TensorBuilder,
+	float8::{f8e4m3, f8e5m2},
+	quantize::{QuantizedElementType, dequantize_to_f32, quantize_f32},
+	types::{IntoTensorElementType, PrimitiveTensorElementType, TensorElementType, Utf8Data}
+};
 #[cfg(feature = "ndarray")]
 pub(crate) use self::types::{extract_primitive_array, extract_primitive_array_mut};
@@ -0,0 +1,126 @@
+use std::fmt::Debug;
+
+use super::PrimitiveTensorElementType;
+use crate::{
+	error::Result,
+	value::{IntoValueTensor, Tensor}
+};
+
+/// Element types which can be used for linearly quantized tensors, i.e. `i8` & `u8`.
+pub trait QuantizedElementType: PrimitiveTensorElementType + Copy + Debug + 'static {
+	#[doc(hidden)]
+	fn saturate_from(x: f32) -> Self;
+	#[doc(hidden)]
+	fn to_f32(self) -> f32;
+
+	crate::private_trait!();
+}
+
+macro_rules! impl_quantized_element_type {
+	($type_:ty) => {
+		impl QuantizedElementType for $type_ {
+			fn saturate_from(x: f32) -> Self {
+				x.clamp(<$type_>::MIN as f32, <$type_>::MAX as f32) as $type_
+			}
+
+			fn to_f32(self) -> f32 {
+				self as f32
+			}
+
+			crate::private_impl!();
+		}
+	};
+}
+
+impl_quantized_element_type!(i8);
+impl_quantized_element_type!(u8);
+
+/// Rounds to the nearest integer, with ties rounding to the nearest even integer, as done by ONNX's `QuantizeLinear`.
+fn round_half_to_even(x: f32) -> f32 {
+	let rounded = x.round();
+	if (x - x.trunc()).abs() == 0.5 { 2.0 * (x / 2.0).round() } else { rounded }
+}
+
+/// Quantizes `f32` data into an `i8` or `u8` tensor, with the same semantics as ONNX's
+/// [`QuantizeLinear`](https://onnx.ai/onnx/operators/onnx__QuantizeLinear.html) operator:
+///
+/// `y = saturate(round(x / scale) + zero_point)`
+///
+/// Rounding is half-to-even, and values outside of the range of `T` are saturated to its minimum/maximum value.
+///
+/// `data` can be any type accepted by [`Tensor::from_array`].
+///
+/// ```
+/// # use ort::tensor::quantize_f32;
+/// # fn main() -> ort::Result<()> {
+/// let tensor = quantize_f32(([4], &[0.0_f32, 0.5, -1.0, 100.0][..]), 0.5, 10_u8)?;
+/// assert_eq!(tensor.extract_raw_tensor().1, &[10, 11, 8, 210]);
+/// # 	Ok(())
+/// # }
+/// ```
+pub fn quantize_f32<T: QuantizedElementType>(data: impl IntoValueTensor<Item = f32>, scale: f32, zero_point: T) -> Result<Tensor<T>> {
+	let (shape, data) = data.ref_parts()?;
+	let zero_point = zero_point.to_f32();
+	let data: Vec<T> = data.iter().map(|x| T::saturate_from(round_half_to_even(x / scale) + zero_point)).collect();
+	Tensor::from_array((shape, data))
+}
+
+/// Dequantizes an `i8` or `u8` tensor to `f32`, with the same semantics as ONNX's
+/// [`DequantizeLinear`](https://onnx.ai/onnx/operators/onnx__DequantizeLinear.html) operator:
+///
+/// `y = (x - zero_point) * scale`
+///
+/// ```
+/// # use ort::{tensor::dequantize_to_f32, value::Tensor};
+/// # fn main() -> ort::Result<()> {
+/// let tensor = Tensor::from_array(([3], vec![10_u8, 11, 8]))?;
+/// let dequantized = dequantize_to_f32(&tensor, 0.5, 10)?;
+/// assert_eq!(dequantized.extract_raw_tensor().1, &[0.0, 0.5, -1.0]);
+/// # 	Ok(())
+/// # }
+/// ```
+pub fn dequantize_to_f32<T: QuantizedElementType>(tensor: &Tensor<T>, scale: f32, zero_point: T) -> Result<Tensor<f32>> {
+	let (shape, data) = tensor.extract_raw_tensor();
+	let zero_point = zero_point.to_f32();
+	let data: Vec<f32> = data.iter().map(|x| (x.to_f32() - zero_point) * scale).collect();
+	Tensor::from_array((shape.to_vec(), data))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{dequantize_to_f32, quantize_f32, round_half_to_even};
+
+	#[test]
+	fn test_round_half_to_even() {
+		assert_eq!(round_half_to_even(0.5), 0.0);
+		assert_eq!(round_half_to_even(1.5), 2.0);
+		assert_eq!(round_half_to_even(2.5), 2.0);
+		assert_eq!(round_half_to_even(-0.5), 0.0);
+		assert_eq!(round_half_to_even(-1.5), -2.0);
+		assert_eq!(round_half_to_even(1.4), 1.0);
+		assert_eq!(round_half_to_even(-1.6), -2.0);
+	}
+
+	#[test]
+	fn test_quantize_roundtrip() -> crate::Result<()> {
+		let data = [-1.0_f32, -0.25, 0.0, 0.25, 1.0];
+
+		let quantized = quantize_f32(([5], &data[..]), 1.0 / 127.0, 0_i8)?;
+		assert_eq!(quantized.extract_raw_tensor().1, &[-127, -32, 0, 32, 127]);
+
+		let dequantized = dequantize_to_f32(&quantized, 1.0 / 127.0, 0_i8)?;
+		for (x, y) in data.iter().zip(dequantized.extract_raw_tensor().1) {
+			assert!((x - y).abs() <= 0.5 / 127.0);
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_quantize_saturates() -> crate::Result<()> {
+		let quantized = quantize_f32(([2], &[-1000.0_f32, 1000.0][..]), 1.0, 128_u8)?;
+		assert_eq!(quantized.extract_raw_tensor().1, &[0, 255]);
+
+		Ok(())
+	}
+}
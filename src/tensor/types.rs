@@ -3,7 +3,13 @@ use std::fmt;
 use std::ptr;
 
 #[cfg(feature = "ndarray")]
-use crate::{error::Result, ortsys};
+use crate::ortsys;
+use crate::error::{Error, ErrorCode, Result};
+
+#[cfg(target_endian = "little")]
+const NUMPY_NATIVE_BYTE_ORDER: char = '<';
+#[cfg(target_endian = "big")]
+const NUMPY_NATIVE_BYTE_ORDER: char = '>';
 
 /// Enum mapping ONNX Runtime's supported tensor data types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -39,7 +45,19 @@ pub enum TensorElementType {
 	/// Brain 16-bit floating point number, equivalent to [`half::bf16`] (requires the `half` feature).
 	#[cfg(feature = "half")]
 	#[cfg_attr(docsrs, doc(cfg(feature = "half")))]
-	Bfloat16
+	Bfloat16,
+	/// 8-bit floating point number with 4 exponent bits & 3 mantissa bits and no infinities (ONNX's `FLOAT8E4M3FN`),
+	/// equivalent to [`f8e4m3`](crate::tensor::f8e4m3).
+	Float8E4M3,
+	/// 8-bit floating point number with 5 exponent bits & 2 mantissa bits (ONNX's `FLOAT8E5M2`), equivalent to
+	/// [`f8e5m2`](crate::tensor::f8e5m2).
+	Float8E5M2,
+	/// Signed 4-bit integer in the range `-8..=7`, with two elements packed into each byte. There is no corresponding
+	/// Rust type; see [`Tensor::try_extract_int4_tensor`](crate::value::Tensor::try_extract_int4_tensor).
+	Int4,
+	/// Unsigned 4-bit integer in the range `0..=15`, with two elements packed into each byte. There is no
+	/// corresponding Rust type; see [`Tensor::try_extract_int4_tensor`](crate::value::Tensor::try_extract_int4_tensor).
+	Uint4
 }
 
 impl fmt::Display for TensorElementType {
@@ -52,6 +70,8 @@ impl fmt::Display for TensorElementType {
 			TensorElementType::Float16 => "f16",
 			TensorElementType::Float32 => "f32",
 			TensorElementType::Float64 => "f64",
+			TensorElementType::Float8E4M3 => "f8e4m3",
+			TensorElementType::Float8E5M2 => "f8e5m2",
 			TensorElementType::Int16 => "i16",
 			TensorElementType::Int32 => "i32",
 			TensorElementType::Int64 => "i64",
@@ -60,11 +80,109 @@ impl fmt::Display for TensorElementType {
 			TensorElementType::Uint16 => "u16",
 			TensorElementType::Uint32 => "u32",
 			TensorElementType::Uint64 => "u64",
-			TensorElementType::Uint8 => "u8"
+			TensorElementType::Uint8 => "u8",
+			TensorElementType::Int4 => "i4",
+			TensorElementType::Uint4 => "u4"
 		})
 	}
 }
 
+impl TensorElementType {
+	/// Returns the size of a single element of this type in bytes, or `None` for [`TensorElementType::String`] and the
+	/// packed 4-bit types, whose elements don't take up a whole number of bytes.
+	pub(crate) fn byte_size(&self) -> Option<usize> {
+		match self {
+			TensorElementType::Bool | TensorElementType::Int8 | TensorElementType::Uint8 => Some(1),
+			TensorElementType::Float8E4M3 | TensorElementType::Float8E5M2 => Some(1),
+			#[cfg(feature = "half")]
+			TensorElementType::Bfloat16 | TensorElementType::Float16 => Some(2),
+			TensorElementType::Int16 | TensorElementType::Uint16 => Some(2),
+			TensorElementType::Float32 | TensorElementType::Int32 | TensorElementType::Uint32 => Some(4),
+			TensorElementType::Float64 | TensorElementType::Int64 | TensorElementType::Uint64 => Some(8),
+			TensorElementType::String | TensorElementType::Int4 | TensorElementType::Uint4 => None
+		}
+	}
+
+	/// Returns the size in bytes of the data of a tensor of this type with `len` elements, or `None` for
+	/// [`TensorElementType::String`]. Packed 4-bit types take up half a byte per element, rounded up.
+	pub(crate) fn data_size(&self, len: usize) -> Option<usize> {
+		match self {
+			TensorElementType::Int4 | TensorElementType::Uint4 => Some(len / 2 + len % 2),
+			ty => ty.byte_size().map(|size| size * len)
+		}
+	}
+
+	/// Returns the [NumPy array-protocol type string](https://numpy.org/doc/stable/reference/arrays.interface.html#object.__array_interface__)
+	/// for this type in native byte order, e.g. `<f4` for [`TensorElementType::Float32`] on little-endian targets.
+	///
+	/// NumPy has no `bfloat16` or 8-bit float types, so [`TensorElementType::Bfloat16`], [`TensorElementType::Float8E4M3`]
+	/// & [`TensorElementType::Float8E5M2`] map to `bfloat16`, `float8_e4m3fn` & `float8_e5m2` respectively, which are the
+	/// names used by the `ml_dtypes` package. Returns `None` for [`TensorElementType::String`], and for the packed 4-bit
+	/// types, since `ml_dtypes` stores those unpacked.
+	pub(crate) fn to_numpy_dtype(&self) -> Option<String> {
+		let (kind, size) = match self {
+			TensorElementType::Bool => ('b', 1),
+			TensorElementType::Int8 => ('i', 1),
+			TensorElementType::Int16 => ('i', 2),
+			TensorElementType::Int32 => ('i', 4),
+			TensorElementType::Int64 => ('i', 8),
+			TensorElementType::Uint8 => ('u', 1),
+			TensorElementType::Uint16 => ('u', 2),
+			TensorElementType::Uint32 => ('u', 4),
+			TensorElementType::Uint64 => ('u', 8),
+			#[cfg(feature = "half")]
+			TensorElementType::Float16 => ('f', 2),
+			TensorElementType::Float32 => ('f', 4),
+			TensorElementType::Float64 => ('f', 8),
+			#[cfg(feature = "half")]
+			TensorElementType::Bfloat16 => return Some("bfloat16".to_string()),
+			TensorElementType::Float8E4M3 => return Some("float8_e4m3fn".to_string()),
+			TensorElementType::Float8E5M2 => return Some("float8_e5m2".to_string()),
+			TensorElementType::String | TensorElementType::Int4 | TensorElementType::Uint4 => return None
+		};
+		Some(format!("{}{kind}{size}", if size == 1 { '|' } else { NUMPY_NATIVE_BYTE_ORDER }))
+	}
+
+	/// Parses a NumPy array-protocol type string, as returned by [`TensorElementType::to_numpy_dtype`]. Types which do
+	/// not use the native byte order are rejected.
+	pub(crate) fn from_numpy_dtype(dtype: &str) -> Result<Self> {
+		#[cfg(feature = "half")]
+		if dtype == "bfloat16" {
+			return Ok(TensorElementType::Bfloat16);
+		}
+		match dtype {
+			"float8_e4m3fn" => return Ok(TensorElementType::Float8E4M3),
+			"float8_e5m2" => return Ok(TensorElementType::Float8E5M2),
+			_ => {}
+		}
+
+		let (byte_order, ty) = match dtype.chars().next() {
+			Some(c @ ('<' | '>' | '|' | '=')) => (c, &dtype[1..]),
+			_ => ('=', dtype)
+		};
+		let ty = match ty {
+			"b1" => TensorElementType::Bool,
+			"i1" => TensorElementType::Int8,
+			"i2" => TensorElementType::Int16,
+			"i4" => TensorElementType::Int32,
+			"i8" => TensorElementType::Int64,
+			"u1" => TensorElementType::Uint8,
+			"u2" => TensorElementType::Uint16,
+			"u4" => TensorElementType::Uint32,
+			"u8" => TensorElementType::Uint64,
+			#[cfg(feature = "half")]
+			"f2" => TensorElementType::Float16,
+			"f4" => TensorElementType::Float32,
+			"f8" => TensorElementType::Float64,
+			_ => return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Unsupported tensor dtype `{dtype}`")))
+		};
+		if matches!(byte_order, '<' | '>') && byte_order != NUMPY_NATIVE_BYTE_ORDER && ty.byte_size().unwrap_or(1) > 1 {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Tensor dtype `{dtype}` does not use the native byte order")));
+		}
+		Ok(ty)
+	}
+}
+
 impl From<TensorElementType> for ort_sys::ONNXTensorElementDataType {
 	fn from(val: TensorElementType) -> Self {
 		match val {
@@ -83,7 +201,11 @@ impl From<TensorElementType> for ort_sys::ONNXTensorElementDataType {
 			TensorElementType::Uint32 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT32,
 			TensorElementType::Uint64 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64,
 			#[cfg(feature = "half")]
-			TensorElementType::Bfloat16 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16
+			TensorElementType::Bfloat16 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16,
+			TensorElementType::Float8E4M3 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E4M3FN,
+			TensorElementType::Float8E5M2 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E5M2,
+			TensorElementType::Int4 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT4,
+			TensorElementType::Uint4 => ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT4
 		}
 	}
 }
@@ -106,6 +228,10 @@ impl From<ort_sys::ONNXTensorElementDataType> for TensorElementType {
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT64 => TensorElementType::Uint64,
 			#[cfg(feature = "half")]
 			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_BFLOAT16 => TensorElementType::Bfloat16,
+			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E4M3FN => TensorElementType::Float8E4M3,
+			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_FLOAT8E5M2 => TensorElementType::Float8E5M2,
+			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_INT4 => TensorElementType::Int4,
+			ort_sys::ONNXTensorElementDataType::ONNX_TENSOR_ELEMENT_DATA_TYPE_UINT4 => TensorElementType::Uint4,
 			_ => panic!("Invalid ONNXTensorElementDataType value")
 		}
 	}
@@ -158,6 +284,8 @@ impl_type_trait!(u64, Uint64);
 #[cfg(feature = "half")]
 #[cfg_attr(docsrs, doc(cfg(feature = "half")))]
 impl_type_trait!(half::bf16, Bfloat16);
+impl_type_trait!(super::f8e4m3, Float8E4M3);
+impl_type_trait!(super::f8e5m2, Float8E5M2);
 
 impl IntoTensorElementType for String {
 	fn into_tensor_element_type() -> TensorElementType {
@@ -216,3 +344,20 @@ pub(crate) fn extract_primitive_array_mut<'t, T>(shape: ndarray::IxDyn, tensor:
 	let array_view = unsafe { ndarray::ArrayViewMut::from_shape_ptr(shape, output_array_ptr) };
 	Ok(array_view)
 }
+
+#[cfg(test)]
+mod tests {
+	use super::TensorElementType;
+
+	#[test]
+	fn test_numpy_dtype() -> crate::Result<()> {
+		for ty in [TensorElementType::Bool, TensorElementType::Uint8, TensorElementType::Int64, TensorElementType::Float32] {
+			assert_eq!(TensorElementType::from_numpy_dtype(&ty.to_numpy_dtype().expect("non-string type"))?, ty);
+		}
+		assert_eq!(TensorElementType::Uint8.to_numpy_dtype().as_deref(), Some("|u1"));
+		assert!(TensorElementType::String.to_numpy_dtype().is_none());
+		assert!(TensorElementType::from_numpy_dtype("<c8").is_err());
+		assert_eq!(TensorElementType::from_numpy_dtype("float8_e4m3fn")?, TensorElementType::Float8E4M3);
+		Ok(())
+	}
+}
@@ -1,12 +1,21 @@
 //! Provides [`Trainer`], a simple interface for on-device training/fine-tuning.
 
 use std::{
+	cell::RefCell,
+	collections::HashMap,
+	ffi::CString,
 	path::Path,
 	ptr::{self, NonNull},
 	sync::OnceLock
 };
 
-use crate::{AsPointer, Error, Result, ortsys, session::RunOptions};
+use crate::{
+	AsPointer, Error, Result,
+	memory::Allocator,
+	ortsys,
+	session::RunOptions,
+	value::{DynValue, Value, ValueTypeMarker}
+};
 
 mod simple;
 mod trainer;
@@ -95,6 +104,8 @@ pub struct Checkpoint {
 }
 
 impl Checkpoint {
+	/// Loads a checkpoint from a file. To load a checkpoint which is already in memory without writing it to disk, see
+	/// [`Checkpoint::from_bytes`].
 	pub fn load(path: impl AsRef<Path>) -> Result<Self> {
 		let path = crate::util::path_to_os_char(path);
 		let mut ptr: *mut ort_sys::OrtCheckpointState = ptr::null_mut();
@@ -104,11 +115,46 @@ impl Checkpoint {
 		})
 	}
 
+	/// Loads a checkpoint from an in-memory buffer, such as one downloaded from object storage or embedded with
+	/// [`include_bytes!`].
+	///
+	/// The buffer must contain a flatbuffer checkpoint, as saved by [`Checkpoint::save`] or ONNX Runtime's
+	/// `onnxruntime.training.artifacts` module. The data is copied by ONNX Runtime, so `data` need not outlive the
+	/// returned checkpoint.
+	///
+	/// ```no_run
+	/// # use ort::training::Checkpoint;
+	/// # fn main() -> ort::Result<()> {
+	/// let data = std::fs::read("tests/data/training/checkpoint").expect("failed to read checkpoint");
+	/// let checkpoint = Checkpoint::from_bytes(&data)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[doc(alias("load_from_bytes", "LoadCheckpointFromBuffer"))]
+	pub fn from_bytes(data: &[u8]) -> Result<Self> {
+		let mut ptr: *mut ort_sys::OrtCheckpointState = ptr::null_mut();
+		trainsys![unsafe LoadCheckpointFromBuffer(data.as_ptr().cast(), data.len(), &mut ptr)?; nonNull(ptr)];
+		Ok(Checkpoint {
+			ptr: unsafe { NonNull::new_unchecked(ptr) }
+		})
+	}
+
 	pub fn save(&self, path: impl AsRef<Path>, include_optimizer_state: bool) -> Result<()> {
 		let path = crate::util::path_to_os_char(path);
 		trainsys![unsafe SaveCheckpoint(self.ptr.as_ptr(), path.as_ptr(), include_optimizer_state)?];
 		Ok(())
 	}
+
+	/// Returns a copy of the current value of the parameter named `name`.
+	pub fn parameter(&self, name: &str) -> Result<DynValue> {
+		get_parameter(self.ptr, name)
+	}
+
+	/// Overwrites the value of the parameter named `name`. `value` must have the same element type & shape as the
+	/// parameter.
+	pub fn update_parameter<Type: ValueTypeMarker + ?Sized>(&self, name: &str, value: &Value<Type>) -> Result<()> {
+		update_parameter(self.ptr, name, value)
+	}
 }
 
 impl AsPointer for Checkpoint {
@@ -127,31 +173,187 @@ impl Drop for Checkpoint {
 }
 
 #[derive(Debug)]
-pub struct Optimizer(NonNull<ort_sys::OrtTrainingSession>);
+pub struct Optimizer {
+	ptr: NonNull<ort_sys::OrtTrainingSession>,
+	checkpoint_ptr: NonNull<ort_sys::OrtCheckpointState>,
+	parameter_names: Vec<String>,
+	/// Learning rates set via [`Optimizer::set_lr_for`], keyed by parameter name prefix.
+	group_lrs: RefCell<Vec<(String, f32)>>
+}
 
 impl Optimizer {
+	pub(crate) fn new(ptr: NonNull<ort_sys::OrtTrainingSession>, checkpoint: &Checkpoint, parameter_names: Vec<String>) -> Self {
+		Self {
+			ptr,
+			checkpoint_ptr: checkpoint.ptr,
+			parameter_names,
+			group_lrs: RefCell::new(Vec::new())
+		}
+	}
+
 	pub fn reset_grad(&self) -> Result<()> {
-		trainsys![unsafe LazyResetGrad(self.0.as_ptr())?];
+		trainsys![unsafe LazyResetGrad(self.ptr.as_ptr())?];
 		Ok(())
 	}
 
+	/// Returns the optimizer's current learning rate.
+	#[doc(alias = "get_lr")]
 	pub fn lr(&self) -> Result<f32> {
 		let mut lr = f32::NAN;
-		trainsys![unsafe GetLearningRate(self.0.as_ptr(), &mut lr)?];
+		trainsys![unsafe GetLearningRate(self.ptr.as_ptr(), &mut lr)?];
 		Ok(lr)
 	}
 
+	/// Sets the optimizer's learning rate.
+	///
+	/// This does not affect the optimizer's state (e.g. AdamW's first & second moment estimates). ONNX Runtime does not
+	/// provide a way to reset the optimizer state of a live training session; to start over with fresh moments, save the
+	/// checkpoint *without* optimizer state via [`Checkpoint::save`] and create a new [`Trainer`] from it.
+	///
+	/// The learning rate applies to every trainable parameter, except those given a different learning rate with
+	/// [`Optimizer::set_lr_for`].
 	pub fn set_lr(&self, lr: f32) -> Result<()> {
-		trainsys![unsafe SetLearningRate(self.0.as_ptr(), lr)?];
+		trainsys![unsafe SetLearningRate(self.ptr.as_ptr(), lr)?];
+		Ok(())
+	}
+
+	/// Sets the learning rate of all trainable parameters whose names start with `prefix`, e.g. to train the embedding
+	/// layer with a lower learning rate than the rest of the model. Other parameters keep using the learning rate set
+	/// by [`Optimizer::set_lr`]. If a parameter matches several prefixes, the longest one takes precedence.
+	///
+	/// ONNX Runtime's optimizers take a single learning rate, so this is emulated by [`Optimizer::step`]: the values of
+	/// the matching parameters are saved before the step, and their updates are then scaled by the ratio of their
+	/// learning rate to the global learning rate. This is exact for the SGD & AdamW optimizers generated by
+	/// `onnxruntime.training.artifacts`, whose updates are proportional to the learning rate, but requires copying the
+	/// matching parameters on every step. Only `f32` parameters are supported.
+	///
+	/// Returns an error if no trainable parameter starts with `prefix`.
+	///
+	/// ```no_run
+	/// # use ort::{memory::Allocator, session::Session, training::Trainer};
+	/// # fn main() -> ort::Result<()> {
+	/// # let trainer = Trainer::new_from_artifacts(Session::builder()?, Allocator::default(), "tests/data/training", None)?;
+	/// let optimizer = trainer.optimizer();
+	/// optimizer.set_lr(1e-4)?;
+	/// optimizer.set_lr_for("transformer.wte.", 1e-5)?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn set_lr_for(&self, prefix: impl Into<String>, lr: f32) -> Result<()> {
+		let prefix = prefix.into();
+		if !self.parameter_names.iter().any(|name| name.starts_with(&prefix)) {
+			return Err(Error::new(format!("No trainable parameter name starts with `{prefix}`")));
+		}
+
+		let mut group_lrs = self.group_lrs.borrow_mut();
+		match group_lrs.iter_mut().find(|(p, _)| *p == prefix) {
+			Some((_, group_lr)) => *group_lr = lr,
+			None => group_lrs.push((prefix, lr))
+		}
 		Ok(())
 	}
 
+	/// Returns the learning rate set for the parameter named `name` via [`Optimizer::set_lr_for`], if any.
+	fn group_lr(&self, name: &str) -> Option<f32> {
+		self.group_lrs
+			.borrow()
+			.iter()
+			.filter(|(prefix, _)| name.starts_with(prefix.as_str()))
+			.max_by_key(|(prefix, _)| prefix.len())
+			.map(|(_, lr)| *lr)
+	}
+
 	pub fn step(&self) -> Result<()> {
 		self.step_with_options(RunOptions::new()?)
 	}
 
 	pub fn step_with_options(&self, options: RunOptions) -> Result<()> {
-		trainsys![unsafe OptimizerStep(self.0.as_ptr(), options.ptr())?];
+		if self.group_lrs.borrow().is_empty() {
+			trainsys![unsafe OptimizerStep(self.ptr.as_ptr(), options.ptr())?];
+			return Ok(());
+		}
+
+		// With a global learning rate of 0, no parameter would be updated at all, so step with a learning rate of 1
+		// instead and scale every parameter's update.
+		let lr = self.lr()?;
+		let step_lr = if lr == 0.0 { 1.0 } else { lr };
+		let scaled = self
+			.parameter_names
+			.iter()
+			.filter_map(|name| Some((name, self.group_lr(name).or((lr == 0.0).then_some(0.0))?)))
+			.map(|(name, target_lr)| {
+				let before = get_parameter(self.checkpoint_ptr, name)?.try_extract_raw_tensor::<f32>()?.1.to_vec();
+				Ok((name, target_lr / step_lr, before))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		if step_lr != lr {
+			self.set_lr(step_lr)?;
+		}
+		let result = crate::error::status_to_result(trainsys![unsafe OptimizerStep(self.ptr.as_ptr(), options.ptr())]);
+		if step_lr != lr {
+			self.set_lr(lr)?;
+		}
+		result?;
+
+		for (name, scale, before) in scaled {
+			let mut value = get_parameter(self.checkpoint_ptr, name)?;
+			let (_, after) = value.try_extract_raw_tensor_mut::<f32>()?;
+			for (after, before) in after.iter_mut().zip(before) {
+				*after = before + (*after - before) * scale;
+			}
+			update_parameter(self.checkpoint_ptr, name, &value)?;
+		}
 		Ok(())
 	}
+
+	/// Returns the names of the model's trainable parameters.
+	pub fn parameter_names(&self) -> &[String] {
+		&self.parameter_names
+	}
+
+	/// Returns a copy of the current value of each of the model's trainable parameters, keyed by name.
+	///
+	/// Frozen (non-trainable) parameters are not included, but can be retrieved by name with
+	/// [`Checkpoint::parameter`].
+	///
+	/// ```no_run
+	/// # use ort::{memory::Allocator, session::Session, training::Trainer};
+	/// # fn main() -> ort::Result<()> {
+	/// # let trainer = Trainer::new_from_artifacts(Session::builder()?, Allocator::default(), "tests/data/training", None)?;
+	/// for (name, value) in trainer.optimizer().get_all_parameters()? {
+	/// 	let norm = value.try_extract_tensor::<f32>()?.iter().map(|x| x * x).sum::<f32>().sqrt();
+	/// 	println!("{name}: {norm}");
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn get_all_parameters(&self) -> Result<HashMap<String, DynValue>> {
+		self.parameter_names
+			.iter()
+			.map(|name| Ok((name.clone(), get_parameter(self.checkpoint_ptr, name)?)))
+			.collect()
+	}
+
+	/// Overwrites the value of the parameter named `name`, e.g. to load pre-trained weights into the training session.
+	///
+	/// `value` must have the same element type & shape as the parameter. Its data is copied, so `value` can be dropped
+	/// afterwards.
+	pub fn set_parameter<Type: ValueTypeMarker + ?Sized>(&self, name: &str, value: &Value<Type>) -> Result<()> {
+		update_parameter(self.checkpoint_ptr, name, value)
+	}
+}
+
+fn get_parameter(checkpoint_ptr: NonNull<ort_sys::OrtCheckpointState>, name: &str) -> Result<DynValue> {
+	let name = CString::new(name)?;
+	let allocator = Allocator::default();
+	let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+	trainsys![unsafe GetParameter(checkpoint_ptr.as_ptr(), name.as_ptr(), allocator.ptr().cast_mut(), &mut value_ptr)?; nonNull(value_ptr)];
+	Ok(unsafe { Value::from_ptr(NonNull::new_unchecked(value_ptr), None) })
+}
+
+fn update_parameter<Type: ValueTypeMarker + ?Sized>(checkpoint_ptr: NonNull<ort_sys::OrtCheckpointState>, name: &str, value: &Value<Type>) -> Result<()> {
+	let name = CString::new(name)?;
+	trainsys![unsafe UpdateParameter(checkpoint_ptr.as_ptr(), name.as_ptr(), value.ptr().cast_mut())?];
+	Ok(())
 }
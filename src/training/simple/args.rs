@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::{
+	path::PathBuf,
+	sync::{Arc, atomic::AtomicBool}
+};
 
 use super::{DataLoader, TrainerCallbacks};
 use crate::session::input::SessionInputs;
@@ -58,7 +61,8 @@ pub struct TrainingArguments<I: Into<SessionInputs<'static, 'static, NI>>, L: In
 	pub(crate) gradient_accumulation_steps: usize,
 	pub(crate) max_steps: usize,
 	pub(crate) max_eval_steps: usize,
-	pub(crate) callbacks: Vec<Box<dyn TrainerCallbacks>>
+	pub(crate) callbacks: Vec<Box<dyn TrainerCallbacks>>,
+	pub(crate) cancellation: Option<Arc<AtomicBool>>
 }
 
 impl<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static, 'static, NL>>, const NI: usize, const NL: usize>
@@ -76,7 +80,8 @@ impl<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static
 			max_saved_ckpts: 1,
 			max_steps: usize::MAX,
 			max_eval_steps: usize::MAX,
-			callbacks: Vec::new()
+			callbacks: Vec::new(),
+			cancellation: None
 		}
 	}
 
@@ -134,4 +139,34 @@ impl<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static
 		self.callbacks.push(Box::new(callbacks));
 		self
 	}
+
+	/// Allows training to be cancelled cooperatively, e.g. from a `SIGINT` handler, by setting `token` to `true`.
+	///
+	/// The token is checked before each training step, so the step in progress is always allowed to finish. Once
+	/// cancelled, a final checkpoint is saved to the [checkpoint path](TrainingArguments::with_ckpt_path) (unless the
+	/// checkpoint strategy is [`CheckpointStrategy::None`]), [`TrainerCallbacks::end`] is fired, and
+	/// [`Trainer::train`](crate::training::Trainer::train) returns `Ok(())`. Gradients accumulated since the last
+	/// optimizer step are discarded.
+	///
+	/// ```no_run
+	/// # use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+	/// # use ort::{session::SessionInputs, training::{Trainer, TrainingArguments}};
+	/// # fn main() -> ort::Result<()> {
+	/// # let trainer: Trainer = unimplemented!();
+	/// # let loader = |_: usize| -> ort::Result<(SessionInputs<'static, 'static, 1>, SessionInputs<'static, 'static, 1>)> { unimplemented!() };
+	/// let cancelled = Arc::new(AtomicBool::new(false));
+	/// // e.g. with the `ctrlc` crate:
+	/// // let c = Arc::clone(&cancelled);
+	/// // ctrlc::set_handler(move || c.store(true, Ordering::Relaxed))?;
+	/// trainer.train(TrainingArguments::new(loader).with_cancellation(Arc::clone(&cancelled)))?;
+	/// if cancelled.load(Ordering::Relaxed) {
+	/// 	println!("training interrupted; checkpoint saved");
+	/// }
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_cancellation(mut self, token: Arc<AtomicBool>) -> Self {
+		self.cancellation = Some(token);
+		self
+	}
 }
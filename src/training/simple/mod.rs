@@ -1,4 +1,9 @@
-use std::{collections::VecDeque, fs};
+use std::{
+	collections::VecDeque,
+	fs,
+	path::PathBuf,
+	sync::atomic::Ordering
+};
 
 use crate::{error::Result, session::input::SessionInputs, training::Trainer};
 
@@ -52,6 +57,13 @@ impl Trainer {
 		let mut state = TrainerState::new(&args);
 		let mut last_epoch = -1.0;
 		for (iter_step, _) in (0..args.max_steps).enumerate() {
+			if args.cancellation.as_deref().is_some_and(|token| token.load(Ordering::Relaxed)) {
+				if !matches!(args.ckpt_strategy, CheckpointStrategy::None) {
+					self.save_checkpoint(&args, &state, &mut saved_ckpts)?;
+				}
+				return self.handle_halt(&mut args.callbacks, &state);
+			}
+
 			state.iter_step = iter_step;
 			state.epoch = args.loader.len().map(|dl_len| iter_step as f32 / dl_len as f32);
 
@@ -78,22 +90,7 @@ impl Trainer {
 			}
 
 			if args.ckpt_strategy.should_fire(state.global_step, iter_step, args.loader.len()) {
-				if !args.ckpt_path.exists() {
-					let _ = fs::create_dir_all(&args.ckpt_path);
-				}
-
-				let ckpt_path =
-					args.ckpt_path
-						.join(format!("epoch={},step={}.ortckpt", state.epoch.map(f32::trunc).unwrap_or(0.0) as usize, state.global_step));
-				self.checkpoint().save(&ckpt_path, true)?;
-
-				saved_ckpts.push_front(ckpt_path.clone());
-				while saved_ckpts.len() > args.max_saved_ckpts {
-					let Some(old_ckpt) = saved_ckpts.pop_back() else {
-						break;
-					};
-					let _ = fs::remove_file(old_ckpt);
-				}
+				self.save_checkpoint(&args, &state, &mut saved_ckpts)?;
 			}
 
 			if args
@@ -108,6 +105,34 @@ impl Trainer {
 		Ok(())
 	}
 
+	fn save_checkpoint<I: Into<SessionInputs<'static, 'static, NI>>, L: Into<SessionInputs<'static, 'static, NL>>, const NI: usize, const NL: usize>(
+		&self,
+		args: &TrainingArguments<I, L, NI, NL>,
+		state: &TrainerState,
+		saved_ckpts: &mut VecDeque<PathBuf>
+	) -> Result<()> {
+		if !args.ckpt_path.exists() {
+			let _ = fs::create_dir_all(&args.ckpt_path);
+		}
+
+		let ckpt_path = args
+			.ckpt_path
+			.join(format!("epoch={},step={}.ortckpt", state.epoch.map(f32::trunc).unwrap_or(0.0) as usize, state.global_step));
+		self.checkpoint().save(&ckpt_path, true)?;
+
+		// saving twice at the same step (i.e. when cancelled right after a scheduled checkpoint) overwrites the same file
+		if saved_ckpts.front() != Some(&ckpt_path) {
+			saved_ckpts.push_front(ckpt_path);
+		}
+		while saved_ckpts.len() > args.max_saved_ckpts {
+			let Some(old_ckpt) = saved_ckpts.pop_back() else {
+				break;
+			};
+			let _ = fs::remove_file(old_ckpt);
+		}
+		Ok(())
+	}
+
 	fn handle_halt(&self, cbs: &mut Vec<Box<dyn TrainerCallbacks>>, state: &TrainerState) -> Result<()> {
 		for cb in cbs {
 			let mut control = TrainerControl::new(self);
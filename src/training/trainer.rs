@@ -9,10 +9,10 @@ use ort_sys::c_char;
 use super::{Checkpoint, Optimizer, trainsys};
 use crate::{
 	AsPointer, char_p_to_string,
-	error::{Result, assert_non_null_pointer, status_to_result},
+	error::{Error, ErrorCode, Result, assert_non_null_pointer, status_to_result},
 	memory::Allocator,
 	session::{RunOptions, SessionInputValue, SessionInputs, SessionOutputs, builder::SessionBuilder},
-	value::Value
+	value::{Tensor, Value}
 };
 
 #[derive(Debug)]
@@ -25,6 +25,12 @@ pub struct Trainer {
 }
 
 impl Trainer {
+	/// Creates a new [`Trainer`] from a checkpoint & the training, eval, and optimizer models generated alongside it.
+	///
+	/// Before creating the training session, the three models are checked for consistency: the eval model's inputs
+	/// must be inputs of the training model, every trainable parameter must also be an input of the eval model, and
+	/// the optimizer model must take the inputs ONNX Runtime expects. If the artifacts don't match, an error naming the
+	/// offending input is returned.
 	pub fn new(
 		session_options: SessionBuilder,
 		allocator: Allocator,
@@ -33,6 +39,8 @@ impl Trainer {
 		eval_model_path: impl AsRef<Path>,
 		optimizer_model_path: impl AsRef<Path>
 	) -> Result<Self> {
+		let parameter_names = validate_artifacts(training_model_path.as_ref(), eval_model_path.as_ref(), optimizer_model_path.as_ref())?;
+
 		let training_model_path = crate::util::path_to_os_char(training_model_path);
 		let eval_model_path = crate::util::path_to_os_char(eval_model_path);
 		let optimizer_model_path = crate::util::path_to_os_char(optimizer_model_path);
@@ -66,7 +74,7 @@ impl Trainer {
 			ptr,
 			_allocator: allocator,
 			train_output_names,
-			optimizer: Optimizer(ptr),
+			optimizer: Optimizer::new(ptr, &ckpt, parameter_names),
 			ckpt
 		})
 	}
@@ -93,6 +101,15 @@ impl Trainer {
 		)
 	}
 
+	/// Runs a single training step (forward & backward pass) with the given inputs & labels, returning the outputs of
+	/// the training graph (typically the loss).
+	///
+	/// Gradients are accumulated in the training session, and are only applied to the model's parameters once
+	/// [`Optimizer::step`] is called; the gradients can be cleared with [`Optimizer::reset_grad`].
+	///
+	/// Note that ONNX Runtime's training API does not expose the accumulated gradients, so they cannot be inspected
+	/// between `step` & [`Optimizer::step`]. The parameters themselves can be inspected at any point with
+	/// [`Trainer::parameters`].
 	pub fn step<'s, 'i1, 'v1: 'i1, 'i2: 'i1, 'v2: 'i2 + 'i1, const N1: usize, const N2: usize>(
 		&'s self,
 		inputs: impl Into<SessionInputs<'i1, 'v1, N1>>,
@@ -211,6 +228,80 @@ impl Trainer {
 		Ok(())
 	}
 
+	/// Exports the model for inferencing like [`Trainer::export`], renaming the outputs of the exported model.
+	///
+	/// `outputs` is a list of `(graph_output, exported_name)` pairs, where `graph_output` is the name of an output in
+	/// the eval graph, and `exported_name` is the name it will have in the exported model. This allows the exported
+	/// model to have names decoupled from the ones used during training. Returns an error if any `graph_output` does not
+	/// exist, if the exported names are not unique, or if an exported name is already used by another value in the graph.
+	///
+	/// ```no_run
+	/// # use ort::{memory::Allocator, session::Session, training::Trainer};
+	/// # fn main() -> ort::Result<()> {
+	/// # let trainer = Trainer::new_from_artifacts(Session::builder()?, Allocator::default(), "training_artifacts", None)?;
+	/// trainer.export_renamed("model.onnx", [("fc2.output", "probs")])?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn export_renamed<O: AsRef<str>, N: AsRef<str>>(&self, out_path: impl AsRef<Path>, outputs: impl AsRef<[(O, N)]>) -> Result<()> {
+		let renames: Vec<(&str, &str)> = outputs.as_ref().iter().map(|(o, n)| (o.as_ref(), n.as_ref())).collect();
+		for (i, (_, name)) in renames.iter().enumerate() {
+			if renames[..i].iter().any(|(_, n)| n == name) {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Exported output name `{name}` is used more than once")));
+			}
+		}
+
+		let out_path = out_path.as_ref();
+		self.export(out_path, renames.iter().map(|(o, _)| *o).collect::<Vec<_>>())?;
+
+		let model = std::fs::read(out_path).map_err(Error::wrap)?;
+		let renamed = crate::model_proto::rename_graph_outputs(&model, &renames)?;
+		std::fs::write(out_path, renamed).map_err(Error::wrap)?;
+		Ok(())
+	}
+
+	/// Exports the model for inferencing like [`Trainer::export`], but stores all `float32` parameters in half
+	/// precision (`float16`), roughly halving the size of the exported model.
+	///
+	/// Only floating-point parameters are converted; integer buffers are left untouched. The graph itself still
+	/// computes in `float32` - each converted parameter is cast back to `float32` when the model is loaded - so this
+	/// mostly reduces storage & transfer size rather than latency. Values outside of `f16`'s range are saturated to
+	/// infinity.
+	#[cfg(feature = "half")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "half")))]
+	pub fn export_fp16<O: AsRef<str>>(&self, out_path: impl AsRef<Path>, output_names: impl AsRef<[O]>) -> Result<()> {
+		let out_path = out_path.as_ref();
+		self.export(out_path, output_names)?;
+
+		let model = std::fs::read(out_path).map_err(crate::Error::wrap)?;
+		let converted = crate::model_proto::convert_initializers_to_fp16(&model)?;
+		std::fs::write(out_path, converted).map_err(crate::Error::wrap)?;
+		Ok(())
+	}
+
+	/// Returns the total number of elements across all of the model's parameters.
+	///
+	/// If `trainable_only` is `true`, frozen (non-trainable) parameters are not counted.
+	pub fn parameter_count(&self, trainable_only: bool) -> Result<usize> {
+		let mut count = 0;
+		trainsys![unsafe GetParametersSize(self.ptr.as_ptr(), &mut count, trainable_only)?];
+		Ok(count)
+	}
+
+	/// Copies the current values of all of the model's parameters into a new, flat `f32` tensor with
+	/// [`Trainer::parameter_count`] elements.
+	///
+	/// The returned tensor is a copy and does not alias the training session's memory, so it can safely be inspected
+	/// (e.g. to check for `NaN`s or compute norms) while training continues.
+	///
+	/// Note that ONNX Runtime's training API does not provide access to the gradients themselves.
+	pub fn parameters(&self, trainable_only: bool) -> Result<Tensor<f32>> {
+		let count = self.parameter_count(trainable_only)?;
+		let mut buffer = Tensor::<f32>::new(&Allocator::default(), [count])?;
+		trainsys![unsafe CopyParametersToBuffer(self.ptr.as_ptr(), buffer.ptr_mut(), trainable_only)?];
+		Ok(buffer)
+	}
+
 	pub fn optimizer(&self) -> &Optimizer {
 		&self.optimizer
 	}
@@ -234,3 +325,54 @@ impl Drop for Trainer {
 		trainsys![unsafe ReleaseTrainingSession(self.ptr.as_ptr())];
 	}
 }
+
+/// Inputs which every optimizer model generated by ONNX Runtime's `onnxblock` takes.
+const OPTIMIZER_INPUTS: [&str; 4] = ["learning_rate", "step", "params", "gradients"];
+/// Suffix of the training model inputs which hold the accumulated gradient of each trainable parameter.
+const GRADIENT_BUFFER_SUFFIX: &str = "_grad.accumulation.buffer";
+
+/// Checks that the training, eval, and optimizer models were generated together, so that mismatched artifacts are
+/// reported when creating the [`Trainer`] rather than on the first training step. Returns the names of the trainable
+/// parameters.
+fn validate_artifacts(training_model_path: &Path, eval_model_path: &Path, optimizer_model_path: &Path) -> Result<Vec<String>> {
+	let input_names = |path: &Path| {
+		std::fs::read(path)
+			.map_err(Error::wrap)
+			.and_then(|model| crate::model_proto::graph_input_names(&model))
+			.map_err(|e| Error::new_with_code(e.code(), format!("Failed to load training artifact `{}`: {e}", path.display())))
+	};
+	let training_inputs = input_names(training_model_path)?;
+	let eval_inputs = input_names(eval_model_path)?;
+	let optimizer_inputs = input_names(optimizer_model_path)?;
+
+	let mismatch = |msg: String| Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Training artifacts are inconsistent: {msg}")));
+	if let Some(missing) = OPTIMIZER_INPUTS.iter().find(|name| !optimizer_inputs.iter().any(|i| i == *name)) {
+		return mismatch(format!("optimizer model `{}` does not have the expected input `{missing}`", optimizer_model_path.display()));
+	}
+	if let Some(extra) = eval_inputs.iter().find(|name| !training_inputs.contains(name)) {
+		return mismatch(format!(
+			"input `{extra}` of eval model `{}` is not an input of training model `{}`",
+			eval_model_path.display(),
+			training_model_path.display()
+		));
+	}
+	let trainable_parameters: Vec<String> = training_inputs
+		.iter()
+		.filter_map(|name| name.strip_suffix(GRADIENT_BUFFER_SUFFIX))
+		.map(str::to_owned)
+		.collect();
+	if trainable_parameters.is_empty() {
+		return mismatch(format!("training model `{}` does not have any trainable parameters", training_model_path.display()));
+	}
+	for parameter in &trainable_parameters {
+		if !eval_inputs.iter().any(|name| name == parameter) {
+			return mismatch(format!(
+				"trainable parameter `{parameter}` of training model `{}` is not an input of eval model `{}`",
+				training_model_path.display(),
+				eval_model_path.display()
+			));
+		}
+	}
+
+	Ok(trainable_parameters)
+}
@@ -0,0 +1,193 @@
+//! Conversions between tensor [`Value`]s and [Apache Arrow](https://arrow.apache.org/) arrays.
+
+use std::sync::Arc;
+
+use arrow_array::{
+	Array, ArrayRef, BooleanArray, FixedSizeListArray, PrimitiveArray,
+	cast::AsArray,
+	types::{Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type, UInt16Type, UInt32Type, UInt64Type}
+};
+use arrow_schema::{DataType, Field};
+
+use super::{DynTensor, DynTensorValueType, DynValue, Tensor, Value, ValueType, ValueTypeMarker};
+use crate::{
+	error::{Error, ErrorCode, Result},
+	tensor::TensorElementType
+};
+
+impl<Type: ValueTypeMarker + ?Sized> Value<Type> {
+	/// Converts this tensor to an Arrow array, copying its data.
+	///
+	/// A 1-dimensional tensor is converted to a [`PrimitiveArray`] (or [`BooleanArray`] for `bool` tensors), and a
+	/// 2-dimensional tensor of shape `[N, M]` is converted to a [`FixedSizeListArray`] of `N` lists of size `M`. Tensors
+	/// of other ranks, string tensors, and tensors which are not in CPU-accessible memory are not supported.
+	///
+	/// ```
+	/// # use arrow_array::{Array, FixedSizeListArray};
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]))?;
+	/// let array = tensor.to_arrow_array()?;
+	///
+	/// let list = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+	/// assert_eq!(list.len(), 2);
+	/// assert_eq!(list.value_length(), 3);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn to_arrow_array(&self) -> Result<ArrayRef> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot convert value of type {} to an Arrow array", self.dtype())));
+		};
+		let tensor = self.view().downcast::<DynTensorValueType>()?;
+
+		macro_rules! primitive_array {
+			($t:ty, $arrow:ty) => {{
+				let (_, data) = tensor.try_extract_raw_tensor::<$t>()?;
+				Arc::new(PrimitiveArray::<$arrow>::from_iter_values(data.iter().copied())) as ArrayRef
+			}};
+		}
+
+		let values = match ty {
+			TensorElementType::Float32 => primitive_array!(f32, Float32Type),
+			TensorElementType::Float64 => primitive_array!(f64, Float64Type),
+			TensorElementType::Int8 => primitive_array!(i8, Int8Type),
+			TensorElementType::Int16 => primitive_array!(i16, Int16Type),
+			TensorElementType::Int32 => primitive_array!(i32, Int32Type),
+			TensorElementType::Int64 => primitive_array!(i64, Int64Type),
+			TensorElementType::Uint8 => primitive_array!(u8, UInt8Type),
+			TensorElementType::Uint16 => primitive_array!(u16, UInt16Type),
+			TensorElementType::Uint32 => primitive_array!(u32, UInt32Type),
+			TensorElementType::Uint64 => primitive_array!(u64, UInt64Type),
+			TensorElementType::Bool => {
+				let (_, data) = tensor.try_extract_raw_tensor::<bool>()?;
+				Arc::new(data.iter().map(|x| Some(*x)).collect::<BooleanArray>()) as ArrayRef
+			}
+			ty => return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot convert tensor of type {ty} to an Arrow array")))
+		};
+
+		match dimensions.as_slice() {
+			[_] => Ok(values),
+			[_, size] => {
+				let field = Arc::new(Field::new("item", values.data_type().clone(), false));
+				let list = FixedSizeListArray::try_new(field, *size as i32, values, None).map_err(Error::wrap)?;
+				Ok(Arc::new(list))
+			}
+			dimensions => Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Only 1- and 2-dimensional tensors can be converted to Arrow arrays; got shape {dimensions:?}")
+			))
+		}
+	}
+}
+
+impl DynValue {
+	/// Creates a tensor from an Arrow array, copying its data.
+	///
+	/// This is the inverse of [`Value::to_arrow_array`]: a [`PrimitiveArray`] or [`BooleanArray`] of length `N` is
+	/// converted to a tensor of shape `[N]`, and a [`FixedSizeListArray`] of `N` lists of size `M` is converted to a
+	/// tensor of shape `[N, M]`. Arrays containing nulls are not supported.
+	///
+	/// ```
+	/// # use arrow_array::Int64Array;
+	/// # use ort::value::DynValue;
+	/// # fn main() -> ort::Result<()> {
+	/// let array = Int64Array::from(vec![1, 2, 3]);
+	/// let value = DynValue::from_arrow_array(&array)?;
+	/// assert_eq!(value.try_extract_raw_tensor::<i64>()?, (&[3_i64][..], &[1, 2, 3][..]));
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn from_arrow_array(array: &dyn Array) -> Result<DynValue> {
+		if array.null_count() > 0 {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot create a tensor from an Arrow array containing nulls"));
+		}
+
+		match array.data_type() {
+			DataType::FixedSizeList(..) => {
+				let list = array.as_fixed_size_list();
+				let size = list.value_length() as usize;
+				let values = list.values().slice(list.offset() * size, list.len() * size);
+				let tensor = tensor_from_arrow_values(values.as_ref())?;
+				Ok(tensor.into_shape([list.len() as i64, size as i64])?.into_dyn())
+			}
+			_ => Ok(tensor_from_arrow_values(array)?.into_dyn())
+		}
+	}
+}
+
+fn tensor_from_arrow_values(array: &dyn Array) -> Result<DynTensor> {
+	if array.null_count() > 0 {
+		return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot create a tensor from an Arrow array containing nulls"));
+	}
+
+	macro_rules! primitive_tensor {
+		($arrow:ty) => {
+			Tensor::from_array(([array.len()], array.as_primitive::<$arrow>().values().to_vec()))?.upcast()
+		};
+	}
+
+	Ok(match array.data_type() {
+		DataType::Float32 => primitive_tensor!(Float32Type),
+		DataType::Float64 => primitive_tensor!(Float64Type),
+		DataType::Int8 => primitive_tensor!(Int8Type),
+		DataType::Int16 => primitive_tensor!(Int16Type),
+		DataType::Int32 => primitive_tensor!(Int32Type),
+		DataType::Int64 => primitive_tensor!(Int64Type),
+		DataType::UInt8 => primitive_tensor!(UInt8Type),
+		DataType::UInt16 => primitive_tensor!(UInt16Type),
+		DataType::UInt32 => primitive_tensor!(UInt32Type),
+		DataType::UInt64 => primitive_tensor!(UInt64Type),
+		DataType::Boolean => Tensor::from_array(([array.len()], array.as_boolean().values().iter().collect::<Vec<bool>>()))?.upcast(),
+		data_type => {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot create a tensor from an Arrow array of type {data_type}")));
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use arrow_array::{
+		Array, BooleanArray, FixedSizeListArray, Int32Array,
+		cast::AsArray,
+		types::{Float32Type, Int32Type}
+	};
+
+	use crate::value::{DynValue, Tensor};
+
+	#[test]
+	fn test_arrow_roundtrip() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]))?;
+		let array = tensor.to_arrow_array()?;
+		let list = array.as_fixed_size_list();
+		assert_eq!((list.len(), list.value_length()), (2, 3));
+		assert_eq!(&list.value(1).as_primitive::<Float32Type>().values()[..], &[4.0, 5.0, 6.0]);
+
+		let value = DynValue::from_arrow_array(&array)?;
+		assert_eq!(value.try_extract_raw_tensor::<f32>()?, tensor.extract_raw_tensor());
+
+		let tensor = Tensor::from_array(([3], vec![true, false, true]))?;
+		let array = tensor.to_arrow_array()?;
+		assert_eq!(array.as_boolean(), &BooleanArray::from(vec![true, false, true]));
+		let value = DynValue::from_arrow_array(&array)?;
+		assert_eq!(value.try_extract_raw_tensor::<bool>()?, tensor.extract_raw_tensor());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_arrow_sliced_list() -> crate::Result<()> {
+		let list = FixedSizeListArray::from_iter_primitive::<Int32Type, _, _>(
+			[Some([Some(1), Some(2)]), Some([Some(3), Some(4)]), Some([Some(5), Some(6)])],
+			2
+		)
+		.slice(1, 2);
+		let value = DynValue::from_arrow_array(&list)?;
+		assert_eq!(value.try_extract_raw_tensor::<i32>()?, (&[2_i64, 2][..], &[3, 4, 5, 6][..]));
+
+		assert!(DynValue::from_arrow_array(&Int32Array::from(vec![Some(1), None])).is_err());
+		assert!(Tensor::from_array(([1, 1, 1], vec![0.0_f32]))?.to_arrow_array().is_err());
+
+		Ok(())
+	}
+}
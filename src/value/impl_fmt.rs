@@ -0,0 +1,101 @@
+//! Human-readable [`Display`](fmt::Display) & [`Debug`](fmt::Debug) implementations for [`Value`]s.
+
+use std::fmt;
+
+use super::{DynTensor, DynTensorValueType, Value, ValueType, ValueTypeMarker};
+use crate::tensor::{PrimitiveTensorElementType, TensorElementType, f8e4m3, f8e5m2};
+
+/// The maximum number of elements shown when formatting a tensor.
+const PREVIEW_LEN: usize = 5;
+
+impl<Type: ValueTypeMarker + ?Sized> fmt::Display for Value<Type> {
+	/// Formats the value's type and, for tensors, its shape & first few elements, e.g.
+	/// `Tensor<f32>[16, 50257] (showing first 5: [0.0123, 0.0034, 0.1, 0.2, 0.3, ...])`.
+	///
+	/// Data of tensors which are not in CPU-accessible memory is not shown.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return write!(f, "{}", self.dtype());
+		};
+		write!(f, "Tensor<{ty}>[{}]", dimensions.iter().map(i64::to_string).collect::<Vec<_>>().join(", "))?;
+
+		if let Some(memory_info) = self.inner.memory_info.as_ref().filter(|m| !m.is_cpu_accessible()) {
+			return write!(f, " (on {})", memory_info.allocation_device().as_str());
+		}
+		let Ok(tensor) = self.view().downcast::<DynTensorValueType>() else {
+			return Ok(());
+		};
+		match ty {
+			TensorElementType::Float32 => fmt_primitive::<f32>(&tensor, f),
+			TensorElementType::Float64 => fmt_primitive::<f64>(&tensor, f),
+			#[cfg(feature = "half")]
+			TensorElementType::Float16 => fmt_primitive::<half::f16>(&tensor, f),
+			#[cfg(feature = "half")]
+			TensorElementType::Bfloat16 => fmt_primitive::<half::bf16>(&tensor, f),
+			TensorElementType::Float8E4M3 => fmt_primitive::<f8e4m3>(&tensor, f),
+			TensorElementType::Float8E5M2 => fmt_primitive::<f8e5m2>(&tensor, f),
+			TensorElementType::Int8 => fmt_primitive::<i8>(&tensor, f),
+			TensorElementType::Int16 => fmt_primitive::<i16>(&tensor, f),
+			TensorElementType::Int32 => fmt_primitive::<i32>(&tensor, f),
+			TensorElementType::Int64 => fmt_primitive::<i64>(&tensor, f),
+			TensorElementType::Uint8 => fmt_primitive::<u8>(&tensor, f),
+			TensorElementType::Uint16 => fmt_primitive::<u16>(&tensor, f),
+			TensorElementType::Uint32 => fmt_primitive::<u32>(&tensor, f),
+			TensorElementType::Uint64 => fmt_primitive::<u64>(&tensor, f),
+			TensorElementType::Bool => fmt_primitive::<bool>(&tensor, f),
+			TensorElementType::Int4 | TensorElementType::Uint4 => match tensor.try_extract_raw_int4_tensor() {
+				Ok((_, data)) => fmt_elements(&data, f),
+				Err(_) => Ok(())
+			},
+			TensorElementType::String => match tensor.try_extract_raw_string_tensor() {
+				Ok((_, data)) => fmt_elements(&data, f),
+				Err(_) => Ok(())
+			}
+		}
+	}
+}
+
+impl<Type: ValueTypeMarker + ?Sized> fmt::Debug for Value<Type> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+fn fmt_primitive<T: PrimitiveTensorElementType + fmt::Debug>(tensor: &DynTensor, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	match tensor.try_extract_raw_tensor::<T>() {
+		Ok((_, data)) => fmt_elements(data, f),
+		Err(_) => Ok(())
+	}
+}
+
+fn fmt_elements<T: fmt::Debug>(data: &[T], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+	if data.len() <= PREVIEW_LEN {
+		return write!(f, " (data: {data:?})");
+	}
+
+	write!(f, " (showing first {PREVIEW_LEN}: [")?;
+	for element in &data[..PREVIEW_LEN] {
+		write!(f, "{element:?}, ")?;
+	}
+	f.write_str("...])")
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::value::Tensor;
+
+	#[test]
+	fn test_value_display() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2, 3], vec![0.5_f32, 1.0, 1.5, 2.0, 2.5, 3.0]))?;
+		assert_eq!(tensor.to_string(), "Tensor<f32>[2, 3] (showing first 5: [0.5, 1.0, 1.5, 2.0, 2.5, ...])");
+		assert_eq!(format!("{:?}", tensor.into_dyn()), "Tensor<f32>[2, 3] (showing first 5: [0.5, 1.0, 1.5, 2.0, 2.5, ...])");
+
+		let tensor = Tensor::from_array(([2], vec![1_i64, -1]))?;
+		assert_eq!(tensor.to_string(), "Tensor<i64>[2] (data: [1, -1])");
+
+		let tensor = Tensor::from_string_array((vec![1_i64], vec!["hello".to_string()].into_boxed_slice()))?;
+		assert_eq!(tensor.to_string(), "Tensor<String>[1] (data: [\"hello\"])");
+
+		Ok(())
+	}
+}
@@ -0,0 +1,140 @@
+//! Conversion of tensor [`Value`]s to JSON, for diagnostic output.
+
+use serde_json::json;
+
+use super::{DynTensorValueType, Value, ValueType, ValueTypeMarker};
+use crate::{
+	error::{Error, ErrorCode, Result},
+	tensor::{TensorElementType, f8e4m3, f8e5m2}
+};
+
+impl<Type: ValueTypeMarker + ?Sized> Value<Type> {
+	/// Converts a tensor to a JSON object of the form `{"dtype": "float32", "shape": [2, 3], "data": [[...], [...]]}`,
+	/// where `data` holds the tensor's elements in nested arrays matching its shape, e.g. for dumping a model's outputs
+	/// while debugging.
+	///
+	/// Numbers are converted to JSON numbers; since JSON has no representation of NaN or infinity, non-finite floats are
+	/// converted to `null`. String tensors are converted to arrays of JSON strings. Unlike `ort`'s [`serde`]
+	/// representation, this is lossy, and is not meant to be converted back into a tensor.
+	///
+	/// Only tensors in CPU-accessible memory can be converted; sequences & maps are not supported.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]))?;
+	/// assert_eq!(
+	/// 	tensor.to_json()?,
+	/// 	serde_json::json!({
+	/// 		"dtype": "float32",
+	/// 		"shape": [2, 3],
+	/// 		"data": [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]
+	/// 	})
+	/// );
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`serde`]: https://docs.rs/serde
+	#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+	pub fn to_json(&self) -> Result<serde_json::Value> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot convert value of type {} to JSON; only tensors are supported", self.dtype())
+			));
+		};
+		let tensor = self.view().downcast::<DynTensorValueType>()?;
+
+		macro_rules! elements {
+			($t:ty) => {
+				elements!($t, |x: &$t| serde_json::Value::from(*x))
+			};
+			($t:ty, $convert:expr) => {
+				tensor.try_extract_raw_tensor::<$t>()?.1.iter().map($convert).collect::<Vec<serde_json::Value>>()
+			};
+		}
+
+		let elements = match ty {
+			TensorElementType::Float32 => elements!(f32),
+			TensorElementType::Float64 => elements!(f64),
+			#[cfg(feature = "half")]
+			TensorElementType::Float16 => elements!(half::f16, |x: &half::f16| serde_json::Value::from(x.to_f32())),
+			#[cfg(feature = "half")]
+			TensorElementType::Bfloat16 => elements!(half::bf16, |x: &half::bf16| serde_json::Value::from(x.to_f32())),
+			TensorElementType::Float8E4M3 => elements!(f8e4m3, |x: &f8e4m3| serde_json::Value::from(x.to_f32())),
+			TensorElementType::Float8E5M2 => elements!(f8e5m2, |x: &f8e5m2| serde_json::Value::from(x.to_f32())),
+			TensorElementType::Int8 => elements!(i8),
+			TensorElementType::Int16 => elements!(i16),
+			TensorElementType::Int32 => elements!(i32),
+			TensorElementType::Int64 => elements!(i64),
+			TensorElementType::Uint8 => elements!(u8),
+			TensorElementType::Uint16 => elements!(u16),
+			TensorElementType::Uint32 => elements!(u32),
+			TensorElementType::Uint64 => elements!(u64),
+			TensorElementType::Bool => elements!(bool),
+			TensorElementType::Int4 | TensorElementType::Uint4 => tensor.try_extract_raw_int4_tensor()?.1.into_iter().map(serde_json::Value::from).collect(),
+			TensorElementType::String => tensor.try_extract_raw_string_tensor()?.1.into_iter().map(serde_json::Value::from).collect()
+		};
+
+		Ok(json!({
+			"dtype": dtype_name(*ty),
+			"shape": dimensions,
+			"data": nest(&mut elements.into_iter(), dimensions)
+		}))
+	}
+}
+
+/// Returns the NumPy name of an element type, e.g. `float32`.
+fn dtype_name(ty: TensorElementType) -> &'static str {
+	match ty {
+		TensorElementType::Float32 => "float32",
+		TensorElementType::Float64 => "float64",
+		#[cfg(feature = "half")]
+		TensorElementType::Float16 => "float16",
+		#[cfg(feature = "half")]
+		TensorElementType::Bfloat16 => "bfloat16",
+		TensorElementType::Float8E4M3 => "float8_e4m3fn",
+		TensorElementType::Float8E5M2 => "float8_e5m2",
+		TensorElementType::Int8 => "int8",
+		TensorElementType::Int16 => "int16",
+		TensorElementType::Int32 => "int32",
+		TensorElementType::Int64 => "int64",
+		TensorElementType::Uint8 => "uint8",
+		TensorElementType::Uint16 => "uint16",
+		TensorElementType::Uint32 => "uint32",
+		TensorElementType::Uint64 => "uint64",
+		TensorElementType::Bool => "bool",
+		TensorElementType::Int4 => "int4",
+		TensorElementType::Uint4 => "uint4",
+		TensorElementType::String => "string"
+	}
+}
+
+/// Arranges the elements of a tensor of the given shape, in row-major order, into nested arrays.
+fn nest(elements: &mut impl Iterator<Item = serde_json::Value>, shape: &[i64]) -> serde_json::Value {
+	match shape.split_first() {
+		None => elements.next().unwrap_or(serde_json::Value::Null),
+		Some((&dim, rest)) => serde_json::Value::Array((0..dim.max(0)).map(|_| nest(elements, rest)).collect())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json::json;
+
+	use crate::value::Tensor;
+
+	#[test]
+	fn test_to_json() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2, 1, 2], vec![1_i64, -2, 3, -4]))?;
+		assert_eq!(tensor.to_json()?, json!({ "dtype": "int64", "shape": [2, 1, 2], "data": [[[1, -2]], [[3, -4]]] }));
+
+		let scalar = Tensor::from_array(((), vec![f32::NAN]))?;
+		assert_eq!(scalar.to_json()?, json!({ "dtype": "float32", "shape": [], "data": null }));
+
+		let strings = Tensor::from_string_array(([2], vec!["hello", "world"].into_boxed_slice()))?;
+		assert_eq!(strings.to_json()?, json!({ "dtype": "string", "shape": [2], "data": ["hello", "world"] }));
+		Ok(())
+	}
+}
@@ -258,6 +258,7 @@ impl<K: IntoTensorElementType + Debug + Clone + Hash + Eq + 'static, V: IntoTens
 					value: V::into_tensor_element_type()
 				},
 				drop: true,
+				upgradable: true,
 				memory_info: None,
 				_backing: Some(Box::new(values))
 			}),
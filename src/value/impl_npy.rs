@@ -0,0 +1,193 @@
+//! Support for reading & writing tensors in NumPy's [`.npy` format](https://numpy.org/doc/stable/reference/generated/numpy.lib.format.html).
+
+use std::{fs, path::Path};
+
+use super::{DowncastableTarget, DynTensor, DynTensorValueType, DynValueTypeMarker, Value, ValueType, ValueTypeMarker};
+use crate::{
+	error::{Error, ErrorCode, Result},
+	tensor::TensorElementType
+};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+/// The total length of the preamble & header is padded to a multiple of this many bytes, so the data is aligned.
+const HEADER_ALIGNMENT: usize = 64;
+
+impl<Type: ValueTypeMarker + ?Sized> Value<Type> {
+	/// Saves this tensor to a NumPy `.npy` file (format version 1.0), which can then be loaded in Python with
+	/// `numpy.load(path)`.
+	///
+	/// Only tensors in CPU-accessible memory can be saved; string tensors, `bf16` & float8 tensors (which NumPy does not
+	/// support without the `ml_dtypes` package), sequences & maps are not supported.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]))?;
+	/// # let path = std::env::temp_dir().join("ort-doctest-save-npy.npy");
+	/// tensor.save_npy(&path)?;
+	///
+	/// let loaded = Tensor::<f32>::load_npy(&path)?;
+	/// assert_eq!(loaded.extract_raw_tensor(), tensor.extract_raw_tensor());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn save_npy(&self, path: impl AsRef<Path>) -> Result<()> {
+		fs::write(path, self.to_npy_bytes()?).map_err(Error::wrap)
+	}
+
+	fn to_npy_bytes(&self) -> Result<Vec<u8>> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot save value of type {} as .npy; only tensors are supported", self.dtype())));
+		};
+		let descr = match ty.to_numpy_dtype() {
+			// these dtypes are only understood by NumPy when `ml_dtypes` is installed
+			Some(descr) if !matches!(descr.as_str(), "bfloat16" | "float8_e4m3fn" | "float8_e5m2") => descr,
+			_ => return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Tensors of type {ty} cannot be saved as .npy")))
+		};
+		let tensor = self.view().downcast::<DynTensorValueType>()?;
+		let data = tensor.data_bytes()?;
+
+		let shape = match dimensions.as_slice() {
+			[dim] => format!("({dim},)"),
+			dimensions => format!("({})", dimensions.iter().map(i64::to_string).collect::<Vec<_>>().join(", "))
+		};
+		let mut header = format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+		// magic + version + header length + header + terminating newline
+		let unpadded_len = MAGIC.len() + 2 + 2 + header.len() + 1;
+		header.extend(std::iter::repeat(' ').take((HEADER_ALIGNMENT - unpadded_len % HEADER_ALIGNMENT) % HEADER_ALIGNMENT));
+		header.push('\n');
+		let header_len = u16::try_from(header.len()).map_err(|_| Error::new_with_code(ErrorCode::InvalidArgument, "Tensor has too many dimensions to be saved as .npy"))?;
+
+		let mut bytes = Vec::with_capacity(MAGIC.len() + 4 + header.len() + data.len());
+		bytes.extend_from_slice(MAGIC);
+		bytes.extend_from_slice(&[1, 0]);
+		bytes.extend_from_slice(&header_len.to_le_bytes());
+		bytes.extend_from_slice(header.as_bytes());
+		bytes.extend_from_slice(data);
+		Ok(bytes)
+	}
+}
+
+impl<Type: ValueTypeMarker + DowncastableTarget + ?Sized> Value<Type> {
+	/// Loads a tensor from a NumPy `.npy` file, like one created with `numpy.save(path, array)` or
+	/// [`Value::save_npy`]. The tensor is allocated in CPU memory.
+	///
+	/// Only arrays of numeric or boolean types in native byte order & C-contiguous layout (i.e. not
+	/// `fortran_order`) are supported. Loading into a strongly typed [`Tensor<T>`](super::Tensor) fails if the array's
+	/// element type is not `T`.
+	pub fn load_npy(path: impl AsRef<Path>) -> Result<Value<Type>> {
+		let path = path.as_ref();
+		let bytes = fs::read(path).map_err(Error::wrap)?;
+		let value = from_npy_bytes(&bytes).map_err(|e| Error::new_with_code(e.code(), format!("Failed to load `{}`: {e}", path.display())))?;
+		if Type::can_downcast(value.dtype()) {
+			Ok(unsafe { std::mem::transmute::<Value<DynValueTypeMarker>, Value<Type>>(value) })
+		} else {
+			Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot load {} from `{}` as {}", value.dtype(), path.display(), Type::format())))
+		}
+	}
+}
+
+fn from_npy_bytes(bytes: &[u8]) -> Result<Value<DynValueTypeMarker>> {
+	let invalid = |msg: &str| Error::new_with_code(ErrorCode::InvalidArgument, format!("Invalid .npy file: {msg}"));
+
+	let rest = bytes.strip_prefix(MAGIC).ok_or_else(|| invalid("missing magic string"))?;
+	let (header_len, rest) = match rest {
+		[1, _, a, b, rest @ ..] => (u16::from_le_bytes([*a, *b]) as usize, rest),
+		[2 | 3, _, a, b, c, d, rest @ ..] => (u32::from_le_bytes([*a, *b, *c, *d]) as usize, rest),
+		[major, ..] => return Err(invalid(&format!("unsupported format version {major}"))),
+		[] => return Err(invalid("file is truncated"))
+	};
+	if rest.len() < header_len {
+		return Err(invalid("file is truncated"));
+	}
+	let (header, data) = rest.split_at(header_len);
+	let header = std::str::from_utf8(header).map_err(|_| invalid("header is not valid UTF-8"))?;
+
+	let descr = header_value(header, "descr")
+		.and_then(|v| {
+			let quote = v.chars().next().filter(|c| matches!(c, '\'' | '"'))?;
+			v[1..].split(quote).next()
+		})
+		.ok_or_else(|| invalid("missing `descr`"))?;
+	if header_value(header, "fortran_order").ok_or_else(|| invalid("missing `fortran_order`"))?.starts_with("True") {
+		return Err(Error::new_with_code(ErrorCode::NotImplemented, "Fortran-order .npy arrays are not supported"));
+	}
+	let shape = header_value(header, "shape")
+		.and_then(|v| v.strip_prefix('('))
+		.and_then(|v| v.split(')').next())
+		.ok_or_else(|| invalid("missing `shape`"))?
+		.split(',')
+		.map(str::trim)
+		.filter(|d| !d.is_empty())
+		.map(|d| d.parse::<i64>().map_err(|_| invalid(&format!("invalid dimension `{d}`"))))
+		.collect::<Result<Vec<_>>>()?;
+
+	let ty = TensorElementType::from_numpy_dtype(descr)?;
+	Ok(DynTensor::from_bytes(ty, shape, data)?.into_dyn())
+}
+
+/// Returns the text following the given key in an `.npy` header dictionary, e.g. `'<f4', 'fortran_order': ...` for
+/// `descr`.
+fn header_value<'h>(header: &'h str, key: &str) -> Option<&'h str> {
+	let start = header.find(&format!("'{key}'")).or_else(|| header.find(&format!("\"{key}\"")))? + key.len() + 2;
+	Some(header[start..].trim_start().strip_prefix(':')?.trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{HEADER_ALIGNMENT, from_npy_bytes};
+	use crate::{
+		tensor::TensorElementType,
+		value::{DynTensor, DynValue, Tensor}
+	};
+
+	#[test]
+	fn test_npy_roundtrip() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2, 1, 3], vec![1_i64, -2, 3, -4, 5, i64::MAX]))?;
+		let bytes = tensor.to_npy_bytes()?;
+		assert_eq!((bytes.len() - 6 * 8) % HEADER_ALIGNMENT, 0);
+
+		let loaded = from_npy_bytes(&bytes)?;
+		assert_eq!(loaded.dtype(), tensor.dtype());
+		assert_eq!(loaded.try_extract_raw_tensor::<i64>()?, tensor.extract_raw_tensor());
+
+		let scalar = Tensor::from_array(((), vec![true]))?;
+		let loaded = from_npy_bytes(&scalar.to_npy_bytes()?)?;
+		assert_eq!(loaded.try_extract_raw_tensor::<bool>()?, (&[][..], &[true][..]));
+
+		let float8 = DynTensor::from_bytes(TensorElementType::Float8E4M3, vec![2], &[0x38, 0x40])?;
+		assert!(float8.to_npy_bytes().is_err());
+
+		Ok(())
+	}
+
+	fn npy_bytes(header: &str, data: &[u8]) -> Vec<u8> {
+		let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+		bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+		bytes.extend_from_slice(header.as_bytes());
+		bytes.extend_from_slice(data);
+		bytes
+	}
+
+	#[test]
+	fn test_npy_load_numpy_header() -> crate::Result<()> {
+		let data: Vec<u8> = [0.0_f32, 1.0, 2.0, 3.0].iter().flat_map(|x| x.to_ne_bytes()).collect();
+
+		// header as written by `numpy.save` for `np.arange(4, dtype=np.float32)`
+		let header = format!("{:<117}\n", "{'descr': '<f4', 'fortran_order': False, 'shape': (4,), }");
+		let loaded: DynValue = from_npy_bytes(&npy_bytes(&header, &data))?;
+		assert_eq!(loaded.try_extract_raw_tensor::<f32>()?, (&[4_i64][..], &[0.0, 1.0, 2.0, 3.0][..]));
+
+		let header = header.replace("False", "True");
+		assert!(from_npy_bytes(&npy_bytes(&header, &data)).is_err());
+
+		let header = header.replace("True", "False").replace("(4,)", "(5,)");
+		assert!(from_npy_bytes(&npy_bytes(&header, &data)).is_err());
+
+		// shapes whose size overflows must be rejected rather than wrapping around
+		let header = header.replace("(5,)", "(4294967296, 4294967296, 4294967296, 4)");
+		assert!(from_npy_bytes(&npy_bytes(&header, &data)).is_err());
+
+		Ok(())
+	}
+}
@@ -140,6 +140,7 @@ impl<T: ValueTypeMarker + DowncastableTarget + Debug + Sized + 'static> Value<Se
 				// 2. `CreateValue` internally uses the first value to determine the element type, so we do the same here
 				dtype: ValueType::Sequence(Box::new(values[0].inner.dtype.clone())),
 				drop: true,
+				upgradable: true,
 				memory_info: None,
 				_backing: Some(Box::new(values))
 			}),
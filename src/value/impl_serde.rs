@@ -0,0 +1,128 @@
+//! [`serde`] support for tensor [`Value`]s.
+//!
+//! Tensors are serialized as a struct of the form `{ dtype: str, shape: [i64], data: bytes }`, where:
+//! - `dtype` is the [NumPy array-protocol type string](https://numpy.org/doc/stable/reference/arrays.interface.html#object.__array_interface__)
+//!   of the element type, e.g. `<f4` for `f32` on little-endian targets, or `bfloat16` for [`half::bf16`];
+//! - `shape` is the shape of the tensor;
+//! - `data` is the raw contents of the tensor in native byte order & standard (C-contiguous) layout.
+//!
+//! This means a serialized tensor can be read in Python with `np.frombuffer(data, dtype=dtype).reshape(shape)`, and
+//! that round-tripping a tensor is always exact.
+
+use std::fmt;
+
+use serde::{
+	Deserialize, Deserializer, Serialize, Serializer,
+	de::{self, SeqAccess, Visitor},
+	ser::{self, SerializeStruct}
+};
+
+use super::{DowncastableTarget, DynTensor, DynTensorValueType, DynValue, DynValueTypeMarker, Value, ValueType, ValueTypeMarker};
+use crate::{error::Result, tensor::TensorElementType};
+
+struct Bytes<'b>(&'b [u8]);
+
+impl Serialize for Bytes<'_> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_bytes(self.0)
+	}
+}
+
+struct ByteBuf(Vec<u8>);
+
+impl<'de> Deserialize<'de> for ByteBuf {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct ByteBufVisitor;
+
+		impl<'de> Visitor<'de> for ByteBufVisitor {
+			type Value = ByteBuf;
+
+			fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				f.write_str("a byte array")
+			}
+
+			fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<ByteBuf, E> {
+				Ok(ByteBuf(v.to_vec()))
+			}
+
+			fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<ByteBuf, E> {
+				Ok(ByteBuf(v))
+			}
+
+			fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteBuf, A::Error> {
+				let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+				while let Some(byte) = seq.next_element()? {
+					bytes.push(byte);
+				}
+				Ok(ByteBuf(bytes))
+			}
+		}
+
+		deserializer.deserialize_byte_buf(ByteBufVisitor)
+	}
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Tensor")]
+struct TensorRepr {
+	dtype: String,
+	shape: Vec<i64>,
+	data: ByteBuf
+}
+
+impl<Type: ValueTypeMarker + ?Sized> Serialize for Value<Type> {
+	/// Serializes a tensor, including its element type & shape. Only tensors in CPU-accessible memory can be serialized;
+	/// string tensors, sequences & maps are not supported.
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(ser::Error::custom(format!("Cannot serialize value of type {}; only tensors are supported", self.dtype())));
+		};
+		let dtype = ty.to_numpy_dtype().ok_or_else(|| ser::Error::custom("String tensors cannot be serialized"))?;
+		let tensor = self.view().downcast::<DynTensorValueType>().map_err(ser::Error::custom)?;
+		let data = tensor.data_bytes().map_err(ser::Error::custom)?;
+
+		let mut state = serializer.serialize_struct("Tensor", 3)?;
+		state.serialize_field("dtype", &dtype)?;
+		state.serialize_field("shape", dimensions)?;
+		state.serialize_field("data", &Bytes(data))?;
+		state.end()
+	}
+}
+
+fn tensor_from_repr(repr: TensorRepr) -> Result<DynValue> {
+	let ty = TensorElementType::from_numpy_dtype(&repr.dtype)?;
+	Ok(DynTensor::from_bytes(ty, repr.shape, &repr.data.0)?.into_dyn())
+}
+
+impl<'de, Type: ValueTypeMarker + DowncastableTarget + ?Sized> Deserialize<'de> for Value<Type> {
+	/// Deserializes a tensor into CPU memory. Deserializing into a strongly typed [`Tensor<T>`](super::Tensor) fails if
+	/// the serialized element type is not `T`.
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let value = tensor_from_repr(TensorRepr::deserialize(deserializer)?).map_err(de::Error::custom)?;
+		if Type::can_downcast(value.dtype()) {
+			Ok(unsafe { std::mem::transmute::<Value<DynValueTypeMarker>, Value<Type>>(value) })
+		} else {
+			Err(de::Error::custom(format!("Cannot deserialize {} as {}", value.dtype(), Type::format())))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::value::{DynValue, Tensor};
+
+	#[test]
+	fn test_serde_roundtrip() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2, 2], vec![1.0_f32, f32::MIN_POSITIVE, -0.1, f32::MAX]))?;
+
+		let json = serde_json::to_string(&tensor).map_err(crate::Error::wrap)?;
+		let deserialized: Tensor<f32> = serde_json::from_str(&json).map_err(crate::Error::wrap)?;
+		assert_eq!(deserialized.extract_raw_tensor(), tensor.extract_raw_tensor());
+
+		let dyn_value: DynValue = serde_json::from_str(&json).map_err(crate::Error::wrap)?;
+		assert_eq!(dyn_value.dtype(), tensor.dtype());
+		assert!(serde_json::from_str::<Tensor<i64>>(&json).is_err());
+
+		Ok(())
+	}
+}
@@ -10,14 +10,14 @@ use std::{
 #[cfg(feature = "ndarray")]
 use ndarray::{ArcArray, Array, ArrayView, CowArray, Dimension};
 
-use super::{DynTensor, Tensor, TensorRefMut, calculate_tensor_size};
+use super::{DynTensor, Tensor, TensorRef, TensorRefMut, calculate_tensor_size};
 use crate::{
 	AsPointer,
 	error::{Error, ErrorCode, Result, assert_non_null_pointer},
 	memory::{AllocationDevice, Allocator, AllocatorType, MemoryInfo, MemoryType},
 	ortsys,
 	tensor::{PrimitiveTensorElementType, TensorElementType, Utf8Data},
-	value::{DynValue, Value, ValueInner, ValueType}
+	value::{DynValue, Value, ValueInner, ValueRef, ValueType}
 };
 
 impl Tensor<String> {
@@ -85,6 +85,7 @@ impl Tensor<String> {
 				},
 				memory_info: MemoryInfo::from_value(value_ptr),
 				drop: true,
+				upgradable: true,
 				_backing: None
 			}),
 			_markers: PhantomData
@@ -92,6 +93,65 @@ impl Tensor<String> {
 	}
 }
 
+impl DynTensor {
+	/// Creates a CPU tensor of the given element type & shape, copying its contents from `data`, which holds the raw
+	/// elements in native byte order.
+	pub(crate) fn from_bytes(ty: TensorElementType, shape: Vec<i64>, data: &[u8]) -> Result<DynTensor> {
+		if let Some(dim) = shape.iter().find(|d| **d < 0) {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Invalid tensor dimension {dim} in shape {shape:?}")));
+		}
+		// the shape may come from untrusted input (e.g. a deserialized tensor), so guard against overflow
+		let too_large = || Error::new_with_code(ErrorCode::InvalidArgument, format!("Tensor of type {ty} and shape {shape:?} is too large"));
+		let elements = shape.iter().try_fold(1usize, |acc, &d| acc.checked_mul(d as usize)).ok_or_else(too_large)?;
+		if ty.byte_size().is_some_and(|size| elements.checked_mul(size).is_none()) {
+			return Err(too_large());
+		}
+		let Some(len) = ty.data_size(elements) else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot create a string tensor from raw data"));
+		};
+		if data.len() != len {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Tensor of type {ty} and shape {shape:?} should have {len} bytes of data; got {}", data.len())
+			));
+		}
+
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![
+			unsafe CreateTensorAsOrtValue(
+				Allocator::default().ptr().cast_mut(),
+				shape.as_ptr(),
+				shape.len(),
+				ty.into(),
+				&mut value_ptr
+			)?;
+			nonNull(value_ptr)
+		];
+
+		let shape_len = shape.len();
+		let mut tensor = Value {
+			inner: Arc::new(ValueInner {
+				ptr: unsafe { NonNull::new_unchecked(value_ptr) },
+				dtype: ValueType::Tensor {
+					ty,
+					dimensions: shape,
+					dimension_symbols: vec![None; shape_len]
+				},
+				drop: true,
+				upgradable: true,
+				memory_info: MemoryInfo::from_value(value_ptr),
+				_backing: None
+			}),
+			_markers: PhantomData
+		};
+		if len > 0 {
+			let data_ptr = tensor.data_ptr_mut()?;
+			unsafe { ptr::copy_nonoverlapping(data.as_ptr(), data_ptr.cast::<u8>(), len) };
+		}
+		Ok(tensor)
+	}
+}
+
 impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 	/// Construct a tensor in a given allocator with a given shape and datatype. The data contained in the
 	/// value will be zero-allocated on the allocation device.
@@ -138,6 +198,7 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 					dimension_symbols: vec![None; shape_len]
 				},
 				drop: true,
+				upgradable: true,
 				memory_info: MemoryInfo::from_value(value_ptr),
 				_backing: None
 			}),
@@ -170,6 +231,18 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 	/// # }
 	/// ```
 	///
+	/// `T` can be any [`PrimitiveTensorElementType`], including `half::f16` & `half::bf16` with the `half` feature
+	/// enabled, so fp16 models can be fed directly from fp16 arrays without any casting:
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// #[cfg(all(feature = "half", feature = "ndarray"))]
+	/// let tensor = Tensor::from_array(ndarray::Array2::<half::f16>::zeros((4, 4)).view())?;
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
 	/// Creating string tensors requires a separate method; see [`DynTensor::from_string_array`].
 	///
 	/// Note that data provided in an `ndarray` may be copied in some circumstances:
@@ -215,12 +288,97 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 					dimension_symbols: vec![None; shape_len]
 				},
 				drop: true,
+				upgradable: true,
 				memory_info: Some(memory_info),
 				_backing: Some(guard)
 			}),
 			_markers: PhantomData
 		})
 	}
+
+	/// Construct a tensor from an array of a different element type, converting each element to `T` in a single pass.
+	///
+	/// This is useful for e.g. feeding `u16` or `u32` token IDs to a model which expects `i64` inputs. Only lossless
+	/// conversions (i.e. those implementing [`From`], like `u16` -> `i64` or `f16` -> `f32`) are supported, so values
+	/// are never truncated, wrapped, or saturated; narrowing conversions must be performed explicitly.
+	///
+	/// `input` can be any type accepted by [`Tensor::from_array`]. The data is always copied.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tokens: Vec<u16> = vec![50256, 464, 2068];
+	/// let tensor = Tensor::<i64>::from_array_casting(([1, 3], &tokens[..]))?;
+	/// assert_eq!(tensor.extract_raw_tensor().1, &[50256, 464, 2068]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn from_array_casting<S: Copy>(input: impl IntoValueTensor<Item = S>) -> Result<Tensor<T>>
+	where
+		T: From<S> + Clone + 'static
+	{
+		let (shape, data) = input.ref_parts()?;
+		let data: Vec<T> = data.iter().map(|x| T::from(*x)).collect();
+		Tensor::from_array((shape, data))
+	}
+
+	/// Creates a tensor view over a borrowed slice with the given shape, without copying the data.
+	///
+	/// Unlike passing a `(shape, &[T])` tuple to [`Tensor::from_array`], which copies the slice, the returned
+	/// [`TensorRef`] points directly to `data`, and borrows it for as long as the tensor is alive. This makes it useful for
+	/// repeatedly running a session on a buffer that is reused between runs.
+	///
+	/// Since the tensor does not own its data, it cannot be upgraded to an owned [`Tensor`];
+	/// [`ValueRef::try_upgrade`] will always fail, even on views created from it.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let buffer = vec![1_i64, 2, 3, 4, 5, 6];
+	/// let tensor = Tensor::from_shape_slice([2, 3], &buffer)?;
+	/// assert_eq!(tensor.extract_raw_tensor().1, &buffer[..]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn from_shape_slice<'a>(shape: impl ToDimensions, data: &'a [T]) -> Result<TensorRef<'a, T>> {
+		let memory_info = MemoryInfo::new(AllocationDevice::CPU, 0, AllocatorType::Arena, MemoryType::CPUInput)?;
+
+		let shape = shape.to_dimensions(Some(data.len()))?;
+		let shape_ptr: *const i64 = shape.as_ptr();
+		let shape_len = shape.len();
+
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		// ONNX Runtime never writes to input tensors, and the returned `TensorRef` does not allow mutable access, so it is
+		// safe to cast away the constness here.
+		ortsys![
+			unsafe CreateTensorWithDataAsOrtValue(
+				memory_info.ptr(),
+				data.as_ptr().cast_mut().cast(),
+				std::mem::size_of_val(data),
+				shape_ptr,
+				shape_len,
+				T::into_tensor_element_type().into(),
+				&mut value_ptr
+			)?;
+			nonNull(value_ptr)
+		];
+
+		Ok(ValueRef::new(Value {
+			inner: Arc::new(ValueInner {
+				ptr: unsafe { NonNull::new_unchecked(value_ptr) },
+				dtype: ValueType::Tensor {
+					ty: T::into_tensor_element_type(),
+					dimensions: shape,
+					dimension_symbols: vec![None; shape_len]
+				},
+				drop: true,
+				upgradable: false,
+				memory_info: Some(memory_info),
+				_backing: None
+			}),
+			_markers: PhantomData
+		}))
+	}
 }
 
 impl<'a, T: PrimitiveTensorElementType + Debug> TensorRefMut<'a, T> {
@@ -278,6 +436,7 @@ impl<'a, T: PrimitiveTensorElementType + Debug> TensorRefMut<'a, T> {
 					dimension_symbols: vec![None; shape_len]
 				},
 				drop: true,
+				upgradable: true,
 				memory_info: Some(info),
 				_backing: None
 			}),
@@ -8,7 +8,7 @@ use super::{Tensor, TensorValueTypeMarker, calculate_tensor_size};
 use crate::tensor::{extract_primitive_array, extract_primitive_array_mut};
 use crate::{
 	AsPointer,
-	error::{Error, ErrorCode, Result},
+	error::{Error, ErrorCode, ErrorKind, Result},
 	ortsys,
 	tensor::{PrimitiveTensorElementType, TensorElementType},
 	value::{Value, ValueType}
@@ -17,7 +17,11 @@ use crate::{
 impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// Attempt to extract the underlying data of type `T` into a read-only [`ndarray::ArrayView`].
 	///
+	/// The returned array has dynamic dimensionality, with the shape of the tensor at runtime, so this works for
+	/// outputs whose rank isn't known ahead of time.
+	///
 	/// See also:
+	/// - [`Tensor::try_extract_fixed`], for extracting an array of a known dimensionality like [`ndarray::Ix2`].
 	/// - the mutable counterpart of this function, [`Tensor::try_extract_tensor_mut`].
 	/// - the infallible counterpart, [`Tensor::extract_tensor`], for typed [`Tensor<T>`]s.
 	/// - the alternative function for strings, [`Tensor::try_extract_string_tensor`].
@@ -58,16 +62,82 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 				if *ty == T::into_tensor_element_type() {
 					Ok(extract_primitive_array(IxDyn(&dimensions.iter().map(|&n| n as usize).collect::<Vec<_>>()), self.ptr())?)
 				} else {
-					Err(Error::new_with_code(
-						ErrorCode::InvalidArgument,
-						format!("Cannot extract Tensor<{}> from Tensor<{}>", T::into_tensor_element_type(), ty)
-					))
+					Err(dtype_mismatch(T::into_tensor_element_type(), *ty))
 				}
 			}
 			t => Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract a Tensor<{}> from {t}", T::into_tensor_element_type())))
 		}
 	}
 
+	/// Attempt to extract the underlying data of type `T` into an owned [`ndarray::ArrayD`].
+	///
+	/// Unlike [`Tensor::try_extract_tensor`], which returns a zero-copy view into the tensor's memory, this **copies**
+	/// the tensor's data. The returned array does not borrow from the value, so it can outlive the value (or the
+	/// [`SessionOutputs`](crate::session::SessionOutputs) it came from) and be moved across threads or `await` points.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let value = Tensor::from_array(([2, 2], vec![1.0_f32, 2.0, 3.0, 4.0]))?.into_dyn();
+	/// let array = value.try_extract_tensor_owned::<f32>()?;
+	/// drop(value);
+	/// assert_eq!(array, ndarray::array![[1.0, 2.0], [3.0, 4.0]].into_dyn());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// May return an error if:
+	/// - This is a [`DynValue`], and the value is not actually a tensor.
+	/// - The provided type `T` does not match the tensor's element type.
+	/// - The tensor's data is not allocated in CPU memory.
+	///
+	/// [`DynValue`]: crate::value::DynValue
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn try_extract_tensor_owned<T: PrimitiveTensorElementType + Clone>(&self) -> Result<ndarray::ArrayD<T>> {
+		self.try_extract_tensor::<T>().map(|view| view.to_owned())
+	}
+
+	/// Attempt to extract the underlying data of type `T` into a read-only [`ndarray::ArrayView`] of a fixed
+	/// dimensionality `D`, for when the rank of the tensor is known ahead of time.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let value = Tensor::from_array(([2, 3], vec![1_i64, 2, 3, 4, 5, 6]))?.into_dyn();
+	///
+	/// let extracted = value.try_extract_fixed::<i64, ndarray::Ix2>()?;
+	/// assert_eq!(extracted[[1, 2]], 6);
+	///
+	/// assert!(value.try_extract_fixed::<i64, ndarray::Ix3>().is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Errors
+	/// May return an error if:
+	/// - This is a [`DynValue`], and the value is not actually a tensor.
+	/// - The provided type `T` does not match the tensor's element type.
+	/// - The number of dimensions of the tensor does not match `D`.
+	/// - The tensor's data is not allocated in CPU memory.
+	///
+	/// [`DynValue`]: crate::value::DynValue
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn try_extract_fixed<T: PrimitiveTensorElementType, D: ndarray::Dimension>(&self) -> Result<ndarray::ArrayView<'_, T, D>> {
+		self.try_extract_tensor::<T>()?.into_dimensionality::<D>().map_err(|_| {
+			Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Cannot extract a {}-dimensional array from a tensor of shape {:?}",
+					D::NDIM.unwrap_or_default(),
+					self.dtype().tensor_dimensions().map(Vec::as_slice).unwrap_or_default()
+				)
+			)
+		})
+	}
+
 	/// Attempt to extract the scalar from a tensor of type `T`.
 	///
 	/// ```
@@ -166,10 +236,7 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 				if *ty == T::into_tensor_element_type() {
 					Ok(extract_primitive_array_mut(IxDyn(&dimensions.iter().map(|&n| n as usize).collect::<Vec<_>>()), self.ptr_mut())?)
 				} else {
-					Err(Error::new_with_code(
-						ErrorCode::InvalidArgument,
-						format!("Cannot extract Tensor<{}> from Tensor<{}>", T::into_tensor_element_type(), ty)
-					))
+					Err(dtype_mismatch(T::into_tensor_element_type(), *ty))
 				}
 			}
 			t => Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract Tensor<{}> from {t}", T::into_tensor_element_type())))
@@ -221,16 +288,115 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 					let len = calculate_tensor_size(dimensions);
 					Ok((dimensions, unsafe { std::slice::from_raw_parts(output_array_ptr, len) }))
 				} else {
-					Err(Error::new_with_code(
-						ErrorCode::InvalidArgument,
-						format!("Cannot extract Tensor<{}> from Tensor<{}>", T::into_tensor_element_type(), ty)
-					))
+					Err(dtype_mismatch(T::into_tensor_element_type(), *ty))
 				}
 			}
 			t => Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract Tensor<{}> from {t}", T::into_tensor_element_type())))
 		}
 	}
 
+	/// Borrows the tensor's data as a flat slice of `T`, in standard (row-major) layout.
+	///
+	/// Returns an error with [`ErrorKind::DTypeMismatch`] if `T` does not match the tensor's element type. See
+	/// [`Tensor::try_extract_raw_tensor`] to also get the tensor's shape.
+	///
+	/// ```
+	/// # use ort::{ErrorKind, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let value = Tensor::from_array(([2, 2], vec![1.0_f32, 2.0, 3.0, 4.0]))?.into_dyn();
+	/// assert_eq!(value.as_slice::<f32>()?, &[1.0, 2.0, 3.0, 4.0]);
+	/// assert!(matches!(value.as_slice::<i64>().unwrap_err().kind(), ErrorKind::DTypeMismatch { .. }));
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn as_slice<T: PrimitiveTensorElementType>(&self) -> Result<&[T]> {
+		Ok(self.try_extract_raw_tensor()?.1)
+	}
+
+	/// Mutably borrows the tensor's data as a flat slice of `T`, in standard (row-major) layout, e.g. to post-process a
+	/// session's output in place without copying it.
+	///
+	/// The slice points directly into the tensor's memory, which for outputs of [`Session::run`] is owned by ONNX
+	/// Runtime. Only tensors in CPU-accessible memory can be borrowed; otherwise, an error is returned. Since this takes
+	/// `&mut self`, no other view of this value can be alive while the slice is borrowed, but the data is still shared
+	/// with anything else that points to the same memory, like the buffer a [`TensorRefMut`] was created from.
+	///
+	/// Returns an error with [`ErrorKind::DTypeMismatch`] if `T` does not match the tensor's element type.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut logits = Tensor::from_array(([4], vec![1.0_f32, 2.0, 3.0, 4.0]))?.into_dyn();
+	///
+	/// // softmax, in place
+	/// let data = logits.as_mut_slice::<f32>()?;
+	/// let max = data.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+	/// data.iter_mut().for_each(|x| *x = (*x - max).exp());
+	/// let sum: f32 = data.iter().sum();
+	/// data.iter_mut().for_each(|x| *x /= sum);
+	///
+	/// assert!((logits.as_slice::<f32>()?.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// [`Session::run`]: crate::session::Session::run
+	/// [`TensorRefMut`]: crate::value::TensorRefMut
+	#[doc(alias("tensor_data_mut", "GetTensorMutableData"))]
+	pub fn as_mut_slice<T: PrimitiveTensorElementType>(&mut self) -> Result<&mut [T]> {
+		Ok(self.try_extract_raw_tensor_mut()?.1)
+	}
+
+	/// Copies the tensor's data into a flat [`Vec<T>`], in standard (row-major) layout.
+	///
+	/// Returns an error with [`ErrorKind::DTypeMismatch`] if `T` does not match the tensor's element type.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let value = Tensor::from_array(([3], vec![1_i64, 2, 3]))?.into_dyn();
+	/// assert_eq!(value.to_vec::<i64>()?, vec![1, 2, 3]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn to_vec<T: PrimitiveTensorElementType + Clone>(&self) -> Result<Vec<T>> {
+		Ok(self.as_slice()?.to_vec())
+	}
+
+	/// Copies the tensor's data into `buf`, replacing its previous contents, and returns the tensor's shape.
+	///
+	/// The data is copied as a flat sequence in standard (row-major) layout, i.e. the element at index `[i, j]` of a
+	/// tensor with shape `[N, M]` is found at `buf[i * M + j]`. `buf` is only reallocated if its capacity is too small
+	/// to hold the tensor, so reusing the same buffer across calls (e.g. in a generation loop) avoids allocating on
+	/// every extraction.
+	///
+	/// Returns an error with [`ErrorKind::DTypeMismatch`] if `T` does not match the tensor's element type, in which
+	/// case `buf` is left untouched.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let mut buf = Vec::with_capacity(6);
+	///
+	/// let value = Tensor::from_array(([2, 3], vec![1.0_f32, 2.0, 3.0, 4.0, 5.0, 6.0]))?.into_dyn();
+	/// let shape = value.try_extract_into::<f32>(&mut buf)?;
+	/// assert_eq!(shape, [2, 3]);
+	/// assert_eq!(buf, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+	///
+	/// let value = Tensor::from_array(([2], vec![7.0_f32, 8.0]))?.into_dyn();
+	/// value.try_extract_into::<f32>(&mut buf)?;
+	/// assert_eq!(buf, [7.0, 8.0]);
+	/// assert!(buf.capacity() >= 6);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn try_extract_into<T: PrimitiveTensorElementType + Clone + 'static>(&self, buf: &mut Vec<T>) -> Result<&[i64]> {
+		let (shape, data) = self.try_extract_raw_tensor()?;
+		buf.clear();
+		buf.extend_from_slice(data);
+		Ok(shape)
+	}
+
 	/// Attempt to extract the underlying data into a "raw" view tuple, consisting of the tensor's dimensions and a
 	/// mutable view into its data.
 	///
@@ -274,10 +440,7 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 					let len = calculate_tensor_size(dimensions);
 					Ok((dimensions, unsafe { std::slice::from_raw_parts_mut(output_array_ptr, len) }))
 				} else {
-					Err(Error::new_with_code(
-						ErrorCode::InvalidArgument,
-						format!("Cannot extract Tensor<{}> from Tensor<{}>", T::into_tensor_element_type(), ty)
-					))
+					Err(dtype_mismatch(T::into_tensor_element_type(), *ty))
 				}
 			}
 			t => Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract Tensor<{}> from {t:?}", T::into_tensor_element_type())))
@@ -415,8 +578,57 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 		}
 	}
 
+	/// Attempt to extract the data of an [`Int4`](TensorElementType::Int4) or [`Uint4`](TensorElementType::Uint4)
+	/// tensor into a Rust `ndarray`, unpacking each 4-bit element into an `i8`.
+	///
+	/// Elements of `Int4` tensors are sign-extended into the range `-8..=7`; elements of `Uint4` tensors are in the range
+	/// `0..=15`.
+	#[cfg(feature = "ndarray")]
+	#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+	pub fn try_extract_int4_tensor(&self) -> Result<ndarray::ArrayD<i8>> {
+		let (dimensions, data) = self.try_extract_raw_int4_tensor()?;
+		Ok(ndarray::Array::from_shape_vec(IxDyn(&dimensions.iter().map(|&n| n as usize).collect::<Vec<_>>()), data)
+			.expect("Shape extracted from tensor didn't match tensor contents"))
+	}
+
+	/// Attempt to extract the data of an [`Int4`](TensorElementType::Int4) or [`Uint4`](TensorElementType::Uint4)
+	/// tensor into a "raw" data tuple, consisting of the tensor's dimensions and an owned `Vec` of its data, with each
+	/// 4-bit element unpacked into an `i8`.
+	///
+	/// ONNX packs two 4-bit elements into each byte, with the first element in the low nibble. Elements of `Int4` tensors
+	/// are sign-extended into the range `-8..=7`; elements of `Uint4` tensors are in the range `0..=15`.
+	pub fn try_extract_raw_int4_tensor(&self) -> Result<(&[i64], Vec<i8>)> {
+		match self.dtype() {
+			ValueType::Tensor { ty, dimensions, .. } => {
+				let signed = match ty {
+					TensorElementType::Int4 => true,
+					TensorElementType::Uint4 => false,
+					_ => return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract 4-bit integers from Tensor<{ty}>")))
+				};
+				let mem = self.memory_info();
+				if !mem.is_cpu_accessible() {
+					return Err(Error::new(format!("Cannot extract from value on device `{}`, which is not CPU accessible", mem.allocation_device().as_str())));
+				}
+
+				let len = calculate_tensor_size(dimensions);
+				let data = self
+					.data_bytes()?
+					.iter()
+					.flat_map(|byte| [byte & 0x0f, byte >> 4])
+					.take(len)
+					.map(|nibble| if signed { ((nibble << 4) as i8) >> 4 } else { nibble as i8 })
+					.collect();
+				Ok((dimensions, data))
+			}
+			t => Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot extract 4-bit integers from {t}")))
+		}
+	}
+
 	/// Returns the shape of the tensor.
 	///
+	/// This can also be used on [`DynValue`](crate::value::DynValue)s, like those returned by
+	/// [`Session::run`](crate::session::Session::run), to inspect their shape without extracting their data.
+	///
 	/// ```
 	/// # use ort::{memory::Allocator, value::Tensor};
 	/// # fn main() -> ort::Result<()> {
@@ -427,6 +639,7 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	/// # 	Ok(())
 	/// # }
 	/// ```
+	#[doc(alias = "GetDimensions")]
 	pub fn shape(&self) -> Result<Vec<i64>> {
 		let mut tensor_info_ptr: *mut ort_sys::OrtTensorTypeAndShapeInfo = std::ptr::null_mut();
 		ortsys![unsafe GetTensorTypeAndShape(self.ptr(), &mut tensor_info_ptr)?];
@@ -528,4 +741,134 @@ impl<T: PrimitiveTensorElementType + Debug> Tensor<T> {
 	pub fn extract_raw_tensor_mut(&mut self) -> (&[i64], &mut [T]) {
 		self.try_extract_raw_tensor_mut().expect("Failed to extract tensor")
 	}
+
+	/// Returns a reference to the element at the given multi-dimensional index, or `None` if the index is out of bounds
+	/// or doesn't have one entry per dimension of the tensor.
+	///
+	/// Unlike [indexing](std::ops::Index) the tensor directly, this never panics; `None` is also returned if the
+	/// tensor is not in CPU-accessible memory.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2, 3], vec![1_i64, 2, 3, 4, 5, 6]))?;
+	/// assert_eq!(tensor.get(&[1, 0]), Some(&4));
+	/// assert_eq!(tensor.get(&[2, 0]), None);
+	/// assert_eq!(tensor.get(&[0]), None);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn get(&self, index: &[i64]) -> Option<&T> {
+		let (shape, data) = self.try_extract_raw_tensor().ok()?;
+		if index.len() != shape.len() {
+			return None;
+		}
+
+		let mut offset = 0;
+		for (&i, &dim) in index.iter().zip(shape) {
+			if i < 0 || i >= dim {
+				return None;
+			}
+			offset = offset * dim as usize + i as usize;
+		}
+		data.get(offset)
+	}
+
+	/// Returns an iterator over the tensor's elements along with their multi-dimensional indices.
+	///
+	/// Elements are visited in standard (row-major) order, i.e. the last dimension's index changes fastest.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2, 2], vec![1.0_f32, 2.0, 3.0, 4.0]))?;
+	/// let elements: Vec<_> = tensor.indexed_iter().collect();
+	/// assert_eq!(elements, [(vec![0, 0], &1.0), (vec![0, 1], &2.0), (vec![1, 0], &3.0), (vec![1, 1], &4.0)]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	///
+	/// # Panics
+	/// Panics if the tensor is not in CPU-accessible memory.
+	pub fn indexed_iter(&self) -> impl Iterator<Item = (Vec<i64>, &T)> + '_ {
+		let (shape, data) = self.extract_raw_tensor();
+		data.iter().enumerate().map(move |(mut offset, element)| {
+			let mut index = vec![0; shape.len()];
+			for (i, &dim) in index.iter_mut().zip(shape).rev() {
+				*i = (offset % dim as usize) as i64;
+				offset /= dim as usize;
+			}
+			(index, element)
+		})
+	}
+}
+
+impl<T: PrimitiveTensorElementType + Debug + Copy + Into<f64>> Tensor<T> {
+	/// Returns `true` if this tensor has the same shape as `other`, and all of their elements are equal within a
+	/// tolerance, like NumPy's [`allclose`](https://numpy.org/doc/stable/reference/generated/numpy.allclose.html).
+	///
+	/// Two elements `a` & `b` (from `self` & `other` respectively) are considered equal if
+	/// `|a - b| <= atol + rtol * |b|`. `NaN`s are never considered equal. Returns `false` (rather than panicking) if the
+	/// shapes differ or either tensor is not in CPU-accessible memory.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let a = Tensor::from_array(([3], vec![1.0_f32, 2.0, 3.0]))?;
+	/// let b = Tensor::from_array(([3], vec![1.0_f32, 2.000001, 3.0]))?;
+	/// assert!(a.approx_eq(&b, 1e-5, 1e-8));
+	/// assert!(!a.approx_eq(&b, 0.0, 0.0));
+	///
+	/// let c = Tensor::from_array(([1, 3], vec![1.0_f32, 2.0, 3.0]))?;
+	/// assert!(!a.approx_eq(&c, 1e-5, 1e-8));
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn approx_eq(&self, other: &Tensor<T>, rtol: f64, atol: f64) -> bool {
+		let (Ok((shape, data)), Ok((other_shape, other_data))) = (self.try_extract_raw_tensor::<T>(), other.try_extract_raw_tensor::<T>()) else {
+			return false;
+		};
+		shape == other_shape
+			&& data.iter().zip(other_data).all(|(&a, &b)| {
+				let (a, b): (f64, f64) = (a.into(), b.into());
+				(a - b).abs() <= atol + rtol * b.abs()
+			})
+	}
+}
+
+/// Extracts an owned copy of a tensor's data, checking that its element type is `T`.
+///
+/// ```
+/// # use ort::value::DynValue;
+/// # fn main() -> ort::Result<()> {
+/// let value: DynValue = ndarray::Array1::from_vec(vec![1.0_f32, 2.0, 3.0]).try_into()?;
+/// let array: ndarray::ArrayD<f32> = value.try_into()?;
+/// assert_eq!(array.as_slice(), Some(&[1.0, 2.0, 3.0][..]));
+/// # 	Ok(())
+/// # }
+/// ```
+#[cfg(feature = "ndarray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+impl<T: PrimitiveTensorElementType + Clone, Type: TensorValueTypeMarker + ?Sized> TryFrom<Value<Type>> for ndarray::ArrayD<T> {
+	type Error = Error;
+	fn try_from(value: Value<Type>) -> Result<Self, Self::Error> {
+		Self::try_from(&value)
+	}
+}
+
+#[cfg(feature = "ndarray")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ndarray")))]
+impl<T: PrimitiveTensorElementType + Clone, Type: TensorValueTypeMarker + ?Sized> TryFrom<&Value<Type>> for ndarray::ArrayD<T> {
+	type Error = Error;
+	fn try_from(value: &Value<Type>) -> Result<Self, Self::Error> {
+		value.try_extract_tensor_owned::<T>()
+	}
+}
+
+fn dtype_mismatch(expected: TensorElementType, got: TensorElementType) -> Error {
+	Error::new_with_kind(
+		ErrorCode::InvalidArgument,
+		ErrorKind::DTypeMismatch { expected, got },
+		format!("Cannot extract Tensor<{expected}> from Tensor<{got}>")
+	)
 }
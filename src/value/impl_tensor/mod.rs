@@ -5,11 +5,21 @@ use std::{
 	fmt::Debug,
 	marker::PhantomData,
 	ops::{Index, IndexMut},
+	ptr::{self, NonNull},
 	sync::Arc
 };
 
-use super::{DowncastableTarget, DynValue, Value, ValueRef, ValueRefMut, ValueType, ValueTypeMarker};
-use crate::{AsPointer, error::Result, memory::MemoryInfo, ortsys, tensor::IntoTensorElementType};
+pub(crate) use self::create::IntoValueTensor;
+use self::create::ToDimensions;
+use super::{DowncastableTarget, DynValue, Value, ValueInner, ValueRef, ValueRefMut, ValueType, ValueTypeMarker};
+use crate::{
+	AsPointer,
+	error::{Error, ErrorCode, Result},
+	memory::MemoryInfo,
+	ortsys,
+	session::Input,
+	tensor::IntoTensorElementType
+};
 
 pub trait TensorValueTypeMarker: ValueTypeMarker {
 	crate::private_trait!();
@@ -139,6 +149,417 @@ impl<Type: TensorValueTypeMarker + ?Sized> Value<Type> {
 	pub fn memory_info(&self) -> &MemoryInfo {
 		unsafe { self.inner.memory_info.as_ref().unwrap_unchecked() }
 	}
+
+	/// Returns the tensor's data as raw bytes in native byte order.
+	///
+	/// Fails for string tensors, and for tensors which are not in CPU-accessible memory.
+	pub(crate) fn data_bytes(&self) -> Result<&[u8]> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot get the raw data of a value of type {}", self.dtype())));
+		};
+		let Some(len) = ty.data_size(calculate_tensor_size(dimensions)) else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot get the raw data of a string tensor"));
+		};
+		if !self.memory_info().is_cpu_accessible() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Cannot get the raw data of a tensor which is not in CPU-accessible memory"));
+		}
+
+		if len == 0 {
+			return Ok(&[]);
+		}
+		Ok(unsafe { std::slice::from_raw_parts(self.data_ptr()?.cast::<u8>(), len) })
+	}
+
+	/// Creates a view of this tensor with a different shape, without copying its data.
+	///
+	/// The new shape must have the same number of elements as the tensor. Since the view shares its data with this
+	/// tensor, this also works for tensors which are not in CPU-accessible memory; it is analogous to
+	/// `ndarray`'s `into_shape` for tensors. String tensors cannot be reshaped.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([6], vec![1_i64, 2, 3, 4, 5, 6]))?;
+	///
+	/// let reshaped = tensor.reshape([2, 3])?;
+	/// assert_eq!(reshaped.shape()?, [2, 3]);
+	/// assert_eq!(reshaped.extract_raw_tensor().1, &[1, 2, 3, 4, 5, 6]);
+	///
+	/// assert!(tensor.reshape([4, 2]).is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn reshape(&self, new_shape: impl ToDimensions) -> Result<ValueRef<'_, Type>> {
+		Ok(ValueRef::new(self.reshaped(new_shape)?))
+	}
+
+	/// Converts this tensor into one with a different shape, without copying its data.
+	///
+	/// This is the owned counterpart of [`Tensor::reshape`]; see its documentation for details.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([2, 2], vec![1.0_f32, 2.0, 3.0, 4.0]))?;
+	/// let flat = tensor.into_shape([4])?;
+	/// assert_eq!(flat.extract_raw_tensor(), (&[4_i64][..], &[1.0, 2.0, 3.0, 4.0][..]));
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn into_shape(self, new_shape: impl ToDimensions) -> Result<Value<Type>> {
+		self.reshaped(new_shape)
+	}
+
+	/// Creates a view of this tensor with the dimension at `axis` removed, without copying its data. The dimension
+	/// must have a size of `1`.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([1, 3], vec![1_i64, 2, 3]))?;
+	/// assert_eq!(tensor.squeeze(0)?.shape()?, [3]);
+	/// assert!(tensor.squeeze(1).is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn squeeze(&self, axis: usize) -> Result<ValueRef<'_, Type>> {
+		let mut shape = self.shape()?;
+		match shape.get(axis) {
+			Some(1) => {
+				shape.remove(axis);
+				self.reshape(shape)
+			}
+			Some(_) => Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot squeeze axis {axis} of tensor with shape {shape:?}; axis does not have a size of 1")
+			)),
+			None => Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Axis {axis} is out of bounds for tensor with shape {shape:?}")))
+		}
+	}
+
+	/// Creates a view of this tensor with a new dimension of size `1` inserted at `axis`, without copying its data.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// let tensor = Tensor::from_array(([3], vec![1_i64, 2, 3]))?;
+	/// assert_eq!(tensor.unsqueeze(0)?.shape()?, [1, 3]);
+	/// assert_eq!(tensor.unsqueeze(1)?.shape()?, [3, 1]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn unsqueeze(&self, axis: usize) -> Result<ValueRef<'_, Type>> {
+		let mut shape = self.shape()?;
+		if axis > shape.len() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Axis {axis} is out of bounds for tensor with shape {shape:?}")));
+		}
+		shape.insert(axis, 1);
+		self.reshape(shape)
+	}
+
+	/// Creates a new CPU tensor with the axes of this tensor reordered, copying its data; this is analogous to
+	/// `ndarray`'s `permuted_axes`, but produces a tensor in standard (row-major) layout.
+	///
+	/// Axis `i` of the new tensor is axis `axes[i]` of this tensor, so `axes` must contain each axis of the tensor
+	/// exactly once. The tensor must be in CPU-accessible memory, and cannot be a string tensor.
+	///
+	/// ```
+	/// # use ort::value::Tensor;
+	/// # fn main() -> ort::Result<()> {
+	/// // [batch, sequence, vocab] -> [sequence, batch, vocab]
+	/// let tensor = Tensor::from_array(([2, 3, 1], vec![1_i64, 2, 3, 4, 5, 6]))?;
+	/// let permuted = tensor.permute(&[1, 0, 2])?;
+	/// assert_eq!(permuted.extract_raw_tensor(), (&[3_i64, 2, 1][..], &[1, 4, 2, 5, 3, 6][..]));
+	///
+	/// assert!(tensor.permute(&[0, 0, 2]).is_err());
+	/// assert!(tensor.permute(&[1, 0]).is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn permute(&self, axes: &[usize]) -> Result<Value<Type>> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot permute a value of type {}", self.dtype())));
+		};
+		let Some(element_size) = ty.byte_size() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Tensors of type {ty} cannot be permuted")));
+		};
+		let mut seen = vec![false; dimensions.len()];
+		let valid = axes.len() == dimensions.len() && axes.iter().all(|&axis| axis < seen.len() && !std::mem::replace(&mut seen[axis], true));
+		if !valid {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("{axes:?} is not a valid permutation of the axes of tensor with shape {dimensions:?}")
+			));
+		}
+
+		let shape: Vec<i64> = axes.iter().map(|&axis| dimensions[axis]).collect();
+		// trailing axes which stay in place don't need to be permuted, and can be copied as one contiguous chunk
+		let rank = axes.len() - axes.iter().rev().enumerate().take_while(|(i, axis)| **axis == axes.len() - 1 - i).count();
+		let chunk = calculate_tensor_size(&dimensions[rank..]) * element_size;
+		// the stride of each of the remaining axes of this tensor, in bytes
+		let mut strides = vec![chunk; rank];
+		for axis in (0..rank.saturating_sub(1)).rev() {
+			strides[axis] = strides[axis + 1] * dimensions[axis + 1] as usize;
+		}
+
+		let data = self.data_bytes()?;
+		let mut out = Vec::with_capacity(data.len());
+		if !data.is_empty() {
+			// iterate over the new tensor's indices in row-major order
+			let mut index = vec![0_usize; rank];
+			'copy: loop {
+				let offset: usize = index.iter().zip(axes).map(|(i, &axis)| i * strides[axis]).sum();
+				out.extend_from_slice(&data[offset..offset + chunk]);
+
+				let mut i = rank;
+				loop {
+					if i == 0 {
+						break 'copy;
+					}
+					i -= 1;
+					index[i] += 1;
+					if index[i] < shape[i] as usize {
+						break;
+					}
+					index[i] = 0;
+				}
+			}
+		}
+
+		let tensor = DynTensor::from_bytes(*ty, shape, &out)?;
+		Ok(Value {
+			inner: tensor.inner,
+			_markers: PhantomData
+		})
+	}
+
+	/// Creates a view of this tensor with a leading batch dimension of size `1` added if `input` expects one, without
+	/// copying its data.
+	///
+	/// The batch dimension is only added if the tensor has exactly one dimension fewer than `input`, and `input`'s first
+	/// dimension is dynamic or `1`. Otherwise, the tensor is returned as-is (and any shape mismatch is left for
+	/// [`Session::run`](crate::session::Session::run) to report), so this can be applied to inputs regardless of whether
+	/// they were already batched.
+	///
+	/// ```
+	/// # use ort::{session::{Session, SessionInputValue}, value::Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let session = Session::builder()?.commit_from_file("tests/data/upsample.onnx")?;
+	/// // the model expects a batch of images of shape `[N, H, W, 3]`
+	/// let image = Tensor::from_array(([64, 64, 3], vec![0.0_f32; 64 * 64 * 3]))?;
+	///
+	/// let batched = image.with_batch_dim_for(&session.inputs[0])?;
+	/// assert_eq!(batched.shape()?, [1, 64, 64, 3]);
+	/// let outputs = session.run([SessionInputValue::from(batched)])?;
+	///
+	/// // already batched, so left untouched
+	/// let images = Tensor::from_array(([2, 64, 64, 3], vec![0.0_f32; 2 * 64 * 64 * 3]))?;
+	/// assert_eq!(images.with_batch_dim_for(&session.inputs[0])?.shape()?, [2, 64, 64, 3]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn with_batch_dim_for(&self, input: &Input) -> Result<ValueRef<'_, Type>> {
+		let ValueType::Tensor { dimensions: expected, .. } = &input.input_type else {
+			return Ok(self.view());
+		};
+		let ValueType::Tensor { dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot add a batch dimension to a value of type {}", self.dtype())));
+		};
+		if dimensions.len() + 1 == expected.len() && matches!(expected[0], -1 | 1) { self.unsqueeze(0) } else { Ok(self.view()) }
+	}
+
+	fn reshaped(&self, new_shape: impl ToDimensions) -> Result<Value<Type>> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Cannot reshape a value of type {}", self.dtype())));
+		};
+		let Some(data_size) = ty.data_size(calculate_tensor_size(dimensions)) else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "String tensors cannot be reshaped"));
+		};
+
+		let new_shape = new_shape.to_dimensions(None)?;
+		let len = calculate_tensor_size(dimensions);
+		if calculate_tensor_size(&new_shape) != len {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!(
+					"Cannot reshape tensor of shape {dimensions:?} ({len} elements) into shape {new_shape:?} ({} elements)",
+					calculate_tensor_size(&new_shape)
+				)
+			));
+		}
+
+		let mut value_ptr: *mut ort_sys::OrtValue = ptr::null_mut();
+		ortsys![
+			unsafe CreateTensorWithDataAsOrtValue(
+				self.memory_info().ptr(),
+				self.data_ptr()?.cast_mut(),
+				data_size,
+				new_shape.as_ptr(),
+				new_shape.len(),
+				(*ty).into(),
+				&mut value_ptr
+			)?;
+			nonNull(value_ptr)
+		];
+
+		Ok(Value {
+			inner: Arc::new(ValueInner {
+				ptr: unsafe { NonNull::new_unchecked(value_ptr) },
+				memory_info: Some(self.memory_info().clone()),
+				dtype: ValueType::Tensor {
+					ty: *ty,
+					dimension_symbols: vec![None; new_shape.len()],
+					dimensions: new_shape
+				},
+				drop: true,
+				// the reshaped tensor points to the original's data, so it must not be upgradable if the original isn't
+				// (e.g. if it borrows its data, or is owned by an operator kernel)
+				upgradable: self.inner.upgradable && self.inner.drop,
+				// keep the original tensor, and thus its data, alive for as long as the reshaped tensor is
+				_backing: Some(Box::new(Arc::clone(&self.inner)))
+			}),
+			_markers: PhantomData
+		})
+	}
+}
+
+impl DynValue {
+	/// Concatenates tensors along `axis` into a new CPU tensor, copying their data directly without going through
+	/// `ndarray`.
+	///
+	/// All values must be tensors in CPU-accessible memory with the same element type & rank, and the same dimensions
+	/// on every axis except `axis`. String tensors cannot be concatenated.
+	///
+	/// ```
+	/// # use ort::value::{DynValue, Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let a = Tensor::from_array(([1, 2], vec![1_i64, 2]))?.into_dyn();
+	/// let b = Tensor::from_array(([2, 2], vec![3_i64, 4, 5, 6]))?.into_dyn();
+	///
+	/// let batch = DynValue::concatenate(&[a, b], 0)?;
+	/// assert_eq!(batch.try_extract_raw_tensor::<i64>()?, (&[3_i64, 2][..], &[1, 2, 3, 4, 5, 6][..]));
+	///
+	/// let a = Tensor::from_array(([2, 1], vec![1_i64, 2]))?.into_dyn();
+	/// let b = Tensor::from_array(([2, 2], vec![3_i64, 4, 5, 6]))?.into_dyn();
+	/// let wide = DynValue::concatenate(&[a, b], 1)?;
+	/// assert_eq!(wide.try_extract_raw_tensor::<i64>()?, (&[2_i64, 3][..], &[1, 3, 4, 2, 5, 6][..]));
+	///
+	/// let c = Tensor::from_array(([1, 3], vec![7_i64, 8, 9]))?.into_dyn();
+	/// assert!(DynValue::concatenate(&[wide, c], 1).is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn concatenate(values: &[DynValue], axis: usize) -> Result<DynValue> {
+		Self::concatenate_refs(&values.iter().collect::<Vec<_>>(), axis)
+	}
+
+	pub(crate) fn concatenate_refs(values: &[&DynValue], axis: usize) -> Result<DynValue> {
+		let Some(ValueType::Tensor { ty, dimensions, .. }) = values.first().map(|value| value.dtype()) else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, "Expected at least one tensor to concatenate"));
+		};
+		let Some(element_size) = ty.byte_size() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Tensors of type {ty} cannot be concatenated")));
+		};
+		if axis >= dimensions.len() {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Axis {axis} is out of bounds for tensor with shape {dimensions:?}")));
+		}
+
+		let mut shape = dimensions.clone();
+		shape[axis] = 0;
+		for (i, value) in values.iter().enumerate() {
+			let ValueType::Tensor { ty: other_ty, dimensions: other, .. } = value.dtype() else {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Value #{i} is not a tensor; got {}", value.dtype())));
+			};
+			if other_ty != ty {
+				return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Value #{i} has element type {other_ty}; expected {ty}")));
+			}
+			let compatible = other.len() == dimensions.len() && other.iter().zip(dimensions).enumerate().all(|(j, (a, b))| j == axis || a == b);
+			if !compatible {
+				return Err(Error::new_with_code(
+					ErrorCode::InvalidArgument,
+					format!("Cannot concatenate tensor #{i} of shape {other:?} with tensor of shape {dimensions:?} along axis {axis}")
+				));
+			}
+			shape[axis] += other[axis];
+		}
+
+		// each tensor is `outer` contiguous chunks of `dimensions[axis] * inner` elements, which are interleaved
+		let outer = calculate_tensor_size(&dimensions[..axis]);
+		let inner = calculate_tensor_size(&dimensions[axis + 1..]) * element_size;
+		let data = values.iter().map(|value| value.data_bytes()).collect::<Result<Vec<_>>>()?;
+		let mut out = Vec::with_capacity(calculate_tensor_size(&shape) * element_size);
+		for o in 0..outer {
+			for (value, data) in values.iter().zip(&data) {
+				let chunk = value.dtype().tensor_dimensions().map_or(0, |dims| dims[axis] as usize) * inner;
+				out.extend_from_slice(&data[o * chunk..(o + 1) * chunk]);
+			}
+		}
+		Ok(DynTensor::from_bytes(*ty, shape, &out)?.into_dyn())
+	}
+
+	/// Splits a tensor along `axis` into new CPU tensors with `sizes[i]` elements on `axis` each; this is the inverse of
+	/// [`DynValue::concatenate`]. `sizes` must sum to the size of the dimension at `axis`. If `sizes` is empty, the
+	/// tensor is split into pieces of size `1`, e.g. to split a batch into its individual items.
+	///
+	/// The tensor must be in CPU-accessible memory, and cannot be a string tensor.
+	///
+	/// ```
+	/// # use ort::value::{DynValue, Tensor};
+	/// # fn main() -> ort::Result<()> {
+	/// let batch = Tensor::from_array(([3, 2], vec![1_i64, 2, 3, 4, 5, 6]))?.into_dyn();
+	///
+	/// let parts = batch.split(0, &[1, 2])?;
+	/// assert_eq!(parts[0].try_extract_raw_tensor::<i64>()?, (&[1_i64, 2][..], &[1, 2][..]));
+	/// assert_eq!(parts[1].try_extract_raw_tensor::<i64>()?, (&[2_i64, 2][..], &[3, 4, 5, 6][..]));
+	///
+	/// let columns = batch.split(1, &[])?;
+	/// assert_eq!(columns.len(), 2);
+	/// assert_eq!(columns[1].try_extract_raw_tensor::<i64>()?, (&[3_i64, 1][..], &[2, 4, 6][..]));
+	///
+	/// assert!(batch.split(0, &[1, 1]).is_err());
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn split(&self, axis: usize, sizes: &[usize]) -> Result<Vec<DynValue>> {
+		let ValueType::Tensor { ty, dimensions, .. } = self.dtype() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Only tensors can be split; got {}", self.dtype())));
+		};
+		let Some(element_size) = ty.byte_size() else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Tensors of type {ty} cannot be split")));
+		};
+		let Some(&dim) = dimensions.get(axis) else {
+			return Err(Error::new_with_code(ErrorCode::InvalidArgument, format!("Axis {axis} is out of bounds for tensor with shape {dimensions:?}")));
+		};
+		let dim = dim.max(0) as usize;
+		let sizes = if sizes.is_empty() { vec![1; dim] } else { sizes.to_vec() };
+		if sizes.iter().sum::<usize>() != dim {
+			return Err(Error::new_with_code(
+				ErrorCode::InvalidArgument,
+				format!("Cannot split axis {axis} of tensor with shape {dimensions:?} into sizes {sizes:?}, which sum to {}", sizes.iter().sum::<usize>())
+			));
+		}
+
+		// the tensor is `outer` contiguous rows of `dim * inner` bytes; each part takes a contiguous chunk of every row
+		let outer = calculate_tensor_size(&dimensions[..axis]);
+		let inner = calculate_tensor_size(&dimensions[axis + 1..]) * element_size;
+		let data = self.data_bytes()?;
+		let mut offset = 0;
+		sizes
+			.iter()
+			.map(|&size| {
+				let mut part = Vec::with_capacity(outer * size * inner);
+				for o in 0..outer {
+					let start = o * dim * inner + offset;
+					part.extend_from_slice(&data[start..start + size * inner]);
+				}
+				offset += size * inner;
+
+				let mut shape = dimensions.clone();
+				shape[axis] = size as i64;
+				Ok(DynTensor::from_bytes(*ty, shape, &part)?.into_dyn())
+			})
+			.collect()
+	}
 }
 
 impl<T: IntoTensorElementType + Debug> Tensor<T> {
@@ -253,6 +674,25 @@ impl<T: IntoTensorElementType + Clone + Debug, const N: usize> IndexMut<[i64; N]
 	}
 }
 
+macro_rules! impl_tensor_eq {
+	($($t:ty),+) => {
+		$(
+			/// Tensors are equal if they have the same shape & elements. Tensors which are not in CPU-accessible memory are
+			/// never equal.
+			impl PartialEq for Tensor<$t> {
+				fn eq(&self, other: &Self) -> bool {
+					match (self.try_extract_raw_tensor::<$t>(), other.try_extract_raw_tensor::<$t>()) {
+						(Ok(a), Ok(b)) => a == b,
+						_ => false
+					}
+				}
+			}
+		)+
+	};
+}
+
+impl_tensor_eq!(u8, i8, u16, i16, u32, i32, u64, i64, bool);
+
 pub(crate) fn calculate_tensor_size(shape: &[i64]) -> usize {
 	let mut size = 1usize;
 	for dim in shape {
@@ -271,7 +711,11 @@ mod tests {
 	use ndarray::{ArcArray1, Array1, CowArray};
 
 	use super::Tensor;
-	use crate::{memory::Allocator, tensor::TensorElementType, value::ValueType};
+	use crate::{
+		memory::Allocator,
+		tensor::TensorElementType,
+		value::{DynTensor, DynValue, ValueType}
+	};
 
 	#[test]
 	#[cfg(feature = "ndarray")]
@@ -371,6 +815,64 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_tensor_from_array_casting() -> crate::Result<()> {
+		let v: Vec<u16> = vec![0, 1, 2, u16::MAX];
+
+		let value = Tensor::<i64>::from_array_casting(([2, 2], &v[..]))?;
+		let (shape, data) = value.extract_raw_tensor();
+		assert_eq!(shape, vec![2, 2]);
+		assert_eq!(data, &[0, 1, 2, 65535]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_from_shape_slice() -> crate::Result<()> {
+		let v: Vec<f32> = vec![1., 2., 3., 4., 5., 6.];
+
+		let value = Tensor::from_shape_slice([2, 3], &v)?;
+		let (shape, data) = value.extract_raw_tensor();
+		assert_eq!(shape, vec![2, 3]);
+		assert_eq!(data.as_ptr(), v.as_ptr());
+
+		// Borrowed tensors must not be upgradable to owned tensors, directly or through a view.
+		assert!(value.view().try_upgrade().is_err());
+		assert!(value.reshape([3, 2])?.try_upgrade().is_err());
+		assert!(value.try_upgrade().is_err());
+
+		assert!(Tensor::from_shape_slice([4, 2], &v).is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_reshape() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([6], vec![1_i32, 2, 3, 4, 5, 6]))?;
+
+		let reshaped = tensor.reshape([3, 2])?;
+		let (shape, data) = reshaped.extract_raw_tensor();
+		assert_eq!(shape, vec![3, 2]);
+		assert_eq!(data.as_ptr(), tensor.extract_raw_tensor().1.as_ptr());
+		assert!(tensor.reshape([4, 2]).is_err());
+
+		let owned = tensor.into_shape([1, 2, 3])?;
+		drop(reshaped);
+		assert_eq!(owned.extract_raw_tensor(), (&[1_i64, 2, 3][..], &[1, 2, 3, 4, 5, 6][..]));
+
+		assert!(Tensor::from_string_array(([2], &["a", "b"][..]))?.reshape([1, 2]).is_err());
+		assert!(crate::value::Map::<String, f32>::new([("a".to_owned(), 1.0)])?.into_dyn().reshape([1]).is_err());
+
+		let tensor = Tensor::from_array(([2, 1, 3], vec![0_u8; 6]))?;
+		assert_eq!(tensor.squeeze(1)?.shape()?, [2, 3]);
+		assert!(tensor.squeeze(0).is_err());
+		assert!(tensor.squeeze(3).is_err());
+		assert_eq!(tensor.unsqueeze(3)?.shape()?, [2, 1, 3, 1]);
+		assert!(tensor.unsqueeze(4).is_err());
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_tensor_index() -> crate::Result<()> {
 		let mut tensor = Tensor::new(&Allocator::default(), [1, 3, 224, 224])?;
@@ -390,4 +892,105 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	#[cfg(all(feature = "half", feature = "ndarray"))]
+	fn test_tensor_from_half_array() -> crate::Result<()> {
+		let array = ndarray::Array2::from_shape_fn((2, 2), |(i, j)| half::f16::from_f32((i * 2 + j) as f32));
+		let inputs = crate::inputs![array.view()]?;
+		assert_eq!(inputs[0].dtype().tensor_type(), Some(TensorElementType::Float16));
+
+		let tensor = Tensor::from_array(array.mapv(|x| half::bf16::from_f32(x.to_f32())).view())?;
+		assert_eq!(tensor.dtype().tensor_type(), Some(TensorElementType::Bfloat16));
+		assert_eq!(tensor.extract_raw_tensor().1[3], half::bf16::from_f32(3.0));
+
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "ndarray")]
+	fn test_tensor_array_conversions() -> crate::Result<()> {
+		let array = ndarray::Array2::from_shape_vec((2, 2), vec![1.0_f32, 2.0, 3.0, 4.0]).expect("invalid shape");
+		let value: DynValue = array.clone().try_into()?;
+
+		let extracted: ndarray::ArrayD<f32> = (&value).try_into()?;
+		assert_eq!(extracted, array.clone().into_dyn());
+		assert!(ndarray::ArrayD::<i64>::try_from(&value).is_err());
+
+		let extracted: ndarray::ArrayD<f32> = value.try_into()?;
+		assert_eq!(extracted, array.into_dyn());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_eq() -> crate::Result<()> {
+		let a = Tensor::from_array(([2, 2], vec![1_i64, 2, 3, 4]))?;
+		assert_eq!(a, Tensor::from_array(([2, 2], vec![1_i64, 2, 3, 4]))?);
+		assert_ne!(a, Tensor::from_array(([2, 2], vec![1_i64, 2, 3, 5]))?);
+		assert_ne!(a, Tensor::from_array(([4], vec![1_i64, 2, 3, 4]))?);
+
+		let b = Tensor::from_array(([3], vec![1.0_f32, 100.0, f32::NAN]))?;
+		let c = Tensor::from_array(([3], vec![1.0_f32 + 1e-7, 100.001, f32::NAN]))?;
+		assert!(!b.approx_eq(&c, 1e-5, 1e-8));
+		let b = Tensor::from_array(([2], vec![1.0_f32, 100.0]))?;
+		let c = Tensor::from_array(([2], vec![1.0_f32 + 1e-7, 100.001]))?;
+		assert!(b.approx_eq(&c, 1e-5, 1e-8));
+		assert!(!b.approx_eq(&c, 1e-6, 1e-8));
+		assert!(!b.approx_eq(&Tensor::from_array(([1, 2], vec![1.0_f32, 100.0]))?, 1e-5, 1e-8));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_tensor_get() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2, 3, 2], (0..12_i32).collect::<Vec<_>>()))?;
+		for (index, element) in tensor.indexed_iter() {
+			assert_eq!(tensor.get(&index), Some(element));
+			assert_eq!(*element as i64, index[0] * 6 + index[1] * 2 + index[2]);
+		}
+		assert_eq!(tensor.indexed_iter().count(), 12);
+
+		assert_eq!(tensor.get(&[1, 2, 1]), Some(&11));
+		assert_eq!(tensor.get(&[1, 3, 0]), None);
+		assert_eq!(tensor.get(&[-1, 0, 0]), None);
+		assert_eq!(tensor.get(&[0, 0]), None);
+
+		let scalar = Tensor::from_array(((), vec![7_i64]))?;
+		assert_eq!(scalar.get(&[]), Some(&7));
+		assert_eq!(scalar.indexed_iter().collect::<Vec<_>>(), [(vec![], &7)]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_int4_tensor() -> crate::Result<()> {
+		// 5 elements packed into 3 bytes, low nibble first: [1, -2, 7, -8, 0]
+		let data = [0xe1_u8, 0x87, 0x00];
+		let tensor = DynTensor::from_bytes(TensorElementType::Int4, vec![5], &data)?;
+		assert_eq!(tensor.shape()?, [5]);
+		assert_eq!(tensor.try_extract_raw_int4_tensor()?.1, [1, -2, 7, -8, 0]);
+		assert!(DynTensor::from_bytes(TensorElementType::Int4, vec![5], &data[..2]).is_err());
+
+		let tensor = DynTensor::from_bytes(TensorElementType::Uint4, vec![1, 5], &data)?;
+		assert_eq!(tensor.try_extract_raw_int4_tensor()?.1, [1, 14, 7, 8, 0]);
+		#[cfg(feature = "ndarray")]
+		assert_eq!(tensor.try_extract_int4_tensor()?, ndarray::array![[1_i8, 14, 7, 8, 0]].into_dyn());
+		assert!(tensor.try_extract_raw_tensor::<u8>().is_err());
+		Ok(())
+	}
+
+	#[test]
+	#[cfg(feature = "ndarray")]
+	fn test_tensor_permute() -> crate::Result<()> {
+		let array = ndarray::Array::from_shape_vec((2, 3, 4, 5), (0..120_i32).collect()).unwrap().into_dyn();
+		let tensor = Tensor::from_array(array.clone())?;
+		for axes in [[0, 1, 2, 3], [3, 2, 1, 0], [1, 0, 2, 3], [0, 2, 1, 3], [2, 0, 3, 1]] {
+			let permuted = tensor.permute(&axes)?;
+			assert_eq!(permuted.extract_tensor(), array.view().permuted_axes(axes.as_slice()), "permuting by {axes:?}");
+		}
+		assert!(tensor.permute(&[0, 1, 2, 4]).is_err());
+		assert!(crate::value::Map::<String, f32>::new([("a".to_owned(), 1.0)])?.into_dyn().permute(&[0]).is_err());
+		Ok(())
+	}
 }
@@ -26,8 +26,16 @@ use std::{
 	sync::Arc
 };
 
+#[cfg(feature = "arrow")]
+mod impl_arrow;
+mod impl_fmt;
+#[cfg(feature = "serde_json")]
+mod impl_json;
 mod impl_map;
+mod impl_npy;
 mod impl_sequence;
+#[cfg(feature = "serde")]
+mod impl_serde;
 mod impl_tensor;
 pub(crate) mod r#type;
 
@@ -39,6 +47,7 @@ pub use self::{
 	impl_tensor::{DynTensor, DynTensorRef, DynTensorRefMut, DynTensorValueType, Tensor, TensorRef, TensorRefMut, TensorValueType, TensorValueTypeMarker},
 	r#type::ValueType
 };
+pub(crate) use self::impl_tensor::{IntoValueTensor, calculate_tensor_size};
 use crate::{
 	AsPointer,
 	error::{Error, ErrorCode, Result},
@@ -53,6 +62,9 @@ pub(crate) struct ValueInner {
 	pub(crate) dtype: ValueType,
 	pub(crate) memory_info: Option<MemoryInfo>,
 	pub(crate) drop: bool,
+	/// Whether a [`ValueRef`]/[`ValueRefMut`] to this value may be upgraded to an owned [`Value`]. This is `false` for
+	/// values which borrow data they do not own, since the upgraded value would not be bound by the borrow's lifetime.
+	pub(crate) upgradable: bool,
 	pub(crate) _backing: Option<Box<dyn Any>>
 }
 
@@ -101,8 +113,9 @@ impl<'v, Type: ValueTypeMarker + ?Sized> ValueRef<'v, Type> {
 	/// Attempts to upgrade this `ValueRef` to an owned [`Value`] holding the same data.
 	pub fn try_upgrade(self) -> Result<Value<Type>, Self> {
 		// We cannot upgade a value which we cannot drop, i.e. `ValueRef`s used in operator kernels. Those only last for the
-		// duration of the kernel, allowing an upgrade would allow a UAF.
-		if !self.inner.inner.drop {
+		// duration of the kernel, allowing an upgrade would allow a UAF. The same goes for values which borrow their data,
+		// like those created via `Tensor::from_shape_slice`.
+		if !self.inner.inner.drop || !self.inner.inner.upgradable {
 			return Err(self);
 		}
 
@@ -149,8 +162,9 @@ impl<'v, Type: ValueTypeMarker + ?Sized> ValueRefMut<'v, Type> {
 	/// Attempts to upgrade this `ValueRefMut` to an owned [`Value`] holding the same data.
 	pub fn try_upgrade(self) -> Result<Value<Type>, Self> {
 		// We cannot upgade a value which we cannot drop, i.e. `ValueRef`s used in operator kernels. Those only last for the
-		// duration of the kernel, allowing an upgrade would allow a UAF.
-		if !self.inner.inner.drop {
+		// duration of the kernel, allowing an upgrade would allow a UAF. The same goes for values which borrow their data,
+		// like those created via `Tensor::from_shape_slice`.
+		if !self.inner.inner.drop || !self.inner.inner.upgradable {
 			return Err(self);
 		}
 
@@ -215,7 +229,6 @@ impl<Type: ValueTypeMarker + ?Sized> DerefMut for ValueRefMut<'_, Type> {
 /// - [`Tensor::extract_tensor`], [`Tensor::extract_raw_tensor`]
 ///
 /// [`Session`]: crate::session::Session
-#[derive(Debug)]
 pub struct Value<Type: ValueTypeMarker + ?Sized = DynValueTypeMarker> {
 	pub(crate) inner: Arc<ValueInner>,
 	pub(crate) _markers: PhantomData<Type>
@@ -278,7 +291,21 @@ unsafe impl<Type: ValueTypeMarker + ?Sized> Send for Value<Type> {}
 unsafe impl<Type: ValueTypeMarker + ?Sized> Sync for Value<Type> {}
 
 impl<Type: ValueTypeMarker + ?Sized> Value<Type> {
-	/// Returns the data type of this [`Value`].
+	/// Returns the data type of this [`Value`], which describes whether it is a tensor, sequence, or map.
+	///
+	/// For tensors, use [`ValueType::tensor_type`] to get the element type, and [`Tensor::shape`] (which is also
+	/// available on [`DynValue`]s) to get the shape, without having to extract the tensor's data.
+	///
+	/// ```
+	/// # use ort::{tensor::TensorElementType, value::{DynValue, Tensor}};
+	/// # fn main() -> ort::Result<()> {
+	/// let value: DynValue = Tensor::from_array(([2, 3], vec![0.0_f32; 6]))?.into_dyn();
+	/// assert_eq!(value.dtype().tensor_type(), Some(TensorElementType::Float32));
+	/// assert_eq!(value.shape()?, [2, 3]);
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	#[doc(alias("element_type", "value_type"))]
 	pub fn dtype(&self) -> &ValueType {
 		&self.inner.dtype
 	}
@@ -304,6 +331,7 @@ impl<Type: ValueTypeMarker + ?Sized> Value<Type> {
 				memory_info: MemoryInfo::from_value(ptr.as_ptr()),
 				dtype: ValueType::from_type_info(typeinfo_ptr),
 				drop: true,
+				upgradable: true,
 				_backing: session.map(|v| Box::new(v) as Box<dyn Any>)
 			}),
 			_markers: PhantomData
@@ -322,6 +350,7 @@ impl<Type: ValueTypeMarker + ?Sized> Value<Type> {
 				memory_info: MemoryInfo::from_value(ptr.as_ptr()),
 				dtype: ValueType::from_type_info(typeinfo_ptr),
 				drop: false,
+				upgradable: true,
 				_backing: session.map(|v| Box::new(v) as Box<dyn Any>)
 			}),
 			_markers: PhantomData
@@ -369,6 +398,45 @@ impl Value<DynValueTypeMarker> {
 		result == 1
 	}
 
+	/// Returns a reference to this value as a [`DynTensor`] if it is a tensor, or `None` otherwise.
+	///
+	/// Together with [`DynValue::as_sequence`] & [`DynValue::as_map`], this can be used to handle any kind of value, e.g.
+	/// the outputs of an arbitrary model:
+	///
+	/// ```
+	/// # use ort::value::{DynValue, Tensor};
+	/// fn describe(value: &DynValue) -> String {
+	/// 	if let Some(tensor) = value.as_tensor() {
+	/// 		format!("tensor of shape {:?}", tensor.shape().unwrap_or_default())
+	/// 	} else if let Some(sequence) = value.as_sequence() {
+	/// 		format!("sequence of {}", sequence.dtype())
+	/// 	} else if let Some(map) = value.as_map() {
+	/// 		format!("map of {}", map.dtype())
+	/// 	} else {
+	/// 		unreachable!()
+	/// 	}
+	/// }
+	///
+	/// # fn main() -> ort::Result<()> {
+	/// let value = Tensor::from_array(([2, 3], vec![0.0_f32; 6]))?.into_dyn();
+	/// assert_eq!(describe(&value), "tensor of shape [2, 3]");
+	/// # 	Ok(())
+	/// # }
+	/// ```
+	pub fn as_tensor(&self) -> Option<DynTensorRef<'_>> {
+		self.downcast_ref().ok()
+	}
+
+	/// Returns a reference to this value as a [`DynSequence`] if it is a sequence, or `None` otherwise.
+	pub fn as_sequence(&self) -> Option<DynSequenceRef<'_>> {
+		self.downcast_ref().ok()
+	}
+
+	/// Returns a reference to this value as a [`DynMap`] if it is a map, or `None` otherwise.
+	pub fn as_map(&self) -> Option<DynMapRef<'_>> {
+		self.downcast_ref().ok()
+	}
+
 	/// Attempts to downcast a dynamic value (like [`DynValue`] or [`DynTensor`]) to a more strongly typed variant,
 	/// like [`Tensor<T>`].
 	#[inline]
@@ -480,4 +548,22 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_dyn_value_kind() -> crate::Result<()> {
+		let tensor = Tensor::from_array(([2], vec![1_i64, 2]))?.into_dyn();
+		assert!(tensor.as_tensor().is_some());
+		assert!(tensor.as_sequence().is_none());
+		assert!(tensor.as_map().is_none());
+
+		let map = Map::<String, f32>::new([("pi".to_owned(), std::f32::consts::PI)])?.into_dyn();
+		assert!(map.as_tensor().is_none());
+		assert!(map.as_map().is_some());
+
+		let sequence = Sequence::new([Tensor::from_array(([1], vec![1_i64]))?])?.into_dyn();
+		assert!(sequence.as_sequence().is_some());
+		assert!(sequence.as_map().is_none());
+
+		Ok(())
+	}
 }
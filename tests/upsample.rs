@@ -1,10 +1,17 @@
-use std::path::Path;
+use std::{path::Path, sync::Arc, time::Duration};
 
 use image::RgbImage;
 use ndarray::{Array, ArrayViewD, CowArray, Ix4};
 use ort::{
-	inputs,
-	session::{Session, builder::GraphOptimizationLevel}
+	ErrorKind, inputs,
+	memory::Allocator,
+	session::{
+		DynamicBatcher, Session, SessionPipeline,
+		builder::GraphOptimizationLevel,
+		run_options::{OutputSelector, RunOptions}
+	},
+	tensor::TensorElementType,
+	value::Tensor
 };
 use test_log::test;
 
@@ -116,3 +123,236 @@ fn upsample_with_ort_model() -> ort::Result<()> {
 
 	Ok(())
 }
+
+#[test]
+fn upsample_into_preallocated_buffer() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_intra_threads(1)?
+		.commit_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+
+	let mut outputs = [Tensor::<f32>::new(&Allocator::default(), [1, 128, 128, 3])?];
+	for _ in 0..2 {
+		let input = Array::from_elem((1, 64, 64, 3), 0.5_f32);
+		session.run_into(inputs![input]?, &mut outputs)?;
+		assert!(outputs[0].extract_tensor().iter().all(|x| *x == 0.5));
+	}
+
+	// wrong rank
+	let mut outputs = [Tensor::<f32>::new(&Allocator::default(), [128, 128, 3])?];
+	assert!(session.run_into(inputs![Array::<f32, _>::zeros((1, 64, 64, 3))]?, &mut outputs).is_err());
+	// wrong element type
+	let mut outputs = [Tensor::<i32>::new(&Allocator::default(), [1, 128, 128, 3])?];
+	assert!(session.run_into(inputs![Array::<f32, _>::zeros((1, 64, 64, 3))]?, &mut outputs).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn upsample_output_introspection() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_intra_threads(1)?
+		.commit_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+
+	let outputs = session.run(inputs![Array::<f32, _>::zeros((1, 24, 32, 3))]?)?;
+	let output = &outputs[0];
+	assert!(output.is_tensor());
+	assert_eq!(output.dtype().tensor_type(), Some(TensorElementType::Float32));
+	assert_eq!(output.shape()?, [1, 48, 64, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_selected_outputs() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_intra_threads(1)?
+		.commit_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+	let output_name = session.outputs[0].name.clone();
+
+	let options = RunOptions::new()?.with_outputs(OutputSelector::no_default().with(output_name.as_str()));
+	let outputs = session.run_with_options(inputs![Array::<f32, _>::zeros((1, 16, 16, 3))]?, &options)?;
+	assert_eq!(outputs.len(), 1);
+	assert_eq!(outputs[output_name.as_str()].shape()?, [1, 32, 32, 3]);
+
+	let options = RunOptions::new()?.with_outputs(OutputSelector::no_default().with("probs"));
+	let err = session
+		.run_with_options(inputs![Array::<f32, _>::zeros((1, 16, 16, 3))]?, &options)
+		.expect_err("unknown output should fail");
+	assert!(err.to_string().contains("`probs`"));
+
+	let outputs = session.run_selecting(inputs![Array::<f32, _>::zeros((1, 16, 16, 3))]?, &[output_name.as_str()])?;
+	assert_eq!(outputs.len(), 1);
+	assert_eq!(outputs[0].shape()?, [1, 32, 32, 3]);
+	assert!(session.run_selecting(inputs![Array::<f32, _>::zeros((1, 16, 16, 3))]?, &["probs"]).is_err());
+
+	let (outputs, timings) = session.run_timed(inputs![Array::<f32, _>::zeros((1, 16, 16, 3))]?)?;
+	assert_eq!(outputs.len(), session.outputs.len());
+	assert_eq!(outputs[0].shape()?, [1, 32, 32, 3]);
+	assert_eq!(timings.total(), timings.input_binding + timings.execution + timings.output_extraction);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_warmup() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?.commit_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+	let elapsed = session.warmup(2)?;
+	assert!(elapsed > Duration::ZERO);
+
+	let stats = session.benchmark(5, inputs![Array::<f32, _>::zeros((1, 16, 16, 3))]?)?;
+	assert!(stats.min_ns <= stats.median_ns && stats.median_ns <= stats.p95_ns && stats.p95_ns <= stats.p99_ns && stats.p99_ns <= stats.max_ns);
+	assert!(stats.min_ns <= stats.mean_ns && stats.mean_ns <= stats.max_ns);
+	assert!(session.benchmark(0, inputs![Array::<f32, _>::zeros((1, 16, 16, 3))]?).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn upsample_invalid_input_errors() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_intra_threads(1)?
+		.commit_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+	let input_name = session.inputs[0].name.clone();
+
+	let err = session.run(inputs![Array::<f32, _>::zeros((1, 64, 64, 2))]?).expect_err("wrong shape should fail");
+	assert_eq!(
+		err.kind(),
+		&ErrorKind::ShapeMismatch {
+			input: input_name.clone(),
+			expected: vec![-1, -1, -1, 3],
+			got: vec![1, 64, 64, 2]
+		}
+	);
+	// the diagnosis is prepended to ONNX Runtime's original error message
+	let diagnosis = format!("Input `{input_name}` has shape [1, 64, 64, 2], but the model expects shape [-1, -1, -1, 3]: ");
+	assert!(err.message().starts_with(&diagnosis) && err.message().len() > diagnosis.len());
+
+	let err = session.run(inputs![Array::<i32, _>::zeros((1, 64, 64, 3))]?).expect_err("wrong type should fail");
+	assert!(matches!(err.kind(), ErrorKind::TypeMismatch { input, .. } if *input == input_name));
+
+	let err = session.run(inputs!["not_an_input" => Array::<f32, _>::zeros((1, 64, 64, 3))]?).expect_err("missing input should fail");
+	assert_eq!(err.kind(), &ErrorKind::MissingInput(input_name));
+
+	Ok(())
+}
+
+#[test]
+fn upsample_concurrent_runs() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Session::builder()?
+		.with_intra_threads(1)?
+		.commit_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?;
+
+	std::thread::scope(|s| {
+		let handles: Vec<_> = (0..4)
+			.map(|i| {
+				let session = &session;
+				s.spawn(move || -> ort::Result<()> {
+					let value = i as f32 / 4.0;
+					for _ in 0..8 {
+						let outputs = session.run(inputs![Array::from_elem((1, 16, 16, 3), value)]?)?;
+						let output: ArrayViewD<f32> = outputs[0].try_extract_tensor()?;
+						assert_eq!(output.shape(), [1, 32, 32, 3]);
+						assert!(output.iter().all(|x| *x == value));
+					}
+					Ok(())
+				})
+			})
+			.collect();
+		handles.into_iter().try_for_each(|handle| handle.join().expect("thread panicked"))
+	})
+}
+
+#[test]
+fn upsample_dynamic_batching() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let session = Arc::new(Session::builder()?.commit_from_file(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx"))?);
+	let batcher = DynamicBatcher::new(Arc::clone(&session), 8, Duration::from_millis(50))?;
+
+	std::thread::scope(|s| {
+		let handles: Vec<_> = (0..6)
+			.map(|i| {
+				let batcher = &batcher;
+				s.spawn(move || -> ort::Result<()> {
+					let value = i as f32 / 6.0;
+					// odd requests use a different resolution, so they can't be batched with the even requests
+					let (height, width) = if i % 2 == 0 { (16, 16) } else { (24, 32) };
+					let input = Tensor::from_array(Array::from_elem((1, height, width, 3), value))?;
+					let outputs = tokio_test::block_on(batcher.run(vec![input.into_dyn()])?)?;
+					assert_eq!(outputs.len(), 1);
+					let output: ArrayViewD<f32> = outputs[0].try_extract_tensor()?;
+					assert_eq!(output.shape(), [1, height * 2, width * 2, 3]);
+					assert!(output.iter().all(|x| *x == value));
+					Ok(())
+				})
+			})
+			.collect();
+		handles.into_iter().try_for_each(|handle| handle.join().expect("thread panicked"))
+	})
+}
+
+#[test]
+fn upsample_pipeline() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let model_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx");
+	let first = Session::builder()?.commit_from_file(&model_path)?;
+	let input_name = first.inputs[0].name.clone();
+	let output_name = first.outputs[0].name.clone();
+	let pipeline = SessionPipeline::new()
+		.with_stage("first", first)?
+		.with_stage("second", Session::builder()?.commit_from_file(&model_path)?)?
+		.with_link("first", output_name.as_str(), "second", input_name.as_str())?;
+	assert!(pipeline.stage("second").is_some());
+	assert!(
+		SessionPipeline::new()
+			.with_stage("first", Session::builder()?.commit_from_file(&model_path)?)?
+			.with_stage("first", Session::builder()?.commit_from_file(&model_path)?)
+			.is_err()
+	);
+
+	let input = Tensor::from_array(Array::<f32, _>::zeros((1, 8, 8, 3)))?;
+	let outputs = pipeline.run([(input_name.as_str(), input.into_dyn())])?;
+	assert_eq!(outputs[&output_name].shape()?, [1, 32, 32, 3]);
+
+	Ok(())
+}
+
+#[test]
+fn upsample_try_clone() -> ort::Result<()> {
+	ort::init().with_name("integration_test").commit()?;
+
+	let model_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join("upsample.onnx");
+	let session = Session::builder()?.with_intra_threads(1)?.with_cloning(true)?.commit_from_file(&model_path)?;
+	let clone = session.try_clone()?;
+	// clones can themselves be cloned
+	drop(clone.try_clone()?);
+	std::thread::spawn(move || -> ort::Result<()> {
+		let outputs = clone.run(inputs![Array::<f32, _>::zeros((1, 4, 4, 3))]?)?;
+		assert_eq!(outputs[0].shape()?, [1, 8, 8, 3]);
+		Ok(())
+	})
+	.join()
+	.expect("thread panicked")?;
+
+	let in_memory = Session::builder()?.commit_from_memory(&std::fs::read(&model_path).expect("failed to read model"))?;
+	assert!(in_memory.try_clone().is_err());
+
+	// cloning is opt-in
+	let not_clonable = Session::builder()?.commit_from_file(&model_path)?;
+	assert!(not_clonable.try_clone().is_err());
+
+	Ok(())
+}